@@ -0,0 +1,44 @@
+//! Startup settings read from the environment
+//!
+//! Services take bind addresses and storage paths from `GIX_*` environment
+//! variables, falling back to compiled-in defaults when a variable is unset.
+
+use std::fmt::Display;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Invalid setting errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("Invalid {name} value {value:?}: {reason}")]
+    Invalid {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+/// Parse setting `name` from `lookup`, or `default` when it is unset
+///
+/// `lookup` is usually [`env_lookup`]; tests pass a map instead.
+pub fn setting<T>(
+    lookup: impl Fn(&str) -> Option<String>,
+    name: &str,
+    default: &str,
+) -> Result<T, ConfigError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = lookup(name).unwrap_or_else(|| default.to_string());
+    value.parse().map_err(|e: T::Err| ConfigError::Invalid {
+        name: name.to_string(),
+        value,
+        reason: e.to_string(),
+    })
+}
+
+/// Read a variable from the process environment
+pub fn env_lookup(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
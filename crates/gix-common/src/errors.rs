@@ -8,4 +8,53 @@ pub enum GixError {
     Protocol(String),
     #[error("Internal error: {0}")]
     InternalError(String),
+    #[error("No match: {0}")]
+    NoMatch(String),
+    #[error("Storage locked: {0}")]
+    StorageLocked(String),
+    #[error("Compliance failure: {0}")]
+    Compliance(String),
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+}
+
+/// Compliance error types
+#[derive(Error, Debug, Clone)]
+pub enum ComplianceError {
+    #[error("Precision violation: {0}")]
+    PrecisionViolation(String),
+    #[error("Shape violation: {0}")]
+    ShapeViolation(String),
+    #[error("Residency violation: {0}")]
+    ResidencyViolation(String),
+}
+
+impl From<ComplianceError> for GixError {
+    fn from(err: ComplianceError) -> Self {
+        GixError::Compliance(err.to_string())
+    }
+}
+
+/// Errors parsing identifiers from strings
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IdParseError {
+    #[error("Invalid lane ID {0:?}: expected an integer from 0 to 255")]
+    InvalidLaneId(String),
+    #[error("SLP ID cannot be empty")]
+    EmptySlpId,
+}
+
+impl GixError {
+    /// Stable numeric code identifying the error category
+    pub fn code(&self) -> u32 {
+        match self {
+            GixError::CryptoFailure => 1001,
+            GixError::Protocol(_) => 1002,
+            GixError::InternalError(_) => 1003,
+            GixError::NoMatch(_) => 1004,
+            GixError::StorageLocked(_) => 1005,
+            GixError::Compliance(_) => 1006,
+            GixError::Overloaded(_) => 1007,
+        }
+    }
 }
@@ -1,18 +1,120 @@
+pub mod config;
 pub mod errors;
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 // --- Re-export GixError so it's accessible as gix_common::GixError
-pub use errors::GixError;
+pub use errors::{ComplianceError, GixError, IdParseError};
 
 /// Unique identifier for a compute job (UUID v4)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct JobId(pub [u8; 16]);
 
+/// Key-derivation context prefix for tenant job ids
+const JOB_ID_CONTEXT: &str = "gix-common 2024 JobId v1 tenant:";
+
+impl JobId {
+    /// Deterministic job id for `nonce` within `tenant`'s namespace
+    ///
+    /// Keyed by the tenant, so tenants reusing a nonce never collide, and a
+    /// tenant can recompute the id of any job it submitted.
+    pub fn derive(tenant: &str, nonce: &[u8]) -> JobId {
+        let key = gix_crypto::derive_key(&format!("{}{}", JOB_ID_CONTEXT, tenant), nonce);
+        let mut id = [0u8; 16];
+        id.copy_from_slice(&key[..16]);
+        JobId(id)
+    }
+}
+
 /// Unique identifier for a Sovereign Liquidity Pool
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SlpId(pub String);
 
 /// Lane identifier for AJR routing (e.g., "Flash", "Deep")
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct LaneId(pub u8);
\ No newline at end of file
+pub struct LaneId(pub u8);
+
+impl SlpId {
+    /// Borrow the identifier as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SlpId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for SlpId {
+    type Err = IdParseError;
+
+    /// Accept any non-empty identifier
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(IdParseError::EmptySlpId);
+        }
+        Ok(SlpId(s.to_string()))
+    }
+}
+
+impl LaneId {
+    /// Lane number widened for protobuf fields
+    pub fn as_u32(&self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl fmt::Display for LaneId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for LaneId {
+    type Err = IdParseError;
+
+    /// Parse a lane number (0-255)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(LaneId)
+            .map_err(|_| IdParseError::InvalidLaneId(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lane_id_parse_and_display() {
+        let lane: LaneId = "7".parse().unwrap();
+        assert_eq!(lane, LaneId(7));
+        assert_eq!(lane.as_u32(), 7);
+        assert_eq!(lane.to_string().parse::<LaneId>().unwrap(), lane);
+
+        for bad in ["", "flash", "-1", "256"] {
+            assert_eq!(bad.parse::<LaneId>(), Err(IdParseError::InvalidLaneId(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_job_id_derivation_is_keyed_by_tenant() {
+        let id = JobId::derive("tenant-a", b"job-42");
+        assert_eq!(JobId::derive("tenant-a", b"job-42"), id);
+        assert_ne!(JobId::derive("tenant-a", b"job-43"), id);
+        assert_ne!(JobId::derive("tenant-b", b"job-42"), id);
+    }
+
+    #[test]
+    fn test_slp_id_parse_and_display() {
+        let slp: SlpId = "slp-us-east-1".parse().unwrap();
+        assert_eq!(slp.as_str(), "slp-us-east-1");
+        assert_eq!(slp.to_string().parse::<SlpId>().unwrap(), slp);
+
+        assert_eq!("".parse::<SlpId>(), Err(IdParseError::EmptySlpId));
+    }
+}
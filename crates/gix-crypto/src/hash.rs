@@ -1,12 +1,50 @@
 //! Cryptographic hashing using Blake3
 
 use blake3;
+use subtle::ConstantTimeEq;
 
 /// Hash input data using Blake3, returning a 32-byte hash
 pub fn hash(input: &[u8]) -> [u8; 32] {
     *blake3::hash(input).as_bytes()
 }
 
+/// Incremental Blake3 hasher for payloads that arrive in pieces
+///
+/// Feeding the same bytes through any sequence of `update` calls yields the
+/// same digest as [`hash`] over the concatenation.
+#[derive(Debug, Clone, Default)]
+pub struct Hasher(blake3::Hasher);
+
+impl Hasher {
+    /// Create an empty hasher
+    pub fn new() -> Self {
+        Hasher(blake3::Hasher::new())
+    }
+
+    /// Append input to the hashed stream
+    pub fn update(&mut self, input: &[u8]) -> &mut Self {
+        self.0.update(input);
+        self
+    }
+
+    /// Finish hashing and return the 32-byte digest
+    pub fn finalize(self) -> [u8; 32] {
+        *self.0.finalize().as_bytes()
+    }
+}
+
+/// Allows `std::io::copy` from a reader straight into the hasher
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Hash input data using Blake3 with a key
 pub fn hash_keyed(key: &[u8; 32], input: &[u8]) -> [u8; 32] {
     let mut hasher = blake3::Hasher::new_keyed(key);
@@ -14,6 +52,20 @@ pub fn hash_keyed(key: &[u8; 32], input: &[u8]) -> [u8; 32] {
     *hasher.finalize().as_bytes()
 }
 
+/// Compute a keyed authentication tag (MAC) over a message
+///
+/// Uses Blake3 keyed mode, which is a secure MAC without the HMAC construction.
+pub fn mac(key: &[u8; 32], message: &[u8]) -> [u8; 32] {
+    hash_keyed(key, message)
+}
+
+/// Verify a keyed authentication tag
+///
+/// The tag comparison is constant-time to avoid leaking how many bytes matched.
+pub fn verify_mac(key: &[u8; 32], message: &[u8], tag: &[u8; 32]) -> bool {
+    mac(key, message).ct_eq(tag).into()
+}
+
 /// Derive a key from input using Blake3 key derivation
 ///
 /// The context should be a human-readable, application-specific string identifier.
@@ -46,6 +98,25 @@ mod tests {
         assert_eq!(hash1.len(), 32);
     }
 
+    #[test]
+    fn test_streaming_hash_matches_one_shot() {
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+        for chunk_size in [1, 7, 1024, payload.len()] {
+            let mut hasher = Hasher::new();
+            for chunk in payload.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), hash(&payload));
+        }
+
+        let mut hasher = Hasher::new();
+        std::io::copy(&mut payload.as_slice(), &mut hasher).unwrap();
+        assert_eq!(hasher.finalize(), hash(&payload));
+
+        assert_eq!(Hasher::new().finalize(), hash(b""));
+    }
+
     #[test]
     fn test_hash_keyed() {
         let key = [0u8; 32];
@@ -56,6 +127,37 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[test]
+    fn test_mac_verify() {
+        let key = [7u8; 32];
+        let message = b"intra-cluster message";
+        let tag = mac(&key, message);
+        assert!(verify_mac(&key, message, &tag));
+        assert!(!verify_mac(&key, b"tampered message", &tag));
+    }
+
+    #[test]
+    fn test_mac_wrong_key_mismatch() {
+        let message = b"intra-cluster message";
+        let tag = mac(&[1u8; 32], message);
+        assert!(!verify_mac(&[2u8; 32], message, &tag));
+    }
+
+    #[test]
+    fn test_verify_mac_checks_every_byte() {
+        // ct_eq compares all bytes rather than returning at the first difference,
+        // so a mismatch anywhere in the tag must be rejected
+        let key = [9u8; 32];
+        let message = b"message";
+        let tag = mac(&key, message);
+
+        for i in [0, 15, 31] {
+            let mut tampered = tag;
+            tampered[i] ^= 0x01;
+            assert!(!verify_mac(&key, message, &tampered));
+        }
+    }
+
     #[test]
     fn test_derive_key() {
         let context = "test context";
@@ -1,12 +1,17 @@
 pub mod hash;
+pub mod merkle;
 pub mod pqc;
 pub mod vdf;
+pub mod wallet;
 
 // Re-export commonly used functions
-pub use hash::hash as hash_blake3;
+pub use hash::{derive_key, hash as hash_blake3, mac, verify_mac, Hasher};
+
+// Merkle tree exports
+pub use merkle::{verify_merkle_proof, MerkleError, MerkleProof, MerkleTree, ProofStep};
 
 // VDF exports
-pub use vdf::{evaluate as vdf_evaluate, prove as vdf_prove, verify as vdf_verify, VdfProof, VdfError};
+pub use vdf::{calibrate as vdf_calibrate, evaluate as vdf_evaluate, prove as vdf_prove, verify as vdf_verify, verify_batch as vdf_verify_batch, verify_with_modulus as vdf_verify_with_modulus, VdfProof, VdfError};
 
 // Kyber KEM exports
 pub use pqc::kyber::{
@@ -24,6 +29,7 @@ pub use pqc::kyber::{
 pub use pqc::dilithium::{
     sign_detached as dilithium_sign,
     verify_detached as dilithium_verify,
+    DilithiumLevel,
     KeyPair as DilithiumKeyPair,
     PublicKey as DilithiumPublicKey,
     SecretKey as DilithiumSecretKey,
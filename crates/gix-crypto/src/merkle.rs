@@ -0,0 +1,226 @@
+//! Merkle tree accumulator using Blake3
+//!
+//! Commits to a batch of 32-byte leaf hashes with a single root. Leaf and
+//! internal nodes are hashed with distinct prefixes so a leaf can never be
+//! confused with an internal node. A node without a sibling is promoted to
+//! the next level unchanged.
+
+use crate::hash::hash;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Domain separation prefix for leaf nodes
+const LEAF_PREFIX: u8 = 0x00;
+/// Domain separation prefix for internal nodes
+const NODE_PREFIX: u8 = 0x01;
+
+/// Merkle tree errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MerkleError {
+    #[error("Merkle tree requires at least one leaf")]
+    EmptyTree,
+    #[error("Leaf index {index} out of range for tree with {leaf_count} leaves")]
+    IndexOutOfRange { index: usize, leaf_count: usize },
+}
+
+/// One step of a Merkle inclusion proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Sibling node hash
+    pub sibling: [u8; 32],
+    /// Whether the sibling sits to the left of the current node
+    pub sibling_is_left: bool,
+}
+
+/// Merkle inclusion proof for a single leaf
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Index of the proven leaf
+    pub leaf_index: usize,
+    /// Sibling hashes from the leaf level up to the root
+    pub steps: Vec<ProofStep>,
+}
+
+/// Blake3 Merkle tree over 32-byte leaves
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// Tree levels, from hashed leaves (index 0) up to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+/// Hash a leaf value with the leaf prefix
+fn hash_leaf(leaf: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 33];
+    buf[0] = LEAF_PREFIX;
+    buf[1..].copy_from_slice(leaf);
+    hash(&buf)
+}
+
+/// Hash two child nodes with the internal node prefix
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 65];
+    buf[0] = NODE_PREFIX;
+    buf[1..33].copy_from_slice(left);
+    buf[33..].copy_from_slice(right);
+    hash(&buf)
+}
+
+impl MerkleTree {
+    /// Build a tree from leaf hashes
+    pub fn new(leaves: Vec<[u8; 32]>) -> Result<Self, MerkleError> {
+        if leaves.is_empty() {
+            return Err(MerkleError::EmptyTree);
+        }
+
+        let mut levels = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let next = levels
+                .last()
+                .expect("at least one level")
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(MerkleTree { levels })
+    }
+
+    /// Number of leaves in the tree
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Get the root hash
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().expect("tree has at least one level")[0]
+    }
+
+    /// Build an inclusion proof for the leaf at `index`
+    pub fn proof(&self, index: usize) -> Result<MerkleProof, MerkleError> {
+        if index >= self.leaf_count() {
+            return Err(MerkleError::IndexOutOfRange {
+                index,
+                leaf_count: self.leaf_count(),
+            });
+        }
+
+        let mut steps = Vec::new();
+        let mut position = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_position = position ^ 1;
+            if let Some(sibling) = level.get(sibling_position) {
+                steps.push(ProofStep {
+                    sibling: *sibling,
+                    sibling_is_left: sibling_position < position,
+                });
+            }
+            position /= 2;
+        }
+
+        Ok(MerkleProof {
+            leaf_index: index,
+            steps,
+        })
+    }
+}
+
+/// Verify that `leaf` is included under `root` according to `proof`
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: &[u8; 32], proof: &MerkleProof) -> bool {
+    let computed = proof.steps.iter().fold(hash_leaf(leaf), |node, step| {
+        if step.sibling_is_left {
+            hash_node(&step.sibling, &node)
+        } else {
+            hash_node(&node, &step.sibling)
+        }
+    });
+
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| hash(&[i])).collect()
+    }
+
+    #[test]
+    fn test_single_leaf_tree() {
+        let leaf = hash(b"only job");
+        let tree = MerkleTree::new(vec![leaf]).unwrap();
+
+        assert_eq!(tree.root(), hash_leaf(&leaf));
+        let proof = tree.proof(0).unwrap();
+        assert!(proof.steps.is_empty());
+        assert!(verify_merkle_proof(&tree.root(), &leaf, &proof));
+    }
+
+    #[test]
+    fn test_power_of_two_tree() {
+        let leaves = leaves(8);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(i).unwrap();
+            assert_eq!(proof.steps.len(), 3);
+            assert!(verify_merkle_proof(&tree.root(), leaf, &proof));
+        }
+    }
+
+    #[test]
+    fn test_odd_leaf_count_tree() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(i).unwrap();
+            assert!(verify_merkle_proof(&tree.root(), leaf, &proof));
+        }
+    }
+
+    #[test]
+    fn test_tampered_proof_fails() {
+        let leaves = leaves(6);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+        let mut proof = tree.proof(2).unwrap();
+
+        proof.steps[0].sibling[0] ^= 0xff;
+        assert!(!verify_merkle_proof(&tree.root(), &leaves[2], &proof));
+
+        // A valid proof must not verify a different leaf
+        let proof = tree.proof(2).unwrap();
+        assert!(!verify_merkle_proof(&tree.root(), &leaves[3], &proof));
+    }
+
+    #[test]
+    fn test_leaf_cannot_pose_as_internal_node() {
+        let leaves = leaves(2);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+        // The root of a two-leaf tree is an internal node; it must not verify as a leaf
+        let empty_proof = MerkleProof {
+            leaf_index: 0,
+            steps: Vec::new(),
+        };
+        assert!(!verify_merkle_proof(&tree.root(), &tree.root(), &empty_proof));
+    }
+
+    #[test]
+    fn test_empty_tree_and_out_of_range() {
+        assert_eq!(MerkleTree::new(Vec::new()).unwrap_err(), MerkleError::EmptyTree);
+
+        let tree = MerkleTree::new(leaves(3)).unwrap();
+        assert!(matches!(
+            tree.proof(3),
+            Err(MerkleError::IndexOutOfRange { index: 3, leaf_count: 3 })
+        ));
+    }
+}
@@ -1,9 +1,11 @@
 //! Dilithium Digital Signature - Real implementation
 //!
-//! This module provides post-quantum digital signatures using Dilithium3.
+//! This module provides post-quantum digital signatures using Dilithium at
+//! NIST security levels 2, 3 and 5 (level 3 by default).
 //! It wraps the pqcrypto-dilithium library for use in GIX.
 
-use pqcrypto_dilithium::dilithium3;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use pqcrypto_dilithium::{dilithium2, dilithium3, dilithium5};
 use pqcrypto_traits::sign::{DetachedSignature as DetachedSignatureTrait, PublicKey as PublicKeyTrait, SecretKey as SecretKeyTrait};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -21,6 +23,107 @@ pub enum SignatureError {
     InvalidKeySize { expected: usize, actual: usize },
     #[error("Invalid signature size: expected {expected}, got {actual}")]
     InvalidSignatureSize { expected: usize, actual: usize },
+    #[error("Unsupported Dilithium level: {0}")]
+    UnsupportedLevel(u8),
+    #[error("Invalid armored key: {0}")]
+    InvalidArmor(String),
+}
+
+/// Armor header for exported public keys
+const ARMOR_BEGIN: &str = "-----BEGIN GIX DILITHIUM PUBLIC KEY-----";
+/// Armor footer for exported public keys
+const ARMOR_END: &str = "-----END GIX DILITHIUM PUBLIC KEY-----";
+/// Base64 line width inside an armored block
+const ARMOR_LINE_WIDTH: usize = 64;
+
+/// Dilithium security level
+///
+/// Serialized as its NIST level number (2, 3 or 5).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum DilithiumLevel {
+    /// Dilithium2 (NIST level 2, smallest signatures)
+    Level2,
+    /// Dilithium3 (NIST level 3)
+    #[default]
+    Level3,
+    /// Dilithium5 (NIST level 5, highest security)
+    Level5,
+}
+
+impl DilithiumLevel {
+    /// Public key size in bytes
+    pub fn public_key_bytes(&self) -> usize {
+        match self {
+            DilithiumLevel::Level2 => dilithium2::public_key_bytes(),
+            DilithiumLevel::Level3 => dilithium3::public_key_bytes(),
+            DilithiumLevel::Level5 => dilithium5::public_key_bytes(),
+        }
+    }
+
+    /// Secret key size in bytes
+    pub fn secret_key_bytes(&self) -> usize {
+        match self {
+            DilithiumLevel::Level2 => dilithium2::secret_key_bytes(),
+            DilithiumLevel::Level3 => dilithium3::secret_key_bytes(),
+            DilithiumLevel::Level5 => dilithium5::secret_key_bytes(),
+        }
+    }
+
+    /// Signature size in bytes
+    pub fn signature_bytes(&self) -> usize {
+        match self {
+            DilithiumLevel::Level2 => dilithium2::signature_bytes(),
+            DilithiumLevel::Level3 => dilithium3::signature_bytes(),
+            DilithiumLevel::Level5 => dilithium5::signature_bytes(),
+        }
+    }
+
+    /// Algorithm name (e.g. "Dilithium3")
+    pub fn name(&self) -> &'static str {
+        match self {
+            DilithiumLevel::Level2 => "Dilithium2",
+            DilithiumLevel::Level3 => "Dilithium3",
+            DilithiumLevel::Level5 => "Dilithium5",
+        }
+    }
+}
+
+impl TryFrom<u8> for DilithiumLevel {
+    type Error = SignatureError;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        match level {
+            2 => Ok(DilithiumLevel::Level2),
+            3 => Ok(DilithiumLevel::Level3),
+            5 => Ok(DilithiumLevel::Level5),
+            other => Err(SignatureError::UnsupportedLevel(other)),
+        }
+    }
+}
+
+impl From<DilithiumLevel> for u8 {
+    fn from(level: DilithiumLevel) -> u8 {
+        match level {
+            DilithiumLevel::Level2 => 2,
+            DilithiumLevel::Level3 => 3,
+            DilithiumLevel::Level5 => 5,
+        }
+    }
+}
+
+impl std::fmt::Display for DilithiumLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Check that a key has the expected size for its level
+fn check_key_size(expected: usize, actual: usize) -> Result<(), SignatureError> {
+    if expected != actual {
+        return Err(SignatureError::InvalidKeySize { expected, actual });
+    }
+    Ok(())
 }
 
 /// Dilithium public key
@@ -28,19 +131,21 @@ pub enum SignatureError {
 pub struct PublicKey {
     /// Public key bytes
     pub bytes: Vec<u8>,
+    /// Security level (keys serialized before levels existed are level 3)
+    #[serde(default)]
+    pub level: DilithiumLevel,
 }
 
 impl PublicKey {
-    /// Create from bytes
+    /// Create a Dilithium3 public key from bytes
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SignatureError> {
-        let expected_size = dilithium3::public_key_bytes();
-        if bytes.len() != expected_size {
-            return Err(SignatureError::InvalidKeySize {
-                expected: expected_size,
-                actual: bytes.len(),
-            });
-        }
-        Ok(PublicKey { bytes })
+        Self::from_bytes_with_level(bytes, DilithiumLevel::Level3)
+    }
+
+    /// Create from bytes at the given security level
+    pub fn from_bytes_with_level(bytes: Vec<u8>, level: DilithiumLevel) -> Result<Self, SignatureError> {
+        check_key_size(level.public_key_bytes(), bytes.len())?;
+        Ok(PublicKey { bytes, level })
     }
 
     /// Get the bytes
@@ -48,9 +153,59 @@ impl PublicKey {
         &self.bytes
     }
 
-    /// Convert to pqcrypto PublicKey type
-    fn to_pqcrypto(&self) -> dilithium3::PublicKey {
-        dilithium3::PublicKey::from_bytes(&self.bytes).expect("Valid public key bytes")
+    /// Export as a labeled base64 block for sharing
+    pub fn to_armored(&self) -> String {
+        let encoded = BASE64.encode(&self.bytes);
+        let mut armored = String::from(ARMOR_BEGIN);
+        armored.push('\n');
+        for line in encoded.as_bytes().chunks(ARMOR_LINE_WIDTH) {
+            armored.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+            armored.push('\n');
+        }
+        armored.push_str(ARMOR_END);
+        armored.push('\n');
+        armored
+    }
+
+    /// Parse a labeled base64 block produced by `to_armored`
+    ///
+    /// The security level is inferred from the decoded key size.
+    pub fn from_armored(armored: &str) -> Result<Self, SignatureError> {
+        let mut lines = armored.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        if lines.next() != Some(ARMOR_BEGIN) {
+            return Err(SignatureError::InvalidArmor("missing BEGIN line".to_string()));
+        }
+
+        let mut body = String::new();
+        let mut terminated = false;
+        for line in lines.by_ref() {
+            if line == ARMOR_END {
+                terminated = true;
+                break;
+            }
+            body.push_str(line);
+        }
+
+        if !terminated {
+            return Err(SignatureError::InvalidArmor("missing END line".to_string()));
+        }
+        if lines.next().is_some() {
+            return Err(SignatureError::InvalidArmor("trailing data after END line".to_string()));
+        }
+
+        let bytes = BASE64
+            .decode(body)
+            .map_err(|e| SignatureError::InvalidArmor(e.to_string()))?;
+
+        let level = [DilithiumLevel::Level2, DilithiumLevel::Level3, DilithiumLevel::Level5]
+            .into_iter()
+            .find(|level| level.public_key_bytes() == bytes.len());
+
+        match level {
+            Some(level) => Self::from_bytes_with_level(bytes, level),
+            None => Self::from_bytes(bytes),
+        }
     }
 }
 
@@ -59,30 +214,27 @@ impl PublicKey {
 pub struct SecretKey {
     /// Secret key bytes
     pub bytes: Vec<u8>,
+    /// Security level (keys serialized before levels existed are level 3)
+    #[serde(default)]
+    pub level: DilithiumLevel,
 }
 
 impl SecretKey {
-    /// Create from bytes
+    /// Create a Dilithium3 secret key from bytes
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SignatureError> {
-        let expected_size = dilithium3::secret_key_bytes();
-        if bytes.len() != expected_size {
-            return Err(SignatureError::InvalidKeySize {
-                expected: expected_size,
-                actual: bytes.len(),
-            });
-        }
-        Ok(SecretKey { bytes })
+        Self::from_bytes_with_level(bytes, DilithiumLevel::Level3)
+    }
+
+    /// Create from bytes at the given security level
+    pub fn from_bytes_with_level(bytes: Vec<u8>, level: DilithiumLevel) -> Result<Self, SignatureError> {
+        check_key_size(level.secret_key_bytes(), bytes.len())?;
+        Ok(SecretKey { bytes, level })
     }
 
     /// Get the bytes
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
-
-    /// Convert to pqcrypto SecretKey type
-    fn to_pqcrypto(&self) -> dilithium3::SecretKey {
-        dilithium3::SecretKey::from_bytes(&self.bytes).expect("Valid secret key bytes")
-    }
 }
 
 /// Dilithium key pair
@@ -97,17 +249,36 @@ pub struct KeyPair {
 impl KeyPair {
     /// Generate a new key pair using Dilithium3
     pub fn generate() -> Self {
-        let (pk, sk) = dilithium3::keypair();
-        
+        Self::generate_with_level(DilithiumLevel::Level3)
+    }
+
+    /// Generate a new key pair at the given security level
+    pub fn generate_with_level(level: DilithiumLevel) -> Self {
+        let (public, secret) = match level {
+            DilithiumLevel::Level2 => {
+                let (pk, sk) = dilithium2::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            DilithiumLevel::Level3 => {
+                let (pk, sk) = dilithium3::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            DilithiumLevel::Level5 => {
+                let (pk, sk) = dilithium5::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+        };
+
         KeyPair {
-            public: PublicKey {
-                bytes: pk.as_bytes().to_vec(),
-            },
-            secret: SecretKey {
-                bytes: sk.as_bytes().to_vec(),
-            },
+            public: PublicKey { bytes: public, level },
+            secret: SecretKey { bytes: secret, level },
         }
     }
+
+    /// Get the security level of this key pair
+    pub fn level(&self) -> DilithiumLevel {
+        self.public.level
+    }
 }
 
 /// Dilithium signature
@@ -118,9 +289,14 @@ pub struct Signature {
 }
 
 impl Signature {
-    /// Create from bytes
+    /// Create a Dilithium3 signature from bytes
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SignatureError> {
-        let expected_size = dilithium3::signature_bytes();
+        Self::from_bytes_with_level(bytes, DilithiumLevel::Level3)
+    }
+
+    /// Create from bytes, checking the size for the given security level
+    pub fn from_bytes_with_level(bytes: Vec<u8>, level: DilithiumLevel) -> Result<Self, SignatureError> {
+        let expected_size = level.signature_bytes();
         if bytes.len() != expected_size {
             return Err(SignatureError::InvalidSignatureSize {
                 expected: expected_size,
@@ -134,11 +310,6 @@ impl Signature {
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
-
-    /// Convert to pqcrypto DetachedSignature type
-    fn to_pqcrypto(&self) -> dilithium3::DetachedSignature {
-        dilithium3::DetachedSignature::from_bytes(&self.bytes).expect("Valid signature bytes")
-    }
 }
 
 /// Sign a message using a secret key
@@ -150,12 +321,29 @@ impl Signature {
 /// # Returns
 /// A detached signature on success
 pub fn sign_detached(message: &[u8], secret_key: &SecretKey) -> Result<Signature, SignatureError> {
-    let sk = secret_key.to_pqcrypto();
-    let sig = dilithium3::detached_sign(message, &sk);
-    
-    Ok(Signature {
-        bytes: sig.as_bytes().to_vec(),
-    })
+    let level = secret_key.level;
+    check_key_size(level.secret_key_bytes(), secret_key.bytes.len())?;
+    let invalid_key = |_| SignatureError::InvalidKeySize {
+        expected: level.secret_key_bytes(),
+        actual: secret_key.bytes.len(),
+    };
+
+    let bytes = match level {
+        DilithiumLevel::Level2 => {
+            let sk = dilithium2::SecretKey::from_bytes(&secret_key.bytes).map_err(invalid_key)?;
+            dilithium2::detached_sign(message, &sk).as_bytes().to_vec()
+        }
+        DilithiumLevel::Level3 => {
+            let sk = dilithium3::SecretKey::from_bytes(&secret_key.bytes).map_err(invalid_key)?;
+            dilithium3::detached_sign(message, &sk).as_bytes().to_vec()
+        }
+        DilithiumLevel::Level5 => {
+            let sk = dilithium5::SecretKey::from_bytes(&secret_key.bytes).map_err(invalid_key)?;
+            dilithium5::detached_sign(message, &sk).as_bytes().to_vec()
+        }
+    };
+
+    Ok(Signature { bytes })
 }
 
 /// Verify a detached signature
@@ -172,12 +360,45 @@ pub fn verify_detached(
     signature: &Signature,
     public_key: &PublicKey,
 ) -> Result<(), SignatureError> {
-    let pk = public_key.to_pqcrypto();
-    let sig = signature.to_pqcrypto();
-    
-    dilithium3::verify_detached_signature(&sig, message, &pk)
-        .map_err(|_| SignatureError::VerificationFailed)?;
-    
+    let level = public_key.level;
+    check_key_size(level.public_key_bytes(), public_key.bytes.len())?;
+
+    // A signature from a different level can never verify
+    if signature.bytes.len() != level.signature_bytes() {
+        return Err(SignatureError::VerificationFailed);
+    }
+
+    let verified = match level {
+        DilithiumLevel::Level2 => {
+            let pk = dilithium2::PublicKey::from_bytes(&public_key.bytes);
+            let sig = dilithium2::DetachedSignature::from_bytes(&signature.bytes);
+            match (pk, sig) {
+                (Ok(pk), Ok(sig)) => dilithium2::verify_detached_signature(&sig, message, &pk).is_ok(),
+                _ => false,
+            }
+        }
+        DilithiumLevel::Level3 => {
+            let pk = dilithium3::PublicKey::from_bytes(&public_key.bytes);
+            let sig = dilithium3::DetachedSignature::from_bytes(&signature.bytes);
+            match (pk, sig) {
+                (Ok(pk), Ok(sig)) => dilithium3::verify_detached_signature(&sig, message, &pk).is_ok(),
+                _ => false,
+            }
+        }
+        DilithiumLevel::Level5 => {
+            let pk = dilithium5::PublicKey::from_bytes(&public_key.bytes);
+            let sig = dilithium5::DetachedSignature::from_bytes(&signature.bytes);
+            match (pk, sig) {
+                (Ok(pk), Ok(sig)) => dilithium5::verify_detached_signature(&sig, message, &pk).is_ok(),
+                _ => false,
+            }
+        }
+    };
+
+    if !verified {
+        return Err(SignatureError::VerificationFailed);
+    }
+
     Ok(())
 }
 
@@ -251,6 +472,94 @@ mod tests {
         // Deserialized signature should still verify
         verify_detached(message, &deserialized, &keypair.public).expect("Verification failed");
     }
-}
 
+    #[test]
+    fn test_dilithium_all_levels_sign_and_verify() {
+        for level in [DilithiumLevel::Level2, DilithiumLevel::Level3, DilithiumLevel::Level5] {
+            let keypair = KeyPair::generate_with_level(level);
+            assert_eq!(keypair.level(), level);
+            assert_eq!(keypair.public.bytes.len(), level.public_key_bytes());
+            assert_eq!(keypair.secret.bytes.len(), level.secret_key_bytes());
+
+            let message = b"Level test message";
+            let signature = sign_detached(message, &keypair.secret).unwrap();
+            assert_eq!(signature.bytes.len(), level.signature_bytes());
+            verify_detached(message, &signature, &keypair.public).expect("Verification failed");
+        }
+    }
 
+    #[test]
+    fn test_dilithium_cross_level_verification_fails() {
+        let keypair2 = KeyPair::generate_with_level(DilithiumLevel::Level2);
+        let keypair5 = KeyPair::generate_with_level(DilithiumLevel::Level5);
+        let message = b"Test message";
+
+        let signature = sign_detached(message, &keypair2.secret).unwrap();
+        assert!(verify_detached(message, &signature, &keypair5.public).is_err());
+
+        let signature = sign_detached(message, &keypair5.secret).unwrap();
+        assert!(verify_detached(message, &signature, &keypair2.public).is_err());
+    }
+
+    #[test]
+    fn test_dilithium_legacy_keypair_defaults_to_level3() {
+        let keypair = KeyPair::generate();
+        let legacy_json = format!(
+            r#"{{"public":{{"bytes":{:?}}},"secret":{{"bytes":{:?}}}}}"#,
+            keypair.public.bytes, keypair.secret.bytes
+        );
+
+        let loaded: KeyPair = serde_json::from_str(&legacy_json).unwrap();
+        assert_eq!(loaded.level(), DilithiumLevel::Level3);
+
+        let signature = sign_detached(b"legacy", &loaded.secret).unwrap();
+        verify_detached(b"legacy", &signature, &loaded.public).expect("Verification failed");
+    }
+
+    #[test]
+    fn test_dilithium_level_serialization() {
+        assert_eq!(serde_json::to_string(&DilithiumLevel::Level5).unwrap(), "5");
+        assert_eq!(serde_json::from_str::<DilithiumLevel>("2").unwrap(), DilithiumLevel::Level2);
+        assert!(serde_json::from_str::<DilithiumLevel>("4").is_err());
+    }
+
+    #[test]
+    fn test_public_key_armor_roundtrip() {
+        for level in [DilithiumLevel::Level2, DilithiumLevel::Level3, DilithiumLevel::Level5] {
+            let keypair = KeyPair::generate_with_level(level);
+            let armored = keypair.public.to_armored();
+
+            assert!(armored.starts_with(ARMOR_BEGIN));
+            assert!(armored.trim_end().ends_with(ARMOR_END));
+            assert!(armored.lines().all(|line| line.len() <= ARMOR_LINE_WIDTH || line.starts_with("-----")));
+
+            let parsed = PublicKey::from_armored(&armored).unwrap();
+            assert_eq!(parsed, keypair.public);
+        }
+    }
+
+    #[test]
+    fn test_public_key_armor_rejects_malformed() {
+        let keypair = KeyPair::generate();
+        let armored = keypair.public.to_armored();
+
+        // Missing header
+        let no_header = armored.replacen(ARMOR_BEGIN, "", 1);
+        assert!(matches!(PublicKey::from_armored(&no_header), Err(SignatureError::InvalidArmor(_))));
+
+        // Missing footer
+        let no_footer = armored.replacen(ARMOR_END, "", 1);
+        assert!(matches!(PublicKey::from_armored(&no_footer), Err(SignatureError::InvalidArmor(_))));
+
+        // Invalid base64
+        let bad_body = format!("{}\n!!!not base64!!!\n{}\n", ARMOR_BEGIN, ARMOR_END);
+        assert!(matches!(PublicKey::from_armored(&bad_body), Err(SignatureError::InvalidArmor(_))));
+
+        // Valid base64 but wrong key size
+        let short = format!("{}\n{}\n{}\n", ARMOR_BEGIN, BASE64.encode([0u8; 32]), ARMOR_END);
+        assert!(matches!(
+            PublicKey::from_armored(&short),
+            Err(SignatureError::InvalidKeySize { actual: 32, .. })
+        ));
+    }
+}
@@ -4,9 +4,26 @@
 //! Note: VDF computation is intentionally slow and cannot be parallelized.
 
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use vdf::{VDFParams, WesolowskiVDFParams, VDF};
 
+/// RSA-equivalent class group size used when proving, and expected when verifying
+pub const DEFAULT_MODULUS_BITS: u16 = 2048;
+
+/// Challenge evaluated by every calibration sample, so all share one setup cost
+const CALIBRATION_INPUT: &[u8] = b"gix-vdf-calibration";
+/// Iteration count of the first calibration sample
+const CALIBRATION_START_ITERATIONS: u64 = 256;
+/// Factor between the iteration counts of successive calibration samples
+const CALIBRATION_GROWTH: u64 = 4;
+/// Sample differences shorter than this are too noisy to extrapolate from
+const CALIBRATION_MIN_SAMPLE: Duration = Duration::from_millis(50);
+
+fn default_modulus_bits() -> u16 {
+    DEFAULT_MODULUS_BITS
+}
+
 /// VDF errors
 #[derive(Error, Debug)]
 pub enum VdfError {
@@ -25,6 +42,9 @@ pub struct VdfProof {
     pub output: Vec<u8>,
     /// Number of iterations (difficulty)
     pub iterations: u64,
+    /// Class group size the proof was computed with
+    #[serde(default = "default_modulus_bits")]
+    pub modulus_bits: u16,
 }
 
 impl VdfProof {
@@ -33,6 +53,7 @@ impl VdfProof {
         VdfProof {
             output,
             iterations,
+            modulus_bits: DEFAULT_MODULUS_BITS,
         }
     }
 
@@ -60,7 +81,7 @@ pub fn evaluate(input: &[u8], iterations: u64) -> Result<Vec<u8>, VdfError> {
     
     // Create VDF parameters with appropriate security level
     // Using 2048-bit RSA modulus for security
-    let params = WesolowskiVDFParams(2048).new();
+    let params = WesolowskiVDFParams(DEFAULT_MODULUS_BITS).new();
     
     // Solve the VDF (this is the slow part!)
     let result = params.solve(challenge.as_bytes(), iterations)
@@ -85,7 +106,7 @@ pub fn prove(input: &[u8], iterations: u64) -> Result<VdfProof, VdfError> {
     let challenge = blake3::hash(input);
     
     // Create VDF parameters
-    let params = WesolowskiVDFParams(2048).new();
+    let params = WesolowskiVDFParams(DEFAULT_MODULUS_BITS).new();
     
     // Solve the VDF
     let output = params.solve(challenge.as_bytes(), iterations)
@@ -106,11 +127,22 @@ pub fn prove(input: &[u8], iterations: u64) -> Result<VdfProof, VdfError> {
 /// # Note
 /// Verification requires recomputing the VDF (the vdf crate v0.1.0 doesn't have separate verify).
 pub fn verify(input: &[u8], vdf_proof: &VdfProof) -> bool {
+    verify_with_modulus(input, vdf_proof, DEFAULT_MODULUS_BITS)
+}
+
+/// Verify a VDF proof computed with a `modulus_bits` class group
+///
+/// The verifier, not the proof, decides the class group size: a smaller one
+/// is cheaper to prove and a larger one more expensive to check, so proofs
+/// declaring any other size are rejected without evaluating them.
+pub fn verify_with_modulus(input: &[u8], vdf_proof: &VdfProof, modulus_bits: u16) -> bool {
+    if vdf_proof.modulus_bits != modulus_bits {
+        return false;
+    }
+
     // Convert input to challenge format
     let challenge = blake3::hash(input);
-    
-    // Create VDF parameters (must match those used for proving)
-    let params = WesolowskiVDFParams(2048).new();
+    let params = WesolowskiVDFParams(modulus_bits).new();
     
     // Verify by recomputing and comparing outputs
     match params.solve(challenge.as_bytes(), vdf_proof.iterations) {
@@ -119,6 +151,68 @@ pub fn verify(input: &[u8], vdf_proof: &VdfProof) -> bool {
     }
 }
 
+/// Estimate the iteration count that takes `target_ms` to evaluate
+///
+/// An evaluation costs a fixed setup (deriving the class group from the
+/// challenge) plus a per-iteration cost, and the setup dominates small
+/// counts. Times [`evaluate`] on one challenge at growing iteration counts
+/// until the difference between two samples clearly exceeds the noise, then
+/// fits both costs and solves for the target. Nothing is cached: every call
+/// measures again, so the result follows the current load of the machine.
+/// Targets below the setup cost return 1.
+pub fn calibrate(target_ms: u64) -> u64 {
+    calibrate_with(target_ms, |iterations| {
+        let start = Instant::now();
+        evaluate(CALIBRATION_INPUT, iterations).ok()?;
+        Some(start.elapsed())
+    })
+}
+
+/// [`calibrate`] against `sample`, which times one evaluation of the given iteration count
+fn calibrate_with(target_ms: u64, mut sample: impl FnMut(u64) -> Option<Duration>) -> u64 {
+    let target = Duration::from_millis(target_ms);
+    let mut low = CALIBRATION_START_ITERATIONS;
+    let Some(mut low_elapsed) = sample(low) else {
+        // Nothing to extrapolate from; the first count is the best guess
+        return low;
+    };
+
+    loop {
+        let high = low.saturating_mul(CALIBRATION_GROWTH);
+        let Some(high_elapsed) = sample(high) else {
+            return low;
+        };
+        let spread = high_elapsed.saturating_sub(low_elapsed);
+
+        // The spread must stand out from run-to-run jitter in the setup
+        if spread >= CALIBRATION_MIN_SAMPLE.max(low_elapsed / 2) || high >= u64::MAX / CALIBRATION_GROWTH {
+            let per_iteration = spread.as_nanos().max(1) as f64 / (high - low) as f64;
+            let setup = (low_elapsed.as_nanos() as f64 - per_iteration * low as f64).max(0.0);
+            let estimate = (target.as_nanos() as f64 - setup) / per_iteration;
+            return (estimate as u64).max(1);
+        }
+        low = high;
+        low_elapsed = high_elapsed;
+    }
+}
+
+/// Verify a batch of (input, proof) pairs
+///
+/// Returns one result per item, in order, so callers can reject only the
+/// failing ones. With the `parallel` feature the items are verified on the
+/// rayon thread pool.
+pub fn verify_batch(items: &[(Vec<u8>, VdfProof)]) -> Vec<bool> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(|(input, proof)| verify(input, proof)).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(|(input, proof)| verify(input, proof)).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +289,82 @@ mod tests {
         // Deserialized proof should still verify
         assert!(verify(input, &deserialized));
     }
+
+    #[test]
+    fn test_vdf_verify_batch() {
+        let iterations = 1000;
+        let valid_a = (b"job a".to_vec(), prove(b"job a", iterations).unwrap());
+        let valid_b = (b"job b".to_vec(), prove(b"job b", iterations).unwrap());
+
+        let mut tampered = valid_a.clone();
+        tampered.1.output[0] ^= 0xff;
+        let wrong_input = (b"job c".to_vec(), valid_b.1.clone());
+        let mut bad_modulus = valid_b.clone();
+        bad_modulus.1.modulus_bits = 64;
+
+        let items = vec![valid_a, tampered, valid_b, wrong_input, bad_modulus];
+        assert_eq!(verify_batch(&items), vec![true, false, true, false, false]);
+        assert!(verify_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_vdf_calibrate_grows_with_target() {
+        // Modelled cost: 300ms setup plus 20us per iteration, no real timing
+        let model = |iterations: u64| Some(Duration::from_millis(300) + Duration::from_micros(20 * iterations));
+
+        let estimates: Vec<u64> = [200, 1_000, 5_000, 20_000].iter().map(|&ms| calibrate_with(ms, model)).collect();
+        assert_eq!(estimates[0], 1, "targets below the setup cost");
+        assert!(estimates.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", estimates);
+
+        // The fit recovers the modelled per-iteration cost
+        let expected = (5_000 - 300) * 1_000 / 20;
+        assert!(estimates[2].abs_diff(expected) <= expected / 100, "{} vs {}", estimates[2], expected);
+    }
+
+    #[test]
+    #[ignore = "takes seconds of wall-clock time; run with --ignored on an idle machine"]
+    fn test_vdf_calibrate_hits_target() {
+        // Comfortably above the setup cost of one evaluation, even unoptimized
+        let target_ms = 6000;
+        let iterations = calibrate(target_ms);
+        assert!(iterations > 1);
+
+        let start = Instant::now();
+        evaluate(b"calibrated", iterations).unwrap();
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        // Generous band: timing on shared CI machines is noisy
+        assert!(
+            (target_ms / 3..=target_ms * 3).contains(&elapsed),
+            "{} iterations took {}ms, target {}ms",
+            iterations,
+            elapsed,
+            target_ms
+        );
+    }
+
+    #[test]
+    fn test_vdf_verifier_sets_the_modulus() {
+        let input = b"test input";
+        let challenge = blake3::hash(input);
+        let output = WesolowskiVDFParams(1024).new().solve(challenge.as_bytes(), 1000).unwrap();
+        let cheap = VdfProof { output: output.to_vec(), iterations: 1000, modulus_bits: 1024 };
+
+        // A valid proof over a smaller group than configured is refused
+        assert!(!verify(input, &cheap));
+        assert!(verify_with_modulus(input, &cheap, 1024));
+
+        // As is a default proof relabelled with another size
+        let mut relabelled = prove(input, 1000).unwrap();
+        relabelled.modulus_bits = 4096;
+        assert!(!verify(input, &relabelled));
+        assert!(!verify_with_modulus(input, &relabelled, 4096));
+    }
+
+    #[test]
+    fn test_vdf_proof_defaults_modulus_bits() {
+        // Proofs serialized before the modulus was recorded
+        let proof: VdfProof = serde_json::from_str(r#"{"output":[1,2,3],"iterations":10}"#).unwrap();
+        assert_eq!(proof.modulus_bits, DEFAULT_MODULUS_BITS);
+    }
 }
@@ -0,0 +1,108 @@
+//! Wallet file encoding
+//!
+//! A wallet holds a Dilithium keypair, stored as pretty JSON by default or as
+//! a compact bincode encoding behind a magic prefix for `.gwl` files. Loading
+//! detects the encoding from the content, so either can be read from any path.
+
+use crate::pqc::dilithium::KeyPair;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// Prefix identifying a binary wallet file
+pub const BINARY_WALLET_MAGIC: &[u8; 4] = b"GWL\x01";
+
+/// File extension that selects the binary wallet format
+pub const BINARY_WALLET_EXTENSION: &str = "gwl";
+
+/// Wallet version written by [`encode_wallet`]
+pub const WALLET_VERSION: u32 = 1;
+
+/// Wallet encoding errors
+#[derive(Error, Debug)]
+pub enum WalletError {
+    #[error("Invalid wallet JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid binary wallet: {0}")]
+    Binary(#[from] bincode::Error),
+    #[error("Unsupported wallet version: {0}. Expected version {WALLET_VERSION}.")]
+    UnsupportedVersion(u32),
+}
+
+/// On-disk wallet encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WalletFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// `BINARY_WALLET_MAGIC` followed by the bincode-encoded wallet
+    Binary,
+}
+
+impl WalletFormat {
+    /// Format implied by a path's extension: binary for `.gwl`, else JSON
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(BINARY_WALLET_EXTENSION) => WalletFormat::Binary,
+            _ => WalletFormat::Json,
+        }
+    }
+}
+
+/// Wallet structure stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Wallet {
+    /// Version for future compatibility
+    pub version: u32,
+    /// Dilithium keypair
+    pub keypair: KeyPair,
+}
+
+/// Encode a keypair as a wallet file in the given format
+pub fn encode_wallet(keypair: &KeyPair, format: WalletFormat) -> Result<Vec<u8>, WalletError> {
+    let wallet = Wallet {
+        version: WALLET_VERSION,
+        keypair: keypair.clone(),
+    };
+    match format {
+        WalletFormat::Json => Ok(serde_json::to_vec_pretty(&wallet)?),
+        WalletFormat::Binary => {
+            let mut bytes = BINARY_WALLET_MAGIC.to_vec();
+            bincode::serialize_into(&mut bytes, &wallet)?;
+            Ok(bytes)
+        }
+    }
+}
+
+/// Decode a wallet file, detecting its format from the magic prefix
+pub fn decode_wallet(bytes: &[u8]) -> Result<KeyPair, WalletError> {
+    let wallet: Wallet = match bytes.strip_prefix(BINARY_WALLET_MAGIC.as_slice()) {
+        Some(encoded) => bincode::deserialize(encoded)?,
+        None => serde_json::from_slice(bytes)?,
+    };
+    if wallet.version != WALLET_VERSION {
+        return Err(WalletError::UnsupportedVersion(wallet.version));
+    }
+    Ok(wallet.keypair)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_detects_either_format() {
+        let keypair = KeyPair::generate();
+        for format in [WalletFormat::Json, WalletFormat::Binary] {
+            let bytes = encode_wallet(&keypair, format).unwrap();
+            assert_eq!(bytes.starts_with(BINARY_WALLET_MAGIC), format == WalletFormat::Binary);
+            let decoded = decode_wallet(&bytes).unwrap();
+            assert_eq!(decoded.public, keypair.public);
+            assert_eq!(decoded.secret, keypair.secret);
+        }
+
+        let future = serde_json::json!({ "version": 2, "keypair": keypair });
+        let err = decode_wallet(future.to_string().as_bytes()).unwrap_err();
+        assert!(matches!(err, WalletError::UnsupportedVersion(2)));
+    }
+}
@@ -0,0 +1,77 @@
+//! Time sources for expiry checks
+//!
+//! Expiry logic takes the current time as an explicit argument; a `Clock`
+//! supplies it. Services use `SystemClock`, tests use `MockClock` to hit
+//! exact boundaries without sleeping.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of the current Unix time in seconds
+pub trait Clock: Send + Sync {
+    /// Current time in seconds since the Unix epoch
+    fn now_secs(&self) -> u64;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    /// Returns 0 if the system clock is set before the Unix epoch
+    fn now_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// Manually controlled clock for tests
+#[derive(Debug, Default)]
+pub struct MockClock(AtomicU64);
+
+impl MockClock {
+    /// Create a clock stopped at `now_secs`
+    pub fn new(now_secs: u64) -> Self {
+        MockClock(AtomicU64::new(now_secs))
+    }
+
+    /// Move the clock to `now_secs`
+    pub fn set(&self, now_secs: u64) {
+        self.0.store(now_secs, Ordering::SeqCst);
+    }
+
+    /// Move the clock forward by `secs`
+    pub fn advance(&self, secs: u64) {
+        self.0.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_secs(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_moves_only_when_told() {
+        let clock = MockClock::new(1_000);
+        assert_eq!(clock.now_secs(), 1_000);
+
+        clock.advance(5);
+        assert_eq!(clock.now_secs(), 1_005);
+
+        clock.set(10);
+        assert_eq!(clock.now_secs(), 10);
+    }
+
+    #[test]
+    fn test_system_clock_is_after_epoch() {
+        assert!(SystemClock.now_secs() > 0);
+    }
+}
@@ -3,14 +3,65 @@
 //! This crate defines the schema, validators, and serialization for GXF,
 //! the standardized format for job execution envelopes in the GIX system.
 
-use gix_common::JobId;
+pub mod clock;
+pub mod limits;
+
+pub use clock::{Clock, MockClock, SystemClock};
+pub use limits::ParseLimits;
+
+use gix_common::{JobId, LaneId, SlpId};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 /// GXF schema version constant
 pub const GXF_VERSION: u8 = 3;
 
+/// Largest envelope payload accepted by `validate` (1 MiB)
+pub const MAX_PAYLOAD_BYTES: usize = 1 << 20;
+
+/// Default price added per KV-cache sequence position
+pub const DEFAULT_SEQ_LEN_RATE: u64 = 10;
+
+/// Hash bytes kept in an envelope fingerprint (hex-encoded to twice as many characters)
+const FINGERPRINT_BYTES: usize = 8;
+
+/// Most entries a job's `parameters` map may hold
+///
+/// Enforced by `validate`. The parse-time [`ParseLimits::max_entries`] is a
+/// looser bound on every JSON object, there to cap the cost of
+/// deserializing untrusted input before any job is checked.
+pub const MAX_PARAMETERS: usize = 64;
+
+/// Longest job parameter value accepted by `validate`, in bytes
+pub const MAX_PARAMETER_VALUE_BYTES: usize = 4096;
+
+/// Default limit on a JSON-encoded envelope accepted by services (4 MiB)
+///
+/// JSON spends up to four bytes per payload byte, so this admits a
+/// maximum-size payload. It matches tonic's default message size limit.
+pub const DEFAULT_MAX_ENVELOPE_BYTES: usize = 4 << 20;
+
+/// v2 `additional_fields` key holding the target lane (v3: `target_lane`)
+const V2_LANE_FIELD: &str = "lane";
+/// v2 `additional_fields` key holding a TTL relative to creation (v3: `expires_at`)
+const V2_TTL_FIELD: &str = "ttl_secs";
+/// v2 lane names and the numeric lane ids v3 uses for them
+const V2_LANE_NAMES: [(&str, LaneId); 2] = [("flash", LaneId(0)), ("deep", LaneId(1))];
+
+/// Lane id for a v2 lane hint, which named the lane rather than numbering it
+fn v2_lane_id(lane: &str) -> Result<LaneId, GxfError> {
+    V2_LANE_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(lane))
+        .map(|(_, lane_id)| lane_id.clone())
+        .or_else(|| lane.parse().ok())
+        .ok_or_else(|| GxfError::InvalidMetadata(format!("Unknown v2 {}: {}", V2_LANE_FIELD, lane)))
+}
+
 /// GXF-specific error types
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum GxfError {
@@ -32,6 +83,32 @@ pub enum GxfError {
     Serialization(String),
     #[error("Deserialization error: {0}")]
     Deserialization(String),
+    #[error("Missing VDF proof")]
+    MissingVdfProof,
+    #[error("Invalid VDF proof")]
+    InvalidVdfProof,
+    #[error("VDF difficulty too low: {iterations} iterations, at least {required} required")]
+    VdfDifficultyTooLow { iterations: u64, required: u64 },
+}
+
+impl GxfError {
+    /// Stable numeric code identifying the error category
+    pub fn code(&self) -> u32 {
+        match self {
+            GxfError::InvalidVersion { .. } => 2001,
+            GxfError::InvalidJobId(_) => 2002,
+            GxfError::InvalidPayload(_) => 2003,
+            GxfError::InvalidMetadata(_) => 2004,
+            GxfError::Expired { .. } => 2005,
+            GxfError::InvalidPrecision => 2006,
+            GxfError::InvalidSequenceLength(_) => 2007,
+            GxfError::Serialization(_) => 2008,
+            GxfError::Deserialization(_) => 2009,
+            GxfError::MissingVdfProof => 2010,
+            GxfError::InvalidVdfProof => 2011,
+            GxfError::VdfDifficultyTooLow { .. } => 2012,
+        }
+    }
 }
 
 /// Precision levels for compute operations
@@ -53,6 +130,51 @@ impl PrecisionLevel {
     pub fn is_valid(&self) -> bool {
         matches!(self, PrecisionLevel::BF16 | PrecisionLevel::FP8 | PrecisionLevel::E5M2 | PrecisionLevel::INT8)
     }
+
+    /// Precisions a job requesting `self` may run at, in order of preference
+    ///
+    /// Starts with `self`. Substitutes must represent every value of the
+    /// requested format closely enough:
+    /// - BF16 has no substitute; every other format loses mantissa or range.
+    /// - FP8 and E5M2 stand in for each other, then fall back to BF16.
+    /// - INT8 falls back to BF16, which holds every 8-bit integer exactly.
+    ///   Neither 8-bit float does, so INT8 never substitutes a float format
+    ///   or the other way round.
+    pub fn fallback_chain(&self) -> Vec<PrecisionLevel> {
+        match self {
+            PrecisionLevel::BF16 => vec![PrecisionLevel::BF16],
+            PrecisionLevel::FP8 => vec![PrecisionLevel::FP8, PrecisionLevel::E5M2, PrecisionLevel::BF16],
+            PrecisionLevel::E5M2 => vec![PrecisionLevel::E5M2, PrecisionLevel::FP8, PrecisionLevel::BF16],
+            PrecisionLevel::INT8 => vec![PrecisionLevel::INT8, PrecisionLevel::BF16],
+        }
+    }
+
+    /// Whether a job requesting `self` may run at `other`
+    ///
+    /// Not symmetric: BF16 substitutes for INT8, but not the reverse.
+    pub fn compatible_with(&self, other: &PrecisionLevel) -> bool {
+        self.fallback_chain().contains(other)
+    }
+
+    /// Default price multiplier for jobs at this precision
+    pub fn default_price_multiplier(&self) -> f64 {
+        match self {
+            PrecisionLevel::INT8 => 1.0,
+            PrecisionLevel::E5M2 => 1.2,
+            PrecisionLevel::FP8 => 1.5,
+            PrecisionLevel::BF16 => 2.0,
+        }
+    }
+
+    /// Stable lowercase name used as the `precision` metrics label
+    pub fn metric_label(&self) -> &'static str {
+        match self {
+            PrecisionLevel::BF16 => "bf16",
+            PrecisionLevel::FP8 => "fp8",
+            PrecisionLevel::E5M2 => "e5m2",
+            PrecisionLevel::INT8 => "int8",
+        }
+    }
 }
 
 /// Job priority levels
@@ -83,6 +205,38 @@ impl JobPriority {
     pub fn as_u8(&self) -> u8 {
         *self as u8
     }
+
+    /// Whether the job should take the low-latency path (High or Critical)
+    pub fn is_urgent(&self) -> bool {
+        *self >= JobPriority::High
+    }
+}
+
+impl fmt::Display for JobPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JobPriority::Low => "low",
+            JobPriority::Normal => "normal",
+            JobPriority::High => "high",
+            JobPriority::Critical => "critical",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for JobPriority {
+    type Err = GxfError;
+
+    /// Parse a priority name, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(JobPriority::Low),
+            "normal" => Ok(JobPriority::Normal),
+            "high" => Ok(JobPriority::High),
+            "critical" => Ok(JobPriority::Critical),
+            _ => Err(GxfError::InvalidMetadata(format!("Unknown priority: {}", s))),
+        }
+    }
 }
 
 /// GXF Job structure
@@ -110,21 +264,158 @@ impl GxfJob {
         }
     }
 
+    /// Deserialize a bare job from JSON bytes
+    ///
+    /// Input is checked against the default [`ParseLimits`] first, as for
+    /// envelope payloads. The job is not validated.
+    pub fn from_json(data: &[u8]) -> Result<Self, GxfError> {
+        Self::from_json_limited(data, &ParseLimits::default())
+    }
+
+    /// Deserialize a bare job from JSON bytes, enforcing `limits`
+    pub fn from_json_limited(data: &[u8], limits: &ParseLimits) -> Result<Self, GxfError> {
+        limits.check_json(data)?;
+        serde_json::from_slice(data)
+            .map_err(|e| GxfError::Deserialization(format!("Failed to deserialize job: {}", e)))
+    }
+
     /// Validate the job structure
     pub fn validate(&self) -> Result<(), GxfError> {
-        if !self.precision.is_valid() {
-            return Err(GxfError::InvalidPrecision);
+        match self.validation_issues().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
         }
+    }
 
+    /// Every validation failure for the job, in the order `validate` checks them
+    pub fn validation_issues(&self) -> Vec<GxfError> {
+        let mut issues = Vec::new();
+        if !self.precision.is_valid() {
+            issues.push(GxfError::InvalidPrecision);
+        }
         if self.kv_cache_seq_len == 0 {
-            return Err(GxfError::InvalidSequenceLength(self.kv_cache_seq_len));
+            issues.push(GxfError::InvalidSequenceLength(self.kv_cache_seq_len));
+        }
+        if self.parameters.len() > MAX_PARAMETERS {
+            issues.push(GxfError::InvalidPayload(format!(
+                "{} parameters exceed maximum of {}",
+                self.parameters.len(),
+                MAX_PARAMETERS
+            )));
+        }
+        for (key, value) in sorted(&self.parameters) {
+            if value.len() > MAX_PARAMETER_VALUE_BYTES {
+                issues.push(GxfError::InvalidPayload(format!(
+                    "parameter '{}' is {} bytes, maximum is {}",
+                    key,
+                    value.len(),
+                    MAX_PARAMETER_VALUE_BYTES
+                )));
+            }
+        }
+        issues
+    }
+
+    /// `base_price` plus `seq_len_rate` per sequence position, or `None` on overflow
+    ///
+    /// The first step of the default provider pricing, before the precision
+    /// multiplier and any utilization surcharge.
+    pub fn surcharged_price(&self, base_price: u64, seq_len_rate: u64) -> Option<u64> {
+        (self.kv_cache_seq_len as u64)
+            .checked_mul(seq_len_rate)
+            .and_then(|surcharge| surcharge.checked_add(base_price))
+    }
+
+    /// Estimated price of the job at a provider charging `base_price`
+    ///
+    /// Applies the default sequence-length rate and precision multiplier but
+    /// not the provider's utilization surcharge, so it equals the quote of an
+    /// idle provider and undershoots a busy one. Saturates at `u64::MAX`.
+    pub fn estimated_cost(&self, base_price: u64) -> u64 {
+        let price = self.surcharged_price(base_price, DEFAULT_SEQ_LEN_RATE).unwrap_or(u64::MAX);
+        (price as f64 * self.precision.default_price_multiplier()) as u64
+    }
+
+    /// Projection of the job with parameters in sorted key order
+    fn canonical(&self) -> CanonicalJob<'_> {
+        CanonicalJob {
+            job_id: &self.job_id,
+            precision: self.precision,
+            kv_cache_seq_len: self.kv_cache_seq_len,
+            parameters: sorted(&self.parameters),
         }
+    }
 
-        Ok(())
+    /// Byte-stable JSON encoding of the job, for hashing and signing
+    ///
+    /// Map keys are sorted, so equal jobs always produce identical bytes.
+    /// The output is ordinary job JSON and decodes as a `GxfJob`.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, GxfError> {
+        serde_json::to_vec(&self.canonical())
+            .map_err(|e| GxfError::Serialization(format!("Failed to serialize job: {}", e)))
+    }
+
+    /// Commitment to the job's inputs: Blake3 of its canonical bytes
+    pub fn input_commitment(&self) -> Result<[u8; 32], GxfError> {
+        Ok(gix_crypto::hash_blake3(&self.canonical_bytes()?))
+    }
+
+    /// Output hash a runtime reports for the completed job
+    ///
+    /// Derived as `hash_blake3(job_id || input_commitment)`, so the hash only
+    /// matches the exact job that was submitted, not just its ID.
+    pub fn output_hash(&self) -> Result<[u8; 32], GxfError> {
+        let mut data = self.job_id.0.to_vec();
+        data.extend_from_slice(&self.input_commitment()?);
+        Ok(gix_crypto::hash_blake3(&data))
+    }
+
+    /// Check that `output_hash` was derived from this job
+    pub fn verify_output(&self, output_hash: &[u8; 32]) -> bool {
+        self.output_hash().is_ok_and(|expected| &expected == output_hash)
     }
 }
 
+/// Sort a string map by key
+fn sorted(map: &std::collections::HashMap<String, String>) -> BTreeMap<&str, &str> {
+    map.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()
+}
+
+/// `GxfJob` with a sorted parameter map (same serialized shape)
+#[derive(Serialize)]
+struct CanonicalJob<'a> {
+    job_id: &'a JobId,
+    precision: PrecisionLevel,
+    kv_cache_seq_len: u32,
+    parameters: BTreeMap<&'a str, &'a str>,
+}
+
+/// Encoding of the serialized job carried in an envelope payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadEncoding {
+    /// JSON (default)
+    #[default]
+    Json,
+    /// Bincode
+    Bincode,
+}
+
+/// Compression applied to an envelope payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadCompression {
+    /// Uncompressed (default)
+    #[default]
+    None,
+    /// Zstandard (requires the `compression` feature)
+    Zstd,
+}
+
 /// GXF Metadata structure
+///
+/// Optional fields are always serialized (as `null` in JSON) so the metadata
+/// stays decodable by non-self-describing formats such as bincode.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GxfMetadata {
     /// Schema version
@@ -134,17 +425,26 @@ pub struct GxfMetadata {
     /// Creation timestamp (Unix epoch in seconds)
     pub created_at: u64,
     /// Expiration timestamp (Unix epoch in seconds, None if no expiration)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub expires_at: Option<u64>,
     /// Source SLP identifier (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub source_slp: Option<String>,
     /// Target lane identifier (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub target_lane: Option<String>,
     /// Additional metadata fields
     #[serde(default)]
     pub additional_fields: std::collections::HashMap<String, String>,
+    /// Encoding of the job in the envelope payload
+    #[serde(default)]
+    pub payload_encoding: PayloadEncoding,
+    /// Compression applied to the envelope payload
+    #[serde(default)]
+    pub payload_compression: PayloadCompression,
+    /// Client-chosen key identifying retries of the same submission (optional)
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 impl GxfMetadata {
@@ -163,11 +463,83 @@ impl GxfMetadata {
             source_slp: None,
             target_lane: None,
             additional_fields: std::collections::HashMap::new(),
+            payload_encoding: PayloadEncoding::default(),
+            payload_compression: PayloadCompression::default(),
+            idempotency_key: None,
         })
     }
 
+    /// Create new metadata that expires `ttl_secs` seconds from now
+    pub fn with_ttl(priority: u8, ttl_secs: u64) -> Result<Self, GxfError> {
+        let mut meta = Self::new(priority)?;
+        meta.expires_at = Some(meta.created_at.saturating_add(ttl_secs));
+        Ok(meta)
+    }
+
+    /// Set the expiration to `ttl_secs` seconds from now
+    pub fn set_ttl(&mut self, ttl_secs: u64) {
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(self.created_at);
+
+        self.expires_at = Some(current_time.saturating_add(ttl_secs));
+    }
+
+    /// Seconds remaining until expiry (saturating at zero), or `None` if no expiration is set
+    pub fn remaining_ttl(&self) -> Option<u64> {
+        let expires_at = self.expires_at?;
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Some(expires_at.saturating_sub(current_time))
+    }
+
+    /// Priority bucket for the raw priority value
+    pub fn priority_class(&self) -> JobPriority {
+        JobPriority::from_u8(self.priority)
+    }
+
+    /// Set the raw priority to the lowest value of `priority`'s bucket
+    pub fn set_job_priority(&mut self, priority: JobPriority) {
+        self.priority = priority.as_u8();
+    }
+
+    /// Projection of the metadata with additional fields in sorted key order
+    fn canonical(&self) -> CanonicalMetadata<'_> {
+        CanonicalMetadata {
+            schema_version: self.schema_version,
+            priority: self.priority,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            source_slp: self.source_slp.as_deref(),
+            target_lane: self.target_lane.as_deref(),
+            additional_fields: sorted(&self.additional_fields),
+            payload_encoding: self.payload_encoding,
+            payload_compression: self.payload_compression,
+            idempotency_key: self.idempotency_key.as_deref(),
+        }
+    }
+
     /// Validate metadata structure
     pub fn validate(&self) -> Result<(), GxfError> {
+        self.validate_with_skew(0)
+    }
+
+    /// Validate metadata, accepting envelopes that expired up to `skew_secs` ago
+    pub fn validate_with_skew(&self, skew_secs: u64) -> Result<(), GxfError> {
+        self.validate_at_with_skew(SystemClock.now_secs(), skew_secs)
+    }
+
+    /// Validate metadata as of `current_time` (Unix seconds)
+    pub fn validate_at(&self, current_time: u64) -> Result<(), GxfError> {
+        self.validate_at_with_skew(current_time, 0)
+    }
+
+    /// Validate metadata as of `current_time`, with a `skew_secs` expiry grace window
+    pub fn validate_at_with_skew(&self, current_time: u64, skew_secs: u64) -> Result<(), GxfError> {
         // Check schema version
         if self.schema_version != GXF_VERSION {
             return Err(GxfError::InvalidVersion {
@@ -177,19 +549,8 @@ impl GxfMetadata {
         }
 
         // Check expiration
+        self.check_expiry_at_with_skew(current_time, skew_secs)?;
         if let Some(expires_at) = self.expires_at {
-            let current_time = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .map_err(|e| GxfError::InvalidMetadata(format!("Failed to get timestamp: {}", e)))?
-                .as_secs();
-
-            if expires_at <= current_time {
-                return Err(GxfError::Expired {
-                    expires_at,
-                    current_time,
-                });
-            }
-
             // Expiration must be after creation
             if expires_at <= self.created_at {
                 return Err(GxfError::InvalidMetadata(
@@ -201,20 +562,116 @@ impl GxfMetadata {
         Ok(())
     }
 
+    /// Check that routing hints name a known lane and a known source SLP
+    ///
+    /// `target_lane` must be a numeric lane id in `known_lanes` and
+    /// `source_slp` must be in `known_slps`. Absent hints are accepted.
+    pub fn validate_routing_hints(&self, known_lanes: &[LaneId], known_slps: &[SlpId]) -> Result<(), GxfError> {
+        if let Some(lane) = &self.target_lane {
+            let lane_id = lane
+                .parse::<LaneId>()
+                .map_err(|e| GxfError::InvalidMetadata(format!("Malformed target lane: {}", e)))?;
+            if !known_lanes.contains(&lane_id) {
+                return Err(GxfError::InvalidMetadata(format!("Unknown target lane: {}", lane)));
+            }
+        }
+
+        if let Some(slp) = &self.source_slp {
+            if !known_slps.iter().any(|known| known.as_str() == slp) {
+                return Err(GxfError::InvalidMetadata(format!("Unknown source SLP: {:?}", slp)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Seconds since the metadata was created (zero if `created_at` is in the future)
+    pub fn age_secs(&self) -> u64 {
+        self.age_secs_at(SystemClock.now_secs())
+    }
+
+    /// Seconds between creation and `current_time` (Unix seconds), saturating at zero
+    pub fn age_secs_at(&self, current_time: u64) -> u64 {
+        current_time.saturating_sub(self.created_at)
+    }
+
+    /// Creation time as an RFC 3339 string, e.g. `2024-01-01T00:00:00Z`
+    #[cfg(feature = "datetime")]
+    pub fn created_at_datetime(&self) -> Result<String, GxfError> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            GxfError::InvalidMetadata(format!("Invalid creation time {}: {}", self.created_at, e))
+        };
+        let seconds = i64::try_from(self.created_at).map_err(|e| invalid(&e))?;
+        time::OffsetDateTime::from_unix_timestamp(seconds)
+            .map_err(|e| invalid(&e))?
+            .format(&time::format_description::well_known::Rfc3339)
+            .map_err(|e| invalid(&e))
+    }
+
     /// Check if metadata is expired
     pub fn is_expired(&self) -> bool {
-        if let Some(expires_at) = self.expires_at {
-            let current_time = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .ok()
-                .map(|d| d.as_secs())
-                .unwrap_or(0);
+        self.is_expired_with_skew(0)
+    }
+
+    /// Check if metadata expired more than `skew_secs` ago
+    ///
+    /// The grace window absorbs clock skew between submitter and service.
+    pub fn is_expired_with_skew(&self, skew_secs: u64) -> bool {
+        self.is_expired_at_with_skew(SystemClock.now_secs(), skew_secs)
+    }
+
+    /// Check if metadata is expired as of `current_time` (Unix seconds)
+    pub fn is_expired_at(&self, current_time: u64) -> bool {
+        self.is_expired_at_with_skew(current_time, 0)
+    }
 
-            expires_at <= current_time
+    /// Check if metadata expired more than `skew_secs` before `current_time`
+    pub fn is_expired_at_with_skew(&self, current_time: u64, skew_secs: u64) -> bool {
+        if let Some(expires_at) = self.expires_at {
+            expires_at.saturating_add(skew_secs) <= current_time
         } else {
             false
         }
     }
+
+    /// Fail with [`GxfError::Expired`] if metadata expired more than `skew_secs` ago
+    pub fn check_expiry_with_skew(&self, skew_secs: u64) -> Result<(), GxfError> {
+        self.check_expiry_at_with_skew(SystemClock.now_secs(), skew_secs)
+    }
+
+    /// Fail with [`GxfError::Expired`] if metadata expired more than `skew_secs` before `current_time`
+    pub fn check_expiry_at_with_skew(&self, current_time: u64, skew_secs: u64) -> Result<(), GxfError> {
+        match self.expires_at {
+            Some(expires_at) if self.is_expired_at_with_skew(current_time, skew_secs) => {
+                Err(GxfError::Expired { expires_at, current_time })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `GxfMetadata` with sorted additional fields (same serialized shape)
+#[derive(Serialize)]
+struct CanonicalMetadata<'a> {
+    schema_version: u8,
+    priority: u8,
+    created_at: u64,
+    expires_at: Option<u64>,
+    source_slp: Option<&'a str>,
+    target_lane: Option<&'a str>,
+    additional_fields: BTreeMap<&'a str, &'a str>,
+    payload_encoding: PayloadEncoding,
+    payload_compression: PayloadCompression,
+    idempotency_key: Option<&'a str>,
+}
+
+/// `GxfEnvelope` with canonical metadata (same serialized shape)
+#[derive(Serialize)]
+struct CanonicalEnvelope<'a> {
+    meta: CanonicalMetadata<'a>,
+    payload: &'a [u8],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vdf_proof: Option<&'a gix_crypto::VdfProof>,
 }
 
 /// GXF Envelope structure
@@ -224,44 +681,306 @@ pub struct GxfEnvelope {
     pub meta: GxfMetadata,
     /// Encrypted payload (contains serialized GxfJob)
     pub payload: Vec<u8>,
+    /// Proof of sequential work over the job content hash
+    ///
+    /// Present in every build so the binary layout does not depend on the
+    /// `vdf` feature; only proving and verifying are gated.
+    #[serde(default)]
+    pub vdf_proof: Option<gix_crypto::VdfProof>,
 }
 
 impl GxfEnvelope {
     /// Create a new GXF envelope
     pub fn new(meta: GxfMetadata, payload: Vec<u8>) -> Self {
-        GxfEnvelope { meta, payload }
+        GxfEnvelope {
+            meta,
+            payload,
+            vdf_proof: None,
+        }
     }
 
     /// Create envelope from job
     pub fn from_job(job: GxfJob, priority: u8) -> Result<Self, GxfError> {
-        // Validate job first
-        job.validate()?;
+        Self::from_job_with_encoding(job, priority, PayloadEncoding::Json)
+    }
+
+    /// Create envelope from job, encoding the payload with the given encoding
+    pub fn from_job_with_encoding(
+        job: GxfJob,
+        priority: u8,
+        encoding: PayloadEncoding,
+    ) -> Result<Self, GxfError> {
+        let mut meta = GxfMetadata::new(priority)?;
+        meta.payload_encoding = encoding;
+
+        Self::from_job_with_meta(job, meta)
+    }
 
-        // Create metadata
-        let meta = GxfMetadata::new(priority)?;
+    /// Create envelope from job with caller-supplied metadata
+    ///
+    /// The payload is encoded per `meta.payload_encoding`; compress it
+    /// afterwards with `compress_payload` rather than setting the flag here.
+    pub fn from_job_with_meta(job: GxfJob, meta: GxfMetadata) -> Result<Self, GxfError> {
+        // Validate job and metadata first
+        job.validate()?;
+        meta.validate()?;
+        if meta.payload_compression != PayloadCompression::None {
+            return Err(GxfError::InvalidMetadata(
+                "Payload compression must be applied after construction".to_string(),
+            ));
+        }
 
-        // Serialize job to payload
-        let payload = serde_json::to_vec(&job)
-            .map_err(|e| GxfError::Serialization(format!("Failed to serialize job: {}", e)))?;
+        // Serialize job to payload (sorted parameters keep the bytes stable)
+        let payload = match meta.payload_encoding {
+            PayloadEncoding::Json => job.canonical_bytes()?,
+            PayloadEncoding::Bincode => bincode::serialize(&job.canonical())
+                .map_err(|e| GxfError::Serialization(format!("Failed to serialize job: {}", e)))?,
+        };
 
         Ok(GxfEnvelope::new(meta, payload))
     }
 
+    /// Compress the payload with zstd at the given level
+    ///
+    /// Does nothing if the payload is already compressed.
+    #[cfg(feature = "compression")]
+    pub fn compress_payload(&mut self, level: i32) -> Result<(), GxfError> {
+        if self.meta.payload_compression == PayloadCompression::Zstd {
+            return Ok(());
+        }
+
+        self.payload = zstd::encode_all(self.payload.as_slice(), level)
+            .map_err(|e| GxfError::Serialization(format!("Failed to compress payload: {}", e)))?;
+        self.meta.payload_compression = PayloadCompression::Zstd;
+
+        Ok(())
+    }
+
+    /// Upgrade an envelope from an older schema version to `GXF_VERSION`
+    ///
+    /// v2 envelopes share the v3 layout minus the payload encoding and
+    /// compression tags (always uncompressed JSON), and carry the target lane
+    /// and a relative TTL as `lane` and `ttl_secs` in `additional_fields`.
+    /// v2 named its lanes (`flash`, `deep`); they become numeric lane ids.
+    /// Current-version envelopes are returned unchanged.
+    pub fn migrate(&self) -> Result<GxfEnvelope, GxfError> {
+        let mut migrated = self.clone();
+
+        match self.meta.schema_version {
+            GXF_VERSION => return Ok(migrated),
+            2 => {
+                let meta = &mut migrated.meta;
+                if let Some(lane) = meta.additional_fields.remove(V2_LANE_FIELD) {
+                    let lane_id = v2_lane_id(&lane)?;
+                    meta.target_lane.get_or_insert(lane_id.to_string());
+                }
+                if let Some(ttl) = meta.additional_fields.remove(V2_TTL_FIELD) {
+                    let ttl_secs: u64 = ttl.parse().map_err(|_| {
+                        GxfError::InvalidMetadata(format!("Invalid v2 {}: {}", V2_TTL_FIELD, ttl))
+                    })?;
+                    meta.expires_at.get_or_insert(meta.created_at.saturating_add(ttl_secs));
+                }
+                meta.payload_encoding = PayloadEncoding::Json;
+                meta.payload_compression = PayloadCompression::None;
+                meta.schema_version = GXF_VERSION;
+            }
+            actual => {
+                return Err(GxfError::InvalidVersion {
+                    expected: GXF_VERSION,
+                    actual,
+                })
+            }
+        }
+
+        Ok(migrated)
+    }
+
+    /// Byte-stable JSON encoding of the envelope, for hashing and signing
+    ///
+    /// Map keys are sorted, so equal envelopes always produce identical bytes.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, GxfError> {
+        let canonical = CanonicalEnvelope {
+            meta: self.meta.canonical(),
+            payload: &self.payload,
+            vdf_proof: self.vdf_proof.as_ref(),
+        };
+        serde_json::to_vec(&canonical)
+            .map_err(|e| GxfError::Serialization(format!("Failed to serialize envelope: {}", e)))
+    }
+
+    /// Short hex identifier of the envelope, for logs and duplicate detection
+    ///
+    /// The leading bytes of the Blake3 hash of `canonical_bytes`, so copies
+    /// that survived a serialization round trip share a fingerprint.
+    pub fn fingerprint(&self) -> String {
+        let bytes = self.canonical_bytes().expect("canonical envelope serializes");
+        blake3::hash(&bytes).as_bytes()[..FINGERPRINT_BYTES]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Blake3 hash of the job's canonical bytes, used as the VDF challenge
+    ///
+    /// Independent of payload encoding and compression.
+    #[cfg(feature = "vdf")]
+    pub fn content_hash(&self) -> Result<[u8; 32], GxfError> {
+        Ok(gix_crypto::hash_blake3(&self.deserialize_job()?.canonical_bytes()?))
+    }
+
+    /// Compute and attach a VDF proof over the content hash
+    ///
+    /// This is intentionally slow; cost grows linearly with `iterations`.
+    #[cfg(feature = "vdf")]
+    pub fn attach_vdf_proof(&mut self, iterations: u64) -> Result<(), GxfError> {
+        let proof = gix_crypto::vdf_prove(&self.content_hash()?, iterations)
+            .map_err(|e| GxfError::Serialization(format!("Failed to compute VDF proof: {}", e)))?;
+        self.vdf_proof = Some(proof);
+        Ok(())
+    }
+
+    /// Check the attached VDF proof against the content hash and a difficulty floor
+    #[cfg(feature = "vdf")]
+    pub fn verify_vdf_proof(&self, min_iterations: u64) -> Result<(), GxfError> {
+        let proof = self.vdf_proof.as_ref().ok_or(GxfError::MissingVdfProof)?;
+        if proof.iterations < min_iterations {
+            return Err(GxfError::VdfDifficultyTooLow {
+                iterations: proof.iterations,
+                required: min_iterations,
+            });
+        }
+        if !gix_crypto::vdf_verify(&self.content_hash()?, proof) {
+            return Err(GxfError::InvalidVdfProof);
+        }
+        Ok(())
+    }
+
+    /// Get the payload with any compression removed
+    ///
+    /// Decompression stops past `MAX_PAYLOAD_BYTES`, so a small compressed
+    /// payload cannot inflate into an arbitrarily large buffer.
+    fn decompressed_payload(&self) -> Result<std::borrow::Cow<'_, [u8]>, GxfError> {
+        match self.meta.payload_compression {
+            PayloadCompression::None => Ok(std::borrow::Cow::Borrowed(&self.payload)),
+            #[cfg(feature = "compression")]
+            PayloadCompression::Zstd => {
+                use std::io::Read;
+
+                let decoder = zstd::stream::Decoder::new(self.payload.as_slice())
+                    .map_err(|e| GxfError::Deserialization(format!("Failed to decompress payload: {}", e)))?;
+                let mut payload = Vec::new();
+                decoder
+                    .take(MAX_PAYLOAD_BYTES as u64 + 1)
+                    .read_to_end(&mut payload)
+                    .map_err(|e| GxfError::Deserialization(format!("Failed to decompress payload: {}", e)))?;
+                if payload.len() > MAX_PAYLOAD_BYTES {
+                    return Err(GxfError::InvalidPayload(format!(
+                        "Decompressed payload exceeds {} bytes",
+                        MAX_PAYLOAD_BYTES
+                    )));
+                }
+                Ok(std::borrow::Cow::Owned(payload))
+            }
+            #[cfg(not(feature = "compression"))]
+            PayloadCompression::Zstd => Err(GxfError::Deserialization(
+                "Payload is zstd-compressed but the `compression` feature is not enabled".to_string(),
+            )),
+        }
+    }
+
+    /// Seconds since the envelope was created, see [`GxfMetadata::age_secs`]
+    pub fn age_secs(&self) -> u64 {
+        self.meta.age_secs()
+    }
+
     /// Deserialize job from payload
+    ///
+    /// A JSON payload that is not valid UTF-8 JSON at all is reported as
+    /// `InvalidPayload`; valid JSON that does not describe a job as
+    /// `Deserialization`.
     pub fn deserialize_job(&self) -> Result<GxfJob, GxfError> {
-        serde_json::from_slice(&self.payload)
-            .map_err(|e| GxfError::Deserialization(format!("Failed to deserialize job: {}", e)))
+        let payload = self.decompressed_payload()?;
+
+        match self.meta.payload_encoding {
+            PayloadEncoding::Json => {
+                let raw: serde_json::Value = serde_json::from_slice(&payload)
+                    .map_err(|e| GxfError::InvalidPayload(format!("Payload is not valid JSON: {}", e)))?;
+                serde_json::from_value(raw)
+                    .map_err(|e| GxfError::Deserialization(format!("Failed to deserialize job: {}", e)))
+            }
+            PayloadEncoding::Bincode => bincode::deserialize(&payload)
+                .map_err(|e| GxfError::Deserialization(format!("Failed to deserialize job: {}", e))),
+        }
+    }
+
+    /// Parse the job and list every validation issue, for inspection tooling
+    ///
+    /// Unlike `validate`, this does not stop at the first problem. A JSON job
+    /// that fails to parse is still examined field by field, so a bad
+    /// precision is reported alongside other invalid fields.
+    pub fn try_inspect(&self) -> (Result<GxfJob, GxfError>, Vec<String>) {
+        let mut issues = Vec::new();
+        if let Err(e) = self.meta.validate() {
+            issues.push(e.to_string());
+        }
+        if self.payload.is_empty() {
+            let e = GxfError::InvalidPayload("Payload cannot be empty".to_string());
+            issues.push(e.to_string());
+            return (Err(e), issues);
+        }
+
+        let job = self.deserialize_job();
+        match &job {
+            Ok(job) => issues.extend(job.validation_issues().iter().map(ToString::to_string)),
+            Err(e) => match self.raw_job_issues() {
+                Some(field_issues) if !field_issues.is_empty() => issues.extend(field_issues),
+                _ => issues.push(e.to_string()),
+            },
+        }
+        (job, issues)
+    }
+
+    /// Field-level issues in a JSON job payload that does not parse as a `GxfJob`
+    fn raw_job_issues(&self) -> Option<Vec<String>> {
+        if self.meta.payload_encoding != PayloadEncoding::Json {
+            return None;
+        }
+        let payload = self.decompressed_payload().ok()?;
+        let raw: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+
+        let mut issues = Vec::new();
+        if let Some(precision) = raw.get("precision") {
+            if serde_json::from_value::<PrecisionLevel>(precision.clone()).is_err() {
+                issues.push(format!("{}: {}", GxfError::InvalidPrecision, precision));
+            }
+        }
+        if raw.get("kv_cache_seq_len").and_then(serde_json::Value::as_u64) == Some(0) {
+            issues.push(GxfError::InvalidSequenceLength(0).to_string());
+        }
+        Some(issues)
     }
 
     /// Validate the entire envelope
     pub fn validate(&self) -> Result<(), GxfError> {
+        self.validate_with_skew(0)
+    }
+
+    /// Validate the envelope, accepting one that expired up to `skew_secs` ago
+    pub fn validate_with_skew(&self, skew_secs: u64) -> Result<(), GxfError> {
         // Validate metadata
-        self.meta.validate()?;
+        self.meta.validate_with_skew(skew_secs)?;
 
         // Check payload is not empty
         if self.payload.is_empty() {
             return Err(GxfError::InvalidPayload("Payload cannot be empty".to_string()));
         }
+        if self.payload.len() > MAX_PAYLOAD_BYTES {
+            return Err(GxfError::InvalidPayload(format!(
+                "Payload is {} bytes, limit is {}",
+                self.payload.len(),
+                MAX_PAYLOAD_BYTES
+            )));
+        }
 
         // Try to deserialize and validate job
         let job = self.deserialize_job()?;
@@ -270,6 +989,14 @@ impl GxfEnvelope {
         Ok(())
     }
 
+    /// Validate the envelope and require its routing hints to name known lanes and SLPs
+    ///
+    /// See [`GxfMetadata::validate_routing_hints`]. `validate` stays lenient.
+    pub fn validate_strict(&self, known_lanes: &[LaneId], known_slps: &[SlpId]) -> Result<(), GxfError> {
+        self.validate()?;
+        self.meta.validate_routing_hints(known_lanes, known_slps)
+    }
+
     /// Serialize envelope to JSON bytes
     pub fn to_json(&self) -> Result<Vec<u8>, GxfError> {
         serde_json::to_vec(self)
@@ -277,8 +1004,54 @@ impl GxfEnvelope {
     }
 
     /// Deserialize envelope from JSON bytes
+    ///
+    /// Input is checked against the default [`ParseLimits`] first.
     pub fn from_json(data: &[u8]) -> Result<Self, GxfError> {
-        serde_json::from_slice(data)
+        Self::from_json_limited(data, &ParseLimits::default())
+    }
+
+    /// Deserialize envelope from JSON bytes, enforcing `limits`
+    ///
+    /// Limits apply to the envelope and, for uncompressed JSON payloads, to
+    /// the job inside it, so oversized `additional_fields` or `parameters`
+    /// are rejected before either structure is built. Compressed payloads
+    /// are left alone: parsing never decompresses, and `validate` bounds the
+    /// decompressed size.
+    pub fn from_json_limited(data: &[u8], limits: &ParseLimits) -> Result<Self, GxfError> {
+        limits.check_json(data)?;
+        let envelope: Self = serde_json::from_slice(data)
+            .map_err(|e| GxfError::Deserialization(format!("Failed to deserialize envelope: {}", e)))?;
+
+        if envelope.meta.payload_encoding == PayloadEncoding::Json
+            && envelope.meta.payload_compression == PayloadCompression::None
+        {
+            limits.check_json(&envelope.payload)?;
+        }
+        Ok(envelope)
+    }
+
+    /// Deserialize envelope from JSON bytes, upgrading older schema versions
+    pub fn from_json_migrated(data: &[u8]) -> Result<Self, GxfError> {
+        Self::from_json(data)?.migrate()
+    }
+
+    /// Serialize envelope to bincode bytes
+    pub fn to_bincode(&self) -> Result<Vec<u8>, GxfError> {
+        bincode::serialize(self)
+            .map_err(|e| GxfError::Serialization(format!("Failed to serialize envelope: {}", e)))
+    }
+
+    /// Deserialize envelope from bincode bytes
+    ///
+    /// Envelopes encoded before `vdf_proof` existed end after the payload and
+    /// decode with no proof.
+    pub fn from_bincode(data: &[u8]) -> Result<Self, GxfError> {
+        bincode::deserialize(data)
+            .or_else(|e| {
+                bincode::deserialize::<(GxfMetadata, Vec<u8>)>(data)
+                    .map(|(meta, payload)| Self::new(meta, payload))
+                    .map_err(|_| e)
+            })
             .map_err(|e| GxfError::Deserialization(format!("Failed to deserialize envelope: {}", e)))
     }
 }
@@ -305,6 +1078,44 @@ mod tests {
         assert!(PrecisionLevel::INT8.is_valid());
     }
 
+    #[test]
+    fn test_precision_metric_labels() {
+        let cases = [
+            (PrecisionLevel::BF16, "bf16"),
+            (PrecisionLevel::FP8, "fp8"),
+            (PrecisionLevel::E5M2, "e5m2"),
+            (PrecisionLevel::INT8, "int8"),
+        ];
+        for (precision, label) in cases {
+            assert_eq!(precision.metric_label(), label);
+            assert_eq!(label, label.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_precision_fallback_chain() {
+        use PrecisionLevel::*;
+
+        assert_eq!(BF16.fallback_chain(), vec![BF16]);
+        assert_eq!(FP8.fallback_chain(), vec![FP8, E5M2, BF16]);
+        assert_eq!(E5M2.fallback_chain(), vec![E5M2, FP8, BF16]);
+        assert_eq!(INT8.fallback_chain(), vec![INT8, BF16]);
+
+        let all = [BF16, FP8, E5M2, INT8];
+        let expected = [
+            (BF16, [true, false, false, false]),
+            (FP8, [true, true, true, false]),
+            (E5M2, [true, true, true, false]),
+            (INT8, [true, false, false, true]),
+        ];
+        for (requested, compatible) in expected {
+            assert_eq!(requested.fallback_chain()[0], requested);
+            for (other, expected) in all.iter().zip(compatible) {
+                assert_eq!(requested.compatible_with(other), expected, "{:?} -> {:?}", requested, other);
+            }
+        }
+    }
+
     #[test]
     fn test_job_priority() {
         assert_eq!(JobPriority::from_u8(0), JobPriority::Low);
@@ -313,6 +1124,28 @@ mod tests {
         assert_eq!(JobPriority::from_u8(192), JobPriority::Critical);
     }
 
+    #[test]
+    fn test_job_priority_names() {
+        let high: JobPriority = "high".parse().unwrap();
+        assert_eq!(high, JobPriority::High);
+        assert_eq!(high.as_u8(), 128);
+        assert_eq!("CRITICAL".parse::<JobPriority>().unwrap().as_u8(), 192);
+        assert!("urgent".parse::<JobPriority>().is_err());
+
+        for priority in [JobPriority::Low, JobPriority::Normal, JobPriority::High, JobPriority::Critical] {
+            assert_eq!(priority.to_string().parse::<JobPriority>().unwrap(), priority);
+            assert_eq!(JobPriority::from_u8(priority.as_u8()), priority);
+        }
+
+        // The wire format keeps the numeric value
+        let mut meta = GxfMetadata::new(0).unwrap();
+        meta.set_job_priority(JobPriority::Normal);
+        assert_eq!(meta.priority, 64);
+        assert_eq!(meta.priority_class(), JobPriority::Normal);
+        let json = serde_json::to_value(&meta).unwrap();
+        assert_eq!(json["priority"], 64);
+    }
+
     #[test]
     fn test_gxf_job_creation() {
         let job_id = JobId([0u8; 16]);
@@ -333,6 +1166,51 @@ mod tests {
         assert!(invalid_job.validate().is_err());
     }
 
+    #[test]
+    fn test_gxf_job_parameter_limits() {
+        let mut job = GxfJob::new(JobId([0u8; 16]), PrecisionLevel::BF16, 1024);
+        job.parameters.insert("batch_size".to_string(), "8".to_string());
+        job.parameters.insert("region".to_string(), "US".to_string());
+        assert!(job.validate().is_ok());
+
+        let mut crowded = job.clone();
+        for i in 0..MAX_PARAMETERS {
+            crowded.parameters.insert(format!("key_{}", i), "v".to_string());
+        }
+        assert!(matches!(crowded.validate(), Err(GxfError::InvalidPayload(_))));
+
+        let mut oversized = job.clone();
+        oversized
+            .parameters
+            .insert("prompt".to_string(), "x".repeat(MAX_PARAMETER_VALUE_BYTES + 1));
+        assert!(matches!(oversized.validate(), Err(GxfError::InvalidPayload(_))));
+    }
+
+    #[test]
+    fn test_try_inspect_reports_every_issue() {
+        let job = GxfJob::new(JobId([6u8; 16]), PrecisionLevel::BF16, 1024);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        let mut raw: serde_json::Value = serde_json::from_slice(&envelope.payload).unwrap();
+        raw["kv_cache_seq_len"] = serde_json::json!(0);
+        envelope.payload = serde_json::to_vec(&raw).unwrap();
+
+        // A parseable job reports its issues alongside the job itself
+        let (parsed, issues) = envelope.try_inspect();
+        assert_eq!(parsed.unwrap().kv_cache_seq_len, 0);
+        assert_eq!(issues, vec![GxfError::InvalidSequenceLength(0).to_string()]);
+
+        // An unknown precision no longer parses, but both problems are still listed
+        raw["precision"] = serde_json::json!("FP4");
+        envelope.payload = serde_json::to_vec(&raw).unwrap();
+        assert!(envelope.validate().is_err());
+
+        let (parsed, issues) = envelope.try_inspect();
+        assert!(matches!(parsed, Err(GxfError::Deserialization(_))));
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0], "Invalid precision level: \"FP4\"");
+        assert_eq!(issues[1], GxfError::InvalidSequenceLength(0).to_string());
+    }
+
     #[test]
     fn test_gxf_metadata_creation() {
         let meta = GxfMetadata::new(64).unwrap();
@@ -342,6 +1220,25 @@ mod tests {
         assert!(meta.expires_at.is_none());
     }
 
+    #[test]
+    fn test_metadata_priority_class_boundaries() {
+        let cases = [
+            (0, JobPriority::Low),
+            (63, JobPriority::Low),
+            (64, JobPriority::Normal),
+            (127, JobPriority::Normal),
+            (128, JobPriority::High),
+            (191, JobPriority::High),
+            (192, JobPriority::Critical),
+            (255, JobPriority::Critical),
+        ];
+        for (priority, expected) in cases {
+            let meta = GxfMetadata::new(priority).unwrap();
+            assert_eq!(meta.priority_class(), expected, "priority {}", priority);
+            assert_eq!(meta.priority_class().is_urgent(), priority >= 128);
+        }
+    }
+
     #[test]
     fn test_gxf_metadata_validation() {
         let meta = GxfMetadata::new(64).unwrap();
@@ -376,6 +1273,35 @@ mod tests {
         assert!(meta.validate().is_err());
     }
 
+    #[test]
+    fn test_gxf_metadata_with_ttl() {
+        let meta = GxfMetadata::with_ttl(64, 3600).unwrap();
+        assert_eq!(meta.expires_at, Some(meta.created_at + 3600));
+        assert!(!meta.is_expired());
+        assert!(meta.validate().is_ok());
+
+        let remaining = meta.remaining_ttl().unwrap();
+        assert!(remaining > 3590 && remaining <= 3600);
+    }
+
+    #[test]
+    fn test_gxf_metadata_with_expired_ttl() {
+        let meta = GxfMetadata::with_ttl(64, 0).unwrap();
+        assert!(meta.is_expired());
+        assert!(meta.validate().is_err());
+        assert_eq!(meta.remaining_ttl(), Some(0));
+    }
+
+    #[test]
+    fn test_gxf_metadata_set_ttl() {
+        let mut meta = GxfMetadata::new(64).unwrap();
+        assert_eq!(meta.remaining_ttl(), None);
+
+        meta.set_ttl(600);
+        assert!(meta.expires_at.unwrap() >= meta.created_at + 600);
+        assert!(meta.validate().is_ok());
+    }
+
     #[test]
     fn test_gxf_envelope_creation() {
         let job_id = JobId([0u8; 16]);
@@ -397,6 +1323,11 @@ mod tests {
         let mut invalid_envelope = envelope.clone();
         invalid_envelope.payload = Vec::new();
         assert!(invalid_envelope.validate().is_err());
+
+        // Invalid: payload over the size limit
+        let mut oversized = envelope.clone();
+        oversized.payload = vec![b' '; MAX_PAYLOAD_BYTES + 1];
+        assert!(matches!(oversized.validate(), Err(GxfError::InvalidPayload(_))));
     }
 
     #[test]
@@ -415,6 +1346,500 @@ mod tests {
         assert_eq!(deserialized.payload, envelope.payload);
     }
 
+    #[test]
+    fn test_gxf_envelope_bincode_roundtrip() {
+        let job_id = JobId([2u8; 16]);
+        let mut job = GxfJob::new(job_id, PrecisionLevel::INT8, 4096);
+        job.parameters.insert("batch_size".to_string(), "8".to_string());
+
+        for encoding in [PayloadEncoding::Json, PayloadEncoding::Bincode] {
+            let envelope = GxfEnvelope::from_job_with_encoding(job.clone(), 64, encoding).unwrap();
+            assert_eq!(envelope.meta.payload_encoding, encoding);
+
+            let bytes = envelope.to_bincode().unwrap();
+            let decoded = GxfEnvelope::from_bincode(&bytes).unwrap();
+            assert!(decoded.validate().is_ok());
+            assert_eq!(decoded.payload, envelope.payload);
+            assert_eq!(decoded.meta.payload_encoding, encoding);
+
+            let decoded_job = decoded.deserialize_job().unwrap();
+            assert_eq!(decoded_job.job_id, job.job_id);
+            assert_eq!(decoded_job.parameters, job.parameters);
+        }
+    }
+
+    #[test]
+    fn test_gxf_envelope_bincode_layout_carries_vdf_proof() {
+        let job = GxfJob::new(JobId([3u8; 16]), PrecisionLevel::INT8, 1024);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        envelope.vdf_proof = Some(gix_crypto::VdfProof::new(vec![7u8; 8], 200));
+
+        // The proof survives regardless of whether this build can verify it
+        let decoded = GxfEnvelope::from_bincode(&envelope.to_bincode().unwrap()).unwrap();
+        assert_eq!(decoded.vdf_proof.as_ref().map(|p| p.iterations), Some(200));
+
+        // Bytes written before the field existed still decode
+        let legacy = bincode::serialize(&(&envelope.meta, &envelope.payload)).unwrap();
+        let decoded = GxfEnvelope::from_bincode(&legacy).unwrap();
+        assert!(decoded.vdf_proof.is_none());
+        assert_eq!(decoded.payload, envelope.payload);
+    }
+
+    #[test]
+    fn test_gxf_metadata_defaults_to_json_encoding() {
+        // Envelopes produced before the encoding tag existed omit the field
+        let json = br#"{"meta":{"schema_version":3,"priority":64,"created_at":1},"payload":[123,125]}"#;
+        let envelope = GxfEnvelope::from_json(json).unwrap();
+        assert_eq!(envelope.meta.payload_encoding, PayloadEncoding::Json);
+        assert!(envelope.meta.expires_at.is_none());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_gxf_envelope_compression_roundtrip() {
+        let job_id = JobId([3u8; 16]);
+        let mut job = GxfJob::new(job_id, PrecisionLevel::BF16, 2048);
+        for i in 0..MAX_PARAMETERS - 1 {
+            job.parameters.insert(format!("layer_{}", i), "attention".repeat(8));
+        }
+
+        let mut envelope = GxfEnvelope::from_job(job.clone(), 64).unwrap();
+        let uncompressed_len = envelope.payload.len();
+
+        envelope.compress_payload(3).unwrap();
+        assert_eq!(envelope.meta.payload_compression, PayloadCompression::Zstd);
+        assert!(envelope.payload.len() < uncompressed_len);
+        assert!(envelope.validate().is_ok());
+
+        let decoded = GxfEnvelope::from_json(&envelope.to_json().unwrap()).unwrap();
+        let decoded_job = decoded.deserialize_job().unwrap();
+        assert_eq!(decoded_job.job_id, job.job_id);
+        assert_eq!(decoded_job.parameters, job.parameters);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed_payload_is_bounded_when_inflated() {
+        let job = GxfJob::new(JobId([8u8; 16]), PrecisionLevel::BF16, 2048);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+
+        // 64 MiB of zeros compress to a few kilobytes
+        envelope.payload = zstd::encode_all(vec![0u8; 64 << 20].as_slice(), 19).unwrap();
+        envelope.meta.payload_compression = PayloadCompression::Zstd;
+        assert!(envelope.payload.len() < MAX_PAYLOAD_BYTES);
+
+        assert!(matches!(envelope.validate(), Err(GxfError::InvalidPayload(_))));
+        assert!(matches!(envelope.deserialize_job(), Err(GxfError::InvalidPayload(_))));
+
+        // Parsing leaves the payload compressed; validation rejects it
+        let parsed = GxfEnvelope::from_json(&envelope.to_json().unwrap()).unwrap();
+        assert!(matches!(parsed.validate(), Err(GxfError::InvalidPayload(_))));
+    }
+
+    #[test]
+    fn test_expiry_boundary_with_skew() {
+        let mut meta = GxfMetadata::new(64).unwrap();
+        meta.created_at = 900;
+        meta.expires_at = Some(1_000);
+
+        // Without tolerance the envelope is expired at exactly `expires_at`
+        assert!(!meta.is_expired_at_with_skew(999, 0));
+        assert!(meta.is_expired_at_with_skew(1_000, 0));
+
+        // A skew window keeps it alive until `expires_at + skew`
+        assert!(!meta.is_expired_at_with_skew(1_000, 5));
+        assert!(!meta.is_expired_at_with_skew(1_004, 5));
+        assert!(meta.is_expired_at_with_skew(1_005, 5));
+        assert!(meta.check_expiry_at_with_skew(1_004, 5).is_ok());
+        assert!(matches!(
+            meta.check_expiry_at_with_skew(1_005, 5),
+            Err(GxfError::Expired { expires_at: 1_000, current_time: 1_005 })
+        ));
+
+        meta.expires_at = None;
+        assert!(!meta.is_expired_at_with_skew(u64::MAX, 0));
+        assert!(meta.check_expiry_at_with_skew(u64::MAX, 0).is_ok());
+    }
+
+    #[test]
+    fn test_from_json_enforces_parse_limits() {
+        let mut job = GxfJob::new(JobId([4u8; 16]), PrecisionLevel::BF16, 2048);
+        job.parameters.insert("batch_size".to_string(), "8".to_string());
+        let normal = GxfEnvelope::from_job(job.clone(), 64).unwrap();
+        assert!(GxfEnvelope::from_json(&normal.to_json().unwrap()).is_ok());
+
+        // 10k job parameters, well under the payload size limit. `from_job`
+        // would refuse them, so the payload is encoded by hand
+        for i in 0..10_000 {
+            job.parameters.insert(format!("p{}", i), "x".to_string());
+        }
+        let mut bloated_job = normal.clone();
+        bloated_job.payload = serde_json::to_vec(&job).unwrap();
+        assert!(matches!(bloated_job.validate(), Err(GxfError::InvalidPayload(_))));
+        assert!(matches!(
+            GxfEnvelope::from_json(&bloated_job.to_json().unwrap()),
+            Err(GxfError::InvalidPayload(_))
+        ));
+
+        // 10k metadata fields
+        let mut bloated_meta = normal.clone();
+        for i in 0..10_000 {
+            bloated_meta.meta.additional_fields.insert(format!("f{}", i), "x".to_string());
+        }
+        assert!(matches!(
+            GxfEnvelope::from_json(&bloated_meta.to_json().unwrap()),
+            Err(GxfError::InvalidPayload(_))
+        ));
+
+        // Callers can raise the limits for trusted input
+        let relaxed = ParseLimits { max_entries: 20_000, ..ParseLimits::default() };
+        assert!(GxfEnvelope::from_json_limited(&bloated_meta.to_json().unwrap(), &relaxed).is_ok());
+
+        // Bare jobs go through the same limits
+        assert!(GxfJob::from_json(&serde_json::to_vec(&normal.deserialize_job().unwrap()).unwrap()).is_ok());
+        assert!(matches!(
+            GxfJob::from_json(&serde_json::to_vec(&job).unwrap()),
+            Err(GxfError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_metadata_age() {
+        let meta = GxfMetadata::new(64).unwrap();
+        assert!(meta.age_secs() <= 1);
+
+        let mut meta = GxfMetadata::new(64).unwrap();
+        meta.created_at -= 300;
+        assert!((300..=301).contains(&meta.age_secs()));
+
+        // A creation time ahead of the local clock reports zero, not an underflow
+        assert_eq!(meta.age_secs_at(meta.created_at - 10), 0);
+        assert_eq!(meta.age_secs_at(meta.created_at + 42), 42);
+    }
+
+    #[cfg(feature = "datetime")]
+    #[test]
+    fn test_created_at_datetime() {
+        let mut meta = GxfMetadata::new(64).unwrap();
+        meta.created_at = 1_704_067_200;
+        assert_eq!(meta.created_at_datetime().unwrap(), "2024-01-01T00:00:00Z");
+
+        meta.created_at = u64::MAX;
+        assert!(matches!(meta.created_at_datetime(), Err(GxfError::InvalidMetadata(_))));
+    }
+
+    #[test]
+    fn test_expiry_with_mock_clock() {
+        let mut meta = GxfMetadata::new(64).unwrap();
+        meta.created_at = 900;
+        meta.expires_at = Some(1_000);
+        let clock = MockClock::new(999);
+
+        // One second before expiry
+        assert!(!meta.is_expired_at(clock.now_secs()));
+        assert!(meta.validate_at(clock.now_secs()).is_ok());
+
+        // Exactly at `expires_at`
+        clock.advance(1);
+        assert!(meta.is_expired_at(clock.now_secs()));
+        assert!(matches!(
+            meta.validate_at(clock.now_secs()),
+            Err(GxfError::Expired { expires_at: 1_000, current_time: 1_000 })
+        ));
+
+        // One second after
+        clock.advance(1);
+        assert!(meta.is_expired_at(clock.now_secs()));
+        assert!(matches!(
+            meta.validate_at(clock.now_secs()),
+            Err(GxfError::Expired { current_time: 1_001, .. })
+        ));
+        assert!(meta.validate_at_with_skew(clock.now_secs(), 2).is_ok());
+    }
+
+    #[test]
+    fn test_validate_with_skew_accepts_recently_expired() {
+        let job = GxfJob::new(JobId([2u8; 16]), PrecisionLevel::INT8, 64);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        envelope.meta.created_at = now - 100;
+        envelope.meta.expires_at = Some(now - 2);
+
+        assert!(envelope.meta.is_expired());
+        assert!(matches!(envelope.validate(), Err(GxfError::Expired { .. })));
+
+        assert!(!envelope.meta.is_expired_with_skew(30));
+        assert!(envelope.validate_with_skew(30).is_ok());
+        assert!(envelope.validate_with_skew(1).is_err());
+    }
+
+    #[test]
+    fn test_validate_strict_routing_hints() {
+        let lanes = [LaneId(0), LaneId(1)];
+        let slps = [SlpId("slp-eu-west-1".to_string())];
+        let job = GxfJob::new(JobId([4u8; 16]), PrecisionLevel::BF16, 512);
+
+        let mut meta = GxfMetadata::new(150).unwrap();
+        meta.target_lane = Some("1".to_string());
+        meta.source_slp = Some("slp-eu-west-1".to_string());
+        let envelope = GxfEnvelope::from_job_with_meta(job.clone(), meta.clone()).unwrap();
+        assert!(envelope.validate_strict(&lanes, &slps).is_ok());
+
+        // Envelopes without hints pass strict validation
+        let bare = GxfEnvelope::from_job(job.clone(), 150).unwrap();
+        assert!(bare.validate_strict(&lanes, &slps).is_ok());
+
+        for lane in ["7", "flash"] {
+            let mut unknown_lane = meta.clone();
+            unknown_lane.target_lane = Some(lane.to_string());
+            let envelope = GxfEnvelope::from_job_with_meta(job.clone(), unknown_lane).unwrap();
+            assert!(envelope.validate().is_ok());
+            assert!(matches!(
+                envelope.validate_strict(&lanes, &slps),
+                Err(GxfError::InvalidMetadata(_))
+            ));
+        }
+
+        let mut unknown_slp = meta;
+        unknown_slp.source_slp = Some("slp-unknown".to_string());
+        let envelope = GxfEnvelope::from_job_with_meta(job, unknown_slp).unwrap();
+        assert!(matches!(
+            envelope.validate_strict(&lanes, &slps),
+            Err(GxfError::InvalidMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn test_gxf_envelope_from_job_with_meta() {
+        let job = GxfJob::new(JobId([4u8; 16]), PrecisionLevel::E5M2, 512);
+        let mut meta = GxfMetadata::with_ttl(150, 300).unwrap();
+        meta.target_lane = Some("1".to_string());
+        meta.source_slp = Some("slp-eu-west-1".to_string());
+        meta.payload_encoding = PayloadEncoding::Bincode;
+        let expires_at = meta.expires_at;
+
+        let envelope = GxfEnvelope::from_job_with_meta(job.clone(), meta).unwrap();
+        assert!(envelope.validate().is_ok());
+        let slps = [SlpId("slp-eu-west-1".to_string())];
+        assert!(envelope.meta.validate_routing_hints(&[LaneId(0), LaneId(1)], &slps).is_ok());
+        assert_eq!(envelope.meta.target_lane.as_deref(), Some("1"));
+        assert_eq!(envelope.meta.expires_at, expires_at);
+        assert_eq!(envelope.meta.priority, 150);
+
+        let decoded = GxfEnvelope::from_json(&envelope.to_json().unwrap()).unwrap();
+        assert_eq!(decoded.deserialize_job().unwrap().job_id, job.job_id);
+        assert_eq!(decoded.meta.target_lane.as_deref(), Some("1"));
+
+        // Metadata is validated before use
+        let mut expired = GxfMetadata::new(64).unwrap();
+        expired.expires_at = Some(1);
+        assert!(matches!(
+            GxfEnvelope::from_job_with_meta(job.clone(), expired),
+            Err(GxfError::Expired { .. })
+        ));
+
+        let mut compressed = GxfMetadata::new(64).unwrap();
+        compressed.payload_compression = PayloadCompression::Zstd;
+        assert!(matches!(
+            GxfEnvelope::from_job_with_meta(job, compressed),
+            Err(GxfError::InvalidMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn test_output_hash_binds_the_job() {
+        let job = GxfJob::new(JobId([6u8; 16]), PrecisionLevel::BF16, 1024);
+        let hash = job.output_hash().unwrap();
+        assert!(job.verify_output(&hash));
+
+        // Same job ID with different inputs
+        let mut other = job.clone();
+        other.kv_cache_seq_len = 2048;
+        assert!(!other.verify_output(&hash));
+
+        let mut forged = hash;
+        forged[0] ^= 0xff;
+        assert!(!job.verify_output(&forged));
+    }
+
+    #[test]
+    fn test_canonical_bytes_ignore_insertion_order() {
+        let keys = ["model", "batch_size", "region", "dimensions", "owner", "tier"];
+        let build = |order: &mut dyn Iterator<Item = &&str>| {
+            let mut job = GxfJob::new(JobId([9u8; 16]), PrecisionLevel::FP8, 256);
+            for key in order {
+                job.parameters.insert(key.to_string(), format!("{}-value", key));
+            }
+            job
+        };
+        let forward = build(&mut keys.iter());
+        let reverse = build(&mut keys.iter().rev());
+
+        let bytes = forward.canonical_bytes().unwrap();
+        assert_eq!(bytes, reverse.canonical_bytes().unwrap());
+
+        // Canonical bytes are ordinary job JSON
+        let decoded: GxfJob = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.parameters, forward.parameters);
+
+        // Envelopes built from either job carry identical payloads
+        for encoding in [PayloadEncoding::Json, PayloadEncoding::Bincode] {
+            let a = GxfEnvelope::from_job_with_encoding(forward.clone(), 64, encoding).unwrap();
+            let b = GxfEnvelope::from_job_with_encoding(reverse.clone(), 64, encoding).unwrap();
+            assert_eq!(a.payload, b.payload);
+            assert_eq!(b.deserialize_job().unwrap().parameters, forward.parameters);
+        }
+
+        // Envelope canonical bytes are stable across metadata insertion order
+        let mut a = GxfEnvelope::from_job(forward, 64).unwrap();
+        let mut b = a.clone();
+        b.meta.additional_fields.clear();
+        for key in keys {
+            a.meta.additional_fields.insert(key.to_string(), "x".to_string());
+        }
+        for key in keys.iter().rev() {
+            b.meta.additional_fields.insert(key.to_string(), "x".to_string());
+        }
+        let bytes = a.canonical_bytes().unwrap();
+        assert_eq!(bytes, b.canonical_bytes().unwrap());
+        let decoded = GxfEnvelope::from_json(&bytes).unwrap();
+        assert_eq!(decoded.meta.additional_fields, a.meta.additional_fields);
+    }
+
+    #[test]
+    fn test_envelope_fingerprint() {
+        let job = GxfJob::new(JobId([3u8; 16]), PrecisionLevel::FP8, 2048);
+        let mut envelope = GxfEnvelope::from_job(job.clone(), 64).unwrap();
+        envelope.meta.additional_fields.insert("b".to_string(), "2".to_string());
+        envelope.meta.additional_fields.insert("a".to_string(), "1".to_string());
+
+        let fingerprint = envelope.fingerprint();
+        assert_eq!(fingerprint.len(), FINGERPRINT_BYTES * 2);
+        assert!(fingerprint.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let round_tripped = GxfEnvelope::from_json(&envelope.to_json().unwrap()).unwrap();
+        assert_eq!(round_tripped.fingerprint(), fingerprint);
+
+        let mut other = envelope.clone();
+        other.meta.priority = 65;
+        assert_ne!(other.fingerprint(), fingerprint);
+        let different_job = GxfEnvelope::from_job(GxfJob::new(JobId([4u8; 16]), PrecisionLevel::FP8, 2048), 64).unwrap();
+        assert_ne!(different_job.fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn test_gxf_envelope_migrate_v2() {
+        let job = GxfJob::new(JobId([8u8; 16]), PrecisionLevel::INT8, 128);
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let v2 = serde_json::json!({
+            "meta": {
+                "schema_version": 2,
+                "priority": 200,
+                "created_at": created_at,
+                "source_slp": "slp-legacy",
+                "additional_fields": {"lane": "flash", "ttl_secs": "600", "owner": "ops"}
+            },
+            "payload": serde_json::to_vec(&job).unwrap(),
+        });
+        let data = serde_json::to_vec(&v2).unwrap();
+
+        let legacy = GxfEnvelope::from_json(&data).unwrap();
+        assert_eq!(
+            legacy.validate(),
+            Err(GxfError::InvalidVersion { expected: GXF_VERSION, actual: 2 })
+        );
+
+        let migrated = GxfEnvelope::from_json_migrated(&data).unwrap();
+        assert!(migrated.validate().is_ok());
+        assert_eq!(migrated.meta.schema_version, GXF_VERSION);
+        assert_eq!(migrated.meta.priority, 200);
+        assert_eq!(migrated.meta.target_lane.as_deref(), Some("0"));
+        let slps = [SlpId("slp-legacy".to_string())];
+        assert!(migrated.meta.validate_routing_hints(&[LaneId(0), LaneId(1)], &slps).is_ok());
+        assert_eq!(migrated.meta.expires_at, Some(created_at + 600));
+        assert_eq!(migrated.meta.source_slp.as_deref(), Some("slp-legacy"));
+        assert_eq!(migrated.meta.additional_fields.len(), 1);
+        assert_eq!(migrated.deserialize_job().unwrap().job_id, job.job_id);
+
+        // Migration is idempotent once at the current version
+        assert_eq!(migrated.migrate().unwrap().meta.expires_at, migrated.meta.expires_at);
+    }
+
+    #[test]
+    fn test_gxf_envelope_migrate_rejects_unknown_versions() {
+        let job = GxfJob::new(JobId([8u8; 16]), PrecisionLevel::INT8, 128);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+
+        envelope.meta.schema_version = 1;
+        assert_eq!(
+            envelope.migrate().unwrap_err(),
+            GxfError::InvalidVersion { expected: GXF_VERSION, actual: 1 }
+        );
+
+        envelope.meta.schema_version = 2;
+        envelope.meta.additional_fields.insert("lane".to_string(), "Deep".to_string());
+        assert_eq!(envelope.migrate().unwrap().meta.target_lane.as_deref(), Some("1"));
+        envelope.meta.additional_fields.insert("lane".to_string(), "turbo".to_string());
+        assert!(matches!(envelope.migrate(), Err(GxfError::InvalidMetadata(_))));
+        envelope.meta.additional_fields.insert("lane".to_string(), "deep".to_string());
+        envelope.meta.additional_fields.insert("ttl_secs".to_string(), "soon".to_string());
+        assert!(matches!(envelope.migrate(), Err(GxfError::InvalidMetadata(_))));
+    }
+
+    #[cfg(feature = "vdf")]
+    #[test]
+    fn test_gxf_envelope_vdf_proof() {
+        let job = GxfJob::new(JobId([5u8; 16]), PrecisionLevel::FP8, 512);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        assert_eq!(envelope.verify_vdf_proof(100), Err(GxfError::MissingVdfProof));
+
+        envelope.attach_vdf_proof(200).unwrap();
+        assert!(envelope.verify_vdf_proof(100).is_ok());
+        assert_eq!(
+            envelope.verify_vdf_proof(500),
+            Err(GxfError::VdfDifficultyTooLow { iterations: 200, required: 500 })
+        );
+
+        // The proof survives the wire format
+        let decoded = GxfEnvelope::from_bincode(&envelope.to_bincode().unwrap()).unwrap();
+        assert!(decoded.verify_vdf_proof(100).is_ok());
+
+        // A tampered output or a different job invalidates the proof
+        let mut tampered = envelope.clone();
+        tampered.vdf_proof.as_mut().unwrap().output[0] ^= 0xff;
+        assert_eq!(tampered.verify_vdf_proof(100), Err(GxfError::InvalidVdfProof));
+
+        let other = GxfJob::new(JobId([6u8; 16]), PrecisionLevel::FP8, 512);
+        let mut swapped = GxfEnvelope::from_job(other, 64).unwrap();
+        swapped.vdf_proof = envelope.vdf_proof.clone();
+        assert_eq!(swapped.verify_vdf_proof(100), Err(GxfError::InvalidVdfProof));
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn test_gxf_envelope_compressed_payload_requires_feature() {
+        let job = GxfJob::new(JobId([3u8; 16]), PrecisionLevel::BF16, 2048);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        envelope.meta.payload_compression = PayloadCompression::Zstd;
+
+        assert!(matches!(envelope.deserialize_job(), Err(GxfError::Deserialization(_))));
+    }
+
+    #[test]
+    fn test_deserialize_job_distinguishes_encoding_from_schema_errors() {
+        let job = GxfJob::new(JobId([4u8; 16]), PrecisionLevel::BF16, 2048);
+        let mut envelope = GxfEnvelope::from_job(job, 64).unwrap();
+
+        envelope.payload = vec![0xff, 0xfe, b'{', 0x00];
+        assert!(matches!(envelope.deserialize_job(), Err(GxfError::InvalidPayload(_))));
+
+        envelope.payload = b"{\"job_id\": ".to_vec();
+        assert!(matches!(envelope.deserialize_job(), Err(GxfError::InvalidPayload(_))));
+
+        envelope.payload = serde_json::to_vec(&serde_json::json!({"precision": "BF16"})).unwrap();
+        assert!(matches!(envelope.deserialize_job(), Err(GxfError::Deserialization(_))));
+    }
+
     #[test]
     fn test_gxf_envelope_job_roundtrip() {
         let job_id = JobId([1u8; 16]);
@@ -0,0 +1,137 @@
+//! Structural limits for untrusted JSON
+//!
+//! `serde_json` allocates for whatever it is given, so a small envelope with
+//! thousands of map entries or deep nesting can cost far more memory than
+//! its size suggests. `ParseLimits::check_json` scans the raw bytes first,
+//! keeping only a depth-bounded stack, and rejects input over any limit
+//! before it is deserialized.
+
+use crate::GxfError;
+
+/// Default maximum entries in any JSON object (`parameters`, `additional_fields`, ...)
+pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+/// Default maximum length of any JSON string, key or value, in bytes
+pub const DEFAULT_MAX_STRING_LEN: usize = 64 * 1024;
+/// Default maximum nesting of JSON objects and arrays
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Limits applied to JSON input before deserialization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum entries in a single object
+    pub max_entries: usize,
+    /// Maximum length of a string (escapes counted as written)
+    pub max_string_len: usize,
+    /// Maximum nesting of objects and arrays
+    pub max_depth: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_string_len: DEFAULT_MAX_STRING_LEN,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl ParseLimits {
+    /// Reject `data` if it exceeds any limit
+    ///
+    /// Only structure is checked; malformed JSON is left for the
+    /// deserializer to report.
+    pub fn check_json(&self, data: &[u8]) -> Result<(), GxfError> {
+        // Entry count per open container; `None` for arrays
+        let mut stack: Vec<Option<usize>> = Vec::new();
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut string_len = 0;
+
+        for &byte in data {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if byte == b'\\' {
+                    escaped = true;
+                } else if byte == b'"' {
+                    in_string = false;
+                    continue;
+                }
+                string_len += 1;
+                if string_len > self.max_string_len {
+                    return Err(GxfError::InvalidPayload(format!(
+                        "JSON string exceeds {} bytes",
+                        self.max_string_len
+                    )));
+                }
+                continue;
+            }
+
+            match byte {
+                b'"' => {
+                    in_string = true;
+                    string_len = 0;
+                }
+                b'{' | b'[' => {
+                    if stack.len() == self.max_depth {
+                        return Err(GxfError::InvalidPayload(format!(
+                            "JSON nesting exceeds {} levels",
+                            self.max_depth
+                        )));
+                    }
+                    stack.push((byte == b'{').then_some(0));
+                }
+                b'}' | b']' => {
+                    stack.pop();
+                }
+                // Each key/value separator in an object is one entry
+                b':' => {
+                    if let Some(Some(entries)) = stack.last_mut() {
+                        *entries += 1;
+                        if *entries > self.max_entries {
+                            return Err(GxfError::InvalidPayload(format!(
+                                "JSON object exceeds {} entries",
+                                self.max_entries
+                            )));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> ParseLimits {
+        ParseLimits { max_entries: 2, max_string_len: 5, max_depth: 2 }
+    }
+
+    #[test]
+    fn test_within_limits() {
+        assert!(limits().check_json(br#"{"a":[1,2,3],"b":{"c":"hello"}}"#).is_ok());
+    }
+
+    #[test]
+    fn test_each_limit_rejects() {
+        let too_many = br#"{"a":1,"b":2,"c":3}"#;
+        let too_long = br#"{"a":"toolong"}"#;
+        let too_deep = br#"{"a":{"b":[1]}}"#;
+
+        for input in [&too_many[..], &too_long[..], &too_deep[..]] {
+            assert!(matches!(limits().check_json(input), Err(GxfError::InvalidPayload(_))));
+        }
+    }
+
+    #[test]
+    fn test_structure_inside_strings_is_ignored() {
+        // Braces, colons and escaped quotes inside strings are not structure
+        assert!(limits().check_json(br#"{"a":"{:[\"","b":"::"}"#).is_ok());
+    }
+}
@@ -1,5 +1,11 @@
+use std::env;
+use std::path::PathBuf;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
     tonic_build::configure()
+        // Served by the reflection service so tools like grpcurl can introspect the API
+        .file_descriptor_set_path(out_dir.join("gix_descriptor.bin"))
         .compile(&["../../proto/gix.proto"], &["../../proto"])?;
     Ok(())
 }
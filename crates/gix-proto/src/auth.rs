@@ -0,0 +1,311 @@
+//! Request authentication with Dilithium-signed tokens
+//!
+//! A client signs the current Unix timestamp with its Dilithium key and sends
+//! the timestamp, public key and signature in the `authorization` metadata
+//! header. [`AuthInterceptor`] accepts the request only if the key has been
+//! registered and the token is fresh, and tags it with the caller's
+//! [`AuthenticatedKey`].
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use gix_crypto::pqc::dilithium::{self, KeyPair, PublicKey, Signature, SignatureError};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// Metadata header carrying the token
+pub const AUTHORIZATION_HEADER: &str = "authorization";
+/// Default accepted token age (and clock skew) in seconds
+pub const DEFAULT_TOKEN_MAX_AGE_SECS: u64 = 300;
+
+/// Scheme prefix of the header value
+const TOKEN_SCHEME: &str = "Gix ";
+/// Domain separation prefix for the signed message
+const TOKEN_DOMAIN: &str = "gix-auth-v1:";
+
+/// Authentication errors
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Missing authorization token")]
+    MissingToken,
+    #[error("Malformed authorization token: {0}")]
+    MalformedToken(String),
+    #[error("Token timestamp {timestamp} outside accepted window at {now}")]
+    Expired { timestamp: u64, now: u64 },
+    #[error("Public key is not registered")]
+    UnknownKey,
+    #[error("Invalid token signature")]
+    InvalidSignature,
+    #[error("Signing failed: {0}")]
+    Signing(#[from] SignatureError),
+}
+
+impl From<AuthError> for Status {
+    fn from(e: AuthError) -> Self {
+        Status::unauthenticated(e.to_string())
+    }
+}
+
+/// Current Unix time in seconds
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Message signed for a token issued at `timestamp`
+fn token_message(timestamp: u64) -> Vec<u8> {
+    format!("{}{}", TOKEN_DOMAIN, timestamp).into_bytes()
+}
+
+/// Verified caller of a request, stored in its extensions by [`AuthInterceptor`]
+///
+/// Holds the hex Blake3 fingerprint of the caller's public key, short enough
+/// to key per-client state such as rate limits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AuthenticatedKey(pub String);
+
+impl AuthenticatedKey {
+    /// Identity of the holder of `public_key`
+    pub fn from_public_key(public_key: &[u8]) -> Self {
+        let hash = gix_crypto::hash_blake3(public_key);
+        AuthenticatedKey(hash[..16].iter().map(|b| format!("{:02x}", b)).collect())
+    }
+}
+
+/// Signed authentication token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthToken {
+    /// Issue time (Unix epoch in seconds)
+    pub timestamp: u64,
+    /// Signer's public key bytes
+    pub public_key: Vec<u8>,
+    /// Signature over the timestamp
+    pub signature: Vec<u8>,
+}
+
+impl AuthToken {
+    /// Sign a token for the given timestamp
+    pub fn sign(keypair: &KeyPair, timestamp: u64) -> Result<Self, AuthError> {
+        let signature = dilithium::sign_detached(&token_message(timestamp), &keypair.secret)?;
+        Ok(AuthToken {
+            timestamp,
+            public_key: keypair.public.bytes.clone(),
+            signature: signature.bytes,
+        })
+    }
+
+    /// Sign a token for the current time
+    pub fn now(keypair: &KeyPair) -> Result<Self, AuthError> {
+        Self::sign(keypair, unix_now())
+    }
+
+    /// Encode as an `authorization` header value
+    pub fn to_header_value(&self) -> String {
+        format!(
+            "{}{}.{}.{}",
+            TOKEN_SCHEME,
+            self.timestamp,
+            BASE64.encode(&self.public_key),
+            BASE64.encode(&self.signature)
+        )
+    }
+
+    /// Parse an `authorization` header value
+    pub fn parse(value: &str) -> Result<Self, AuthError> {
+        let body = value
+            .strip_prefix(TOKEN_SCHEME)
+            .ok_or_else(|| AuthError::MalformedToken("missing Gix scheme".to_string()))?;
+
+        let mut parts = body.split('.');
+        let (Some(timestamp), Some(public_key), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(AuthError::MalformedToken("expected timestamp.key.signature".to_string()));
+        };
+
+        let decode = |part: &str| BASE64.decode(part).map_err(|e| AuthError::MalformedToken(e.to_string()));
+        Ok(AuthToken {
+            timestamp: timestamp
+                .parse()
+                .map_err(|_| AuthError::MalformedToken("invalid timestamp".to_string()))?,
+            public_key: decode(public_key)?,
+            signature: decode(signature)?,
+        })
+    }
+
+    /// Attach this token to outgoing request metadata
+    pub fn insert_into(&self, metadata: &mut MetadataMap) {
+        let value = MetadataValue::try_from(self.to_header_value())
+            .expect("token header value is ASCII");
+        metadata.insert(AUTHORIZATION_HEADER, value);
+    }
+}
+
+/// Interceptor accepting only requests signed by registered keys
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    /// Registered public keys
+    allowed_keys: Arc<RwLock<Vec<PublicKey>>>,
+    /// Accepted token age (and clock skew) in seconds
+    max_age_secs: u64,
+}
+
+impl AuthInterceptor {
+    /// Create an interceptor with no registered keys
+    pub fn new() -> Self {
+        Self::with_max_age(DEFAULT_TOKEN_MAX_AGE_SECS)
+    }
+
+    /// Create an interceptor accepting tokens up to `max_age_secs` old
+    pub fn with_max_age(max_age_secs: u64) -> Self {
+        AuthInterceptor {
+            allowed_keys: Arc::new(RwLock::new(Vec::new())),
+            max_age_secs,
+        }
+    }
+
+    /// Register a public key allowed to make requests
+    pub fn allow_key(&self, key: PublicKey) {
+        let mut keys = self.allowed_keys.write().expect("auth key lock poisoned");
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    /// Verify request metadata against the current time
+    pub fn verify(&self, metadata: &MetadataMap) -> Result<AuthenticatedKey, AuthError> {
+        self.verify_at(metadata, unix_now())
+    }
+
+    /// Verify request metadata as of `now` (Unix epoch in seconds)
+    pub fn verify_at(&self, metadata: &MetadataMap, now: u64) -> Result<AuthenticatedKey, AuthError> {
+        let value = metadata
+            .get(AUTHORIZATION_HEADER)
+            .ok_or(AuthError::MissingToken)?
+            .to_str()
+            .map_err(|e| AuthError::MalformedToken(e.to_string()))?;
+        let token = AuthToken::parse(value)?;
+
+        if token.timestamp.abs_diff(now) > self.max_age_secs {
+            return Err(AuthError::Expired { timestamp: token.timestamp, now });
+        }
+
+        let keys = self.allowed_keys.read().expect("auth key lock poisoned");
+        let public_key = keys
+            .iter()
+            .find(|key| key.bytes == token.public_key)
+            .ok_or(AuthError::UnknownKey)?;
+
+        let signature = Signature { bytes: token.signature };
+        dilithium::verify_detached(&token_message(token.timestamp), &signature, public_key)
+            .map_err(|_| AuthError::InvalidSignature)?;
+        Ok(AuthenticatedKey::from_public_key(&public_key.bytes))
+    }
+}
+
+impl Default for AuthInterceptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let caller = self.verify(request.metadata())?;
+        request.extensions_mut().insert(caller);
+        Ok(request)
+    }
+}
+
+/// Interceptor that authenticates only when an authenticator is configured
+#[derive(Clone, Default)]
+pub struct OptionalAuth(pub Option<AuthInterceptor>);
+
+impl Interceptor for OptionalAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        match self.0.as_mut() {
+            Some(auth) => auth.call(request),
+            None => Ok(request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_with(token: &AuthToken) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        token.insert_into(&mut metadata);
+        metadata
+    }
+
+    #[test]
+    fn test_valid_token_accepted() {
+        let keypair = KeyPair::generate();
+        let auth = AuthInterceptor::new();
+        auth.allow_key(keypair.public.clone());
+
+        let token = AuthToken::sign(&keypair, 1_000).unwrap();
+        assert_eq!(AuthToken::parse(&token.to_header_value()).unwrap(), token);
+        auth.verify_at(&metadata_with(&token), 1_010).unwrap();
+
+        // The interceptor itself accepts a fresh token
+        let mut request = Request::new(());
+        AuthToken::now(&keypair).unwrap().insert_into(request.metadata_mut());
+        let request = auth.clone().call(request).unwrap();
+        assert_eq!(
+            request.extensions().get::<AuthenticatedKey>(),
+            Some(&AuthenticatedKey::from_public_key(&keypair.public.bytes))
+        );
+
+        // Without an authenticator every request passes
+        assert!(OptionalAuth(None).call(Request::new(())).is_ok());
+        assert!(OptionalAuth(Some(auth)).call(Request::new(())).is_err());
+    }
+
+    #[test]
+    fn test_expired_token_rejected() {
+        let keypair = KeyPair::generate();
+        let auth = AuthInterceptor::with_max_age(60);
+        auth.allow_key(keypair.public.clone());
+
+        let token = AuthToken::sign(&keypair, 1_000).unwrap();
+        assert!(matches!(
+            auth.verify_at(&metadata_with(&token), 1_061),
+            Err(AuthError::Expired { timestamp: 1_000, now: 1_061 })
+        ));
+        // Tokens from too far in the future are rejected as well
+        assert!(matches!(auth.verify_at(&metadata_with(&token), 900), Err(AuthError::Expired { .. })));
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let registered = KeyPair::generate();
+        let stranger = KeyPair::generate();
+        let auth = AuthInterceptor::new();
+        auth.allow_key(registered.public.clone());
+
+        let token = AuthToken::sign(&stranger, 1_000).unwrap();
+        assert!(matches!(auth.verify_at(&metadata_with(&token), 1_000), Err(AuthError::UnknownKey)));
+    }
+
+    #[test]
+    fn test_unsigned_or_forged_request_rejected() {
+        let keypair = KeyPair::generate();
+        let auth = AuthInterceptor::new();
+        auth.allow_key(keypair.public.clone());
+
+        let status = auth.clone().call(Request::new(())).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        // A signature over a different timestamp does not verify
+        let mut token = AuthToken::sign(&keypair, 1_000).unwrap();
+        token.timestamp = 1_001;
+        assert!(matches!(auth.verify_at(&metadata_with(&token), 1_001), Err(AuthError::InvalidSignature)));
+    }
+}
@@ -0,0 +1,212 @@
+//! Mapping of GIX errors onto gRPC statuses
+//!
+//! Each status carries the error's numeric code in the `x-gix-error-code`
+//! metadata entry so clients can branch on the exact error, while the tonic
+//! code conveys its category.
+
+use gix_common::{ComplianceError, GixError};
+use gix_gxf::GxfError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tonic::metadata::MetadataValue;
+use tonic::{Code, Status};
+
+/// Metadata key holding the numeric GIX error code
+pub const ERROR_CODE_METADATA: &str = "x-gix-error-code";
+
+/// Errors with a numeric code and a gRPC status category
+pub trait StatusError: std::error::Error {
+    /// Stable numeric error code
+    fn error_code(&self) -> u32;
+    /// gRPC status code for the error's category
+    fn grpc_code(&self) -> Code;
+}
+
+impl StatusError for GixError {
+    fn error_code(&self) -> u32 {
+        self.code()
+    }
+
+    fn grpc_code(&self) -> Code {
+        match self {
+            GixError::CryptoFailure => Code::Unauthenticated,
+            GixError::Protocol(_) => Code::InvalidArgument,
+            GixError::InternalError(_) => Code::Internal,
+            GixError::NoMatch(_) => Code::NotFound,
+            GixError::StorageLocked(_) => Code::Unavailable,
+            GixError::Compliance(_) => Code::FailedPrecondition,
+            GixError::Overloaded(_) => Code::ResourceExhausted,
+        }
+    }
+}
+
+impl StatusError for ComplianceError {
+    fn error_code(&self) -> u32 {
+        GixError::from(self.clone()).code()
+    }
+
+    fn grpc_code(&self) -> Code {
+        Code::FailedPrecondition
+    }
+}
+
+impl StatusError for GxfError {
+    fn error_code(&self) -> u32 {
+        self.code()
+    }
+
+    fn grpc_code(&self) -> Code {
+        match self {
+            GxfError::Expired { .. } => Code::DeadlineExceeded,
+            GxfError::MissingVdfProof
+            | GxfError::InvalidVdfProof
+            | GxfError::VdfDifficultyTooLow { .. } => Code::Unauthenticated,
+            GxfError::Serialization(_) => Code::Internal,
+            GxfError::InvalidVersion { .. }
+            | GxfError::InvalidJobId(_)
+            | GxfError::InvalidPayload(_)
+            | GxfError::InvalidMetadata(_)
+            | GxfError::InvalidPrecision
+            | GxfError::InvalidSequenceLength(_)
+            | GxfError::Deserialization(_) => Code::InvalidArgument,
+        }
+    }
+}
+
+/// Build a status with `message`, tagged with the error's category and code
+fn status_for(err: &dyn StatusError, message: String) -> Status {
+    let mut status = Status::new(err.grpc_code(), message);
+    if let Ok(value) = MetadataValue::try_from(err.error_code().to_string()) {
+        status.metadata_mut().insert(ERROR_CODE_METADATA, value);
+    }
+    status
+}
+
+/// Convert a GIX error into a gRPC status
+pub fn to_status<E: StatusError>(err: &E) -> Status {
+    status_for(err, err.to_string())
+}
+
+/// Convert an error chain into a gRPC status
+///
+/// The first `GixError`, `GxfError` or `ComplianceError` in the source chain
+/// decides the status; chains without one become `Internal`. The message
+/// joins the whole chain.
+pub fn chain_to_status(err: &(dyn std::error::Error + 'static)) -> Status {
+    let message = std::iter::successors(Some(err), |e| e.source())
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(": ");
+
+    for cause in std::iter::successors(Some(err), |e| e.source()) {
+        if let Some(e) = cause.downcast_ref::<GixError>() {
+            return status_for(e, message);
+        }
+        if let Some(e) = cause.downcast_ref::<GxfError>() {
+            return status_for(e, message);
+        }
+        if let Some(e) = cause.downcast_ref::<ComplianceError>() {
+            return status_for(e, message);
+        }
+    }
+
+    Status::internal(message)
+}
+
+/// Status rejecting an encoded envelope longer than `max_bytes`, if it is
+///
+/// Checked before parsing so oversized input is never deserialized.
+pub fn oversized_envelope(len: usize, max_bytes: usize) -> Option<Status> {
+    (len > max_bytes).then(|| {
+        Status::invalid_argument(format!(
+            "envelope too large: {} bytes exceeds limit of {}",
+            len, max_bytes
+        ))
+    })
+}
+
+/// Room left for request fields besides the envelope in [`decoding_limit`]
+pub const REQUEST_OVERHEAD_BYTES: usize = 64 * 1024;
+
+/// gRPC decoding limit for a server accepting envelopes of up to `max_envelope_bytes`
+///
+/// tonic rejects larger messages before the service sees them, 4 MiB by
+/// default, so raising the envelope limit alone has no effect above that.
+pub fn decoding_limit(max_envelope_bytes: usize) -> usize {
+    max_envelope_bytes.saturating_add(REQUEST_OVERHEAD_BYTES)
+}
+
+/// Status rejecting a new request while the server drains for shutdown, if it is
+///
+/// Requests already in flight are unaffected and run to completion.
+pub fn draining(flag: &AtomicBool) -> Option<Status> {
+    flag.load(Ordering::SeqCst).then(|| Status::unavailable("draining"))
+}
+
+/// Read the numeric GIX error code from a status, if present
+pub fn error_code(status: &Status) -> Option<u32> {
+    status
+        .metadata()
+        .get(ERROR_CODE_METADATA)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("Routing failed")]
+    struct Wrapper(#[source] GxfError);
+
+    #[test]
+    fn test_gix_errors_map_to_codes() {
+        let status = to_status(&GixError::NoMatch("no provider".to_string()));
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(error_code(&status), Some(1004));
+
+        assert_eq!(to_status(&GixError::CryptoFailure).code(), Code::Unauthenticated);
+        assert_eq!(to_status(&GixError::Protocol("bad".to_string())).code(), Code::InvalidArgument);
+        assert_eq!(to_status(&GixError::InternalError("oops".to_string())).code(), Code::Internal);
+
+        let status = to_status(&GixError::Compliance("region mismatch".to_string()));
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert_eq!(error_code(&status), Some(1006));
+
+        let status = to_status(&GixError::Overloaded("All lanes at capacity".to_string()));
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(error_code(&status), Some(1007));
+
+        let status = to_status(&ComplianceError::ResidencyViolation("region mismatch".to_string()));
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert_eq!(error_code(&status), Some(1006));
+    }
+
+    #[test]
+    fn test_gxf_errors_map_to_codes() {
+        let status = to_status(&GxfError::Expired { expires_at: 1, current_time: 2 });
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+        assert_eq!(error_code(&status), Some(2005));
+
+        let status = to_status(&GxfError::InvalidSequenceLength(0));
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(error_code(&status), Some(2007));
+
+        assert_eq!(to_status(&GxfError::InvalidVdfProof).code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn test_error_chain_mapping() {
+        let status = chain_to_status(&Wrapper(GxfError::InvalidPrecision));
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(error_code(&status), Some(2006));
+        assert_eq!(status.message(), "Routing failed: Invalid precision level");
+
+        let io = std::io::Error::other("disk full");
+        let status = chain_to_status(&io);
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(error_code(&status), None);
+    }
+}
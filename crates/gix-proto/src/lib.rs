@@ -7,7 +7,9 @@
 //!
 //! - **RouterService** - Anonymized job routing (AJR) on port 50051
 //! - **AuctionService** - Global compute auction (GCAM) on port 50052
+//! - **ProviderAdminService** - GCAM provider management, served alongside the auction
 //! - **ExecutionService** - Secure execution envelope (GSEE) on port 50053
+//! - **HealthService** - Readiness checks, served by every daemon
 //!
 //! ## Usage
 //!
@@ -52,6 +54,8 @@
 //!     let request = tonic::Request::new(RunAuctionRequest {
 //!         job: vec![],
 //!         priority: 128,
+//!         source_slp: String::new(),
+//!         envelope: vec![],
 //!     });
 //!     
 //!     let response = client.run_auction(request).await?;
@@ -59,6 +63,37 @@
 //! }
 //! ```
 //!
+//! ## Authentication
+//!
+//! The [`auth`] module provides an interceptor that accepts only requests
+//! carrying a fresh token signed by a registered Dilithium key.
+//!
+//! ## Errors
+//!
+//! The [`errors`] module maps `GixError` and `GxfError` onto gRPC statuses,
+//! tagging each with a numeric error code clients can branch on.
+//!
+//! ## Tracing
+//!
+//! The [`trace`] module propagates a per-job correlation id in request
+//! metadata so one job's log lines can be matched across services.
+//!
+//! ## TLS
+//!
+//! Servers and clients speak plaintext by default. The [`tls`] module loads PEM
+//! files into tonic TLS configs and connects channels with an optional CA.
+//!
+//! ## Reflection
+//!
+//! The [`reflection`] module builds a gRPC server reflection service from the
+//! compiled `gix.v1` descriptors, so `grpcurl` works without the proto file.
+//!
+//! ## Testing
+//!
+//! With the `test-util` feature, the `mock` module provides in-memory
+//! implementations of the router, auction and execution services and a helper
+//! serving them on an ephemeral port.
+//!
 //! ## Protocol Version
 //!
 //! This crate implements GIX Network Protocol v0.2.0 as defined in `specs/integrated/network_protocol_v0.2.0.md`.
@@ -67,10 +102,22 @@ pub mod v1 {
     tonic::include_proto!("gix.v1");
 }
 
+pub mod auth;
+pub mod errors;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod reflection;
+pub mod tls;
+pub mod trace;
+
 // Re-export clients and servers for easier access
 pub use v1::router_service_client::RouterServiceClient;
 pub use v1::router_service_server::{RouterService, RouterServiceServer};
 pub use v1::auction_service_client::AuctionServiceClient;
 pub use v1::auction_service_server::{AuctionService, AuctionServiceServer};
+pub use v1::provider_admin_service_client::ProviderAdminServiceClient;
+pub use v1::provider_admin_service_server::{ProviderAdminService, ProviderAdminServiceServer};
 pub use v1::execution_service_client::ExecutionServiceClient;
 pub use v1::execution_service_server::{ExecutionService, ExecutionServiceServer};
+pub use v1::health_service_client::HealthServiceClient;
+pub use v1::health_service_server::{HealthService, HealthServiceServer};
@@ -0,0 +1,348 @@
+//! In-memory mock servers for tests
+//!
+//! [`MockServices`] implements the router, auction, execution and health
+//! services with canned successful answers, counting the requests it sees.
+//! [`spawn_mock_server`] serves all of them on an ephemeral local port so
+//! clients can be tested without the service binaries, and
+//! [`spawn_mock_server_on`] restarts them on a known address.
+//!
+//! Enabled by the `test-util` feature.
+
+use crate::errors::to_status;
+use crate::v1::{
+    CancelJobRequest, CancelJobResponse, ExecuteJobRequest, ExecuteJobResponse, ExecutionStatus,
+    GetAuctionHistoryRequest, GetAuctionHistoryResponse, GetAuctionStatsRequest,
+    GetAuctionStatsResponse, GetRouterStatsRequest, GetRouterStatsResponse, GetRuntimeStatsRequest,
+    GetRuntimeStatsResponse, HealthCheckRequest, HealthCheckResponse, JobId, LaneId,
+    ReleaseCapacityRequest, ReleaseCapacityResponse, RouteEnvelopeRequest, RouteEnvelopeResponse,
+    RunAuctionRequest, RunAuctionResponse, ServingStatus, SlpId, StreamStatsRequest,
+};
+use crate::{
+    AuctionService, AuctionServiceServer, ExecutionService, ExecutionServiceServer, HealthService,
+    HealthServiceServer, RouterService, RouterServiceServer,
+};
+use gix_gxf::{GxfEnvelope, GxfError};
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::StreamExt;
+use tonic::transport::server::{Connected, TcpConnectInfo};
+use tonic::{Request, Response, Status};
+
+/// Provider every mock auction is awarded to
+pub const MOCK_SLP_ID: &str = "slp-mock";
+
+/// Clearing price of every mock auction
+pub const MOCK_PRICE: u64 = 1000;
+
+/// Requests handled by the mock services
+#[derive(Debug, Default)]
+pub struct MockCounters {
+    /// Envelopes routed
+    pub routed: AtomicU64,
+    /// Auctions run
+    pub auctions: AtomicU64,
+    /// Capacity releases
+    pub released: AtomicU64,
+    /// Jobs executed
+    pub executed: AtomicU64,
+}
+
+/// Router, auction, execution and health services backed by counters
+#[derive(Debug, Clone, Default)]
+pub struct MockServices {
+    counters: Arc<MockCounters>,
+}
+
+impl MockServices {
+    /// Create mock services with zeroed counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counters shared by every clone of these services
+    pub fn counters(&self) -> &MockCounters {
+        &self.counters
+    }
+}
+
+/// Job id of an encoded envelope, or `None` when no envelope was sent
+fn envelope_job_id(envelope: &[u8]) -> Result<Option<JobId>, GxfError> {
+    if envelope.is_empty() {
+        return Ok(None);
+    }
+    let job = GxfEnvelope::from_json(envelope)?.deserialize_job()?;
+    Ok(Some(JobId { id: job.job_id.0.to_vec() }))
+}
+
+#[tonic::async_trait]
+impl RouterService for MockServices {
+    async fn route_envelope(
+        &self,
+        request: Request<RouteEnvelopeRequest>,
+    ) -> Result<Response<RouteEnvelopeResponse>, Status> {
+        envelope_job_id(&request.get_ref().envelope).map_err(|e| to_status(&e))?;
+        self.counters.routed.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(RouteEnvelopeResponse {
+            lane_id: Some(LaneId { id: 0 }),
+            success: true,
+            error: String::new(),
+        }))
+    }
+
+    async fn get_router_stats(
+        &self,
+        _request: Request<GetRouterStatsRequest>,
+    ) -> Result<Response<GetRouterStatsResponse>, Status> {
+        Ok(Response::new(GetRouterStatsResponse {
+            total_routed: self.counters.routed.load(Ordering::SeqCst),
+            ..Default::default()
+        }))
+    }
+}
+
+impl MockServices {
+    fn auction_stats(&self) -> GetAuctionStatsResponse {
+        let auctions = self.counters.auctions.load(Ordering::SeqCst);
+        GetAuctionStatsResponse {
+            total_auctions: auctions,
+            total_matches: auctions,
+            total_volume: auctions * MOCK_PRICE,
+            ..Default::default()
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl AuctionService for MockServices {
+    async fn run_auction(
+        &self,
+        request: Request<RunAuctionRequest>,
+    ) -> Result<Response<RunAuctionResponse>, Status> {
+        let job_id = envelope_job_id(&request.get_ref().envelope).map_err(|e| to_status(&e))?;
+        self.counters.auctions.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(RunAuctionResponse {
+            job_id,
+            slp_id: Some(SlpId { id: MOCK_SLP_ID.to_string() }),
+            lane_id: Some(LaneId { id: 0 }),
+            price: MOCK_PRICE,
+            success: true,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_auction_stats(
+        &self,
+        _request: Request<GetAuctionStatsRequest>,
+    ) -> Result<Response<GetAuctionStatsResponse>, Status> {
+        Ok(Response::new(self.auction_stats()))
+    }
+
+    type StreamAuctionStatsStream = tokio_stream::Once<Result<GetAuctionStatsResponse, Status>>;
+
+    /// Send a single snapshot of the current stats
+    async fn stream_auction_stats(
+        &self,
+        _request: Request<StreamStatsRequest>,
+    ) -> Result<Response<Self::StreamAuctionStatsStream>, Status> {
+        Ok(Response::new(tokio_stream::once(Ok(self.auction_stats()))))
+    }
+
+    async fn get_auction_history(
+        &self,
+        _request: Request<GetAuctionHistoryRequest>,
+    ) -> Result<Response<GetAuctionHistoryResponse>, Status> {
+        Ok(Response::new(GetAuctionHistoryResponse::default()))
+    }
+
+    async fn release_capacity(
+        &self,
+        _request: Request<ReleaseCapacityRequest>,
+    ) -> Result<Response<ReleaseCapacityResponse>, Status> {
+        self.counters.released.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(ReleaseCapacityResponse {
+            success: true,
+            error: String::new(),
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl ExecutionService for MockServices {
+    async fn execute_job(
+        &self,
+        request: Request<ExecuteJobRequest>,
+    ) -> Result<Response<ExecuteJobResponse>, Status> {
+        let job_id = envelope_job_id(&request.get_ref().envelope).map_err(|e| to_status(&e))?;
+        self.counters.executed.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(ExecuteJobResponse {
+            job_id,
+            status: ExecutionStatus::Completed as i32,
+            success: true,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_runtime_stats(
+        &self,
+        _request: Request<GetRuntimeStatsRequest>,
+    ) -> Result<Response<GetRuntimeStatsResponse>, Status> {
+        let executed = self.counters.executed.load(Ordering::SeqCst);
+        Ok(Response::new(GetRuntimeStatsResponse {
+            total_executed: executed,
+            total_completed: executed,
+            ..Default::default()
+        }))
+    }
+
+    /// Jobs complete as soon as they arrive, so there is never one to cancel
+    async fn cancel_job(
+        &self,
+        _request: Request<CancelJobRequest>,
+    ) -> Result<Response<CancelJobResponse>, Status> {
+        Ok(Response::new(CancelJobResponse { cancelled: false }))
+    }
+}
+
+#[tonic::async_trait]
+impl HealthService for MockServices {
+    async fn check(
+        &self,
+        _request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        Ok(Response::new(HealthCheckResponse {
+            status: ServingStatus::Serving as i32,
+        }))
+    }
+}
+
+/// Mock services listening on a local port
+///
+/// Dropping the server stops accepting connections and closes the open ones,
+/// as a restarting daemon would.
+pub struct MockServer {
+    /// `http://` address every mock service is reachable at
+    pub addr: String,
+    /// Socket address the server listens on, for restarting it in place
+    pub local_addr: SocketAddr,
+    /// Services answering requests, for inspecting their counters
+    pub services: MockServices,
+    shutdown: Option<oneshot::Sender<()>>,
+    /// Set on drop to fail the connections already accepted
+    stopped: Arc<AtomicBool>,
+    _handle: JoinHandle<Result<(), tonic::transport::Error>>,
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// Accepted connection that fails its next read or write once the server is dropped
+///
+/// Shutting tonic's server down only stops accepting; connections opened
+/// earlier would otherwise keep being served.
+struct MockConnection {
+    stream: TcpStream,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MockConnection {
+    fn check_open(&self) -> io::Result<()> {
+        if self.stopped.load(Ordering::SeqCst) {
+            return Err(io::ErrorKind::ConnectionAborted.into());
+        }
+        Ok(())
+    }
+}
+
+impl Connected for MockConnection {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        self.stream.connect_info()
+    }
+}
+
+impl AsyncRead for MockConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.check_open()?;
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for MockConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.check_open()?;
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+/// Serve fresh mock services on an ephemeral port of 127.0.0.1
+pub async fn spawn_mock_server() -> std::io::Result<MockServer> {
+    spawn_mock_server_on(SocketAddr::from(([127, 0, 0, 1], 0))).await
+}
+
+/// Serve fresh mock services on `local_addr`
+pub async fn spawn_mock_server_on(local_addr: SocketAddr) -> std::io::Result<MockServer> {
+    let listener = TcpListener::bind(local_addr).await?;
+    let local_addr = listener.local_addr()?;
+    let services = MockServices::new();
+    let (shutdown, shutdown_requested) = oneshot::channel::<()>();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let connection_stopped = stopped.clone();
+    let incoming = TcpListenerStream::new(listener).map(move |accepted| {
+        accepted.map(|stream| MockConnection {
+            stream,
+            stopped: connection_stopped.clone(),
+        })
+    });
+
+    let handle = tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(RouterServiceServer::new(services.clone()))
+            .add_service(AuctionServiceServer::new(services.clone()))
+            .add_service(ExecutionServiceServer::new(services.clone()))
+            .add_service(HealthServiceServer::new(services.clone()))
+            .serve_with_incoming_shutdown(incoming, async {
+                let _ = shutdown_requested.await;
+            }),
+    );
+
+    Ok(MockServer {
+        addr: format!("http://{}", local_addr),
+        local_addr,
+        services,
+        shutdown: Some(shutdown),
+        stopped,
+        _handle: handle,
+    })
+}
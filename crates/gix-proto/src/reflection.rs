@@ -0,0 +1,67 @@
+//! gRPC server reflection for the `gix.v1` services
+//!
+//! Lets tools such as `grpcurl` list the services a daemon serves and
+//! describe their messages without a copy of `gix.proto`.
+
+use tonic_reflection::pb::server_reflection_server::{ServerReflection, ServerReflectionServer};
+use tonic_reflection::server::{Builder, Error};
+
+/// Encoded file descriptor set of `gix.proto`
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("gix_descriptor");
+
+/// Build a reflection service describing every `gix.v1` service
+pub fn reflection_service() -> Result<ServerReflectionServer<impl ServerReflection>, Error> {
+    Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::Endpoint;
+    use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+    use tonic_reflection::pb::server_reflection_request::MessageRequest;
+    use tonic_reflection::pb::server_reflection_response::MessageResponse;
+    use tonic_reflection::pb::ServerReflectionRequest;
+
+    #[tokio::test]
+    async fn test_reflection_lists_gix_services() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(reflection_service().unwrap())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let channel = Endpoint::from_shared(addr).unwrap().connect().await.unwrap();
+        let mut client = ServerReflectionClient::new(channel);
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let mut responses = client
+            .server_reflection_info(tokio_stream::once(request))
+            .await
+            .unwrap()
+            .into_inner();
+        let response = responses.message().await.unwrap().expect("reflection response");
+
+        let Some(MessageResponse::ListServicesResponse(list)) = response.message_response else {
+            panic!("unexpected reflection response: {:?}", response.message_response);
+        };
+        let names: Vec<&str> = list.service.iter().map(|service| service.name.as_str()).collect();
+        for expected in [
+            "gix.v1.RouterService",
+            "gix.v1.AuctionService",
+            "gix.v1.ProviderAdminService",
+            "gix.v1.ExecutionService",
+            "gix.v1.HealthService",
+        ] {
+            assert!(names.contains(&expected), "{} missing from {:?}", expected, names);
+        }
+    }
+}
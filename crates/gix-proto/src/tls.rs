@@ -0,0 +1,115 @@
+//! TLS configuration for GIX gRPC servers and clients
+//!
+//! Loads PEM files into tonic TLS configs. Callers that are not given any TLS
+//! files keep using plaintext channels.
+
+use std::path::Path;
+use thiserror::Error;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity, ServerTlsConfig};
+
+/// Errors while loading TLS material or connecting
+#[derive(Error, Debug)]
+pub enum TlsError {
+    #[error("Failed to read TLS file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid endpoint: {0}")]
+    InvalidEndpoint(String),
+    #[error("Transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+}
+
+/// Build a server TLS config from PEM certificate chain and private key files
+pub fn server_tls_config(cert_path: &Path, key_path: &Path) -> Result<ServerTlsConfig, TlsError> {
+    let cert = std::fs::read(cert_path)?;
+    let key = std::fs::read(key_path)?;
+    Ok(ServerTlsConfig::new().identity(Identity::from_pem(cert, key)))
+}
+
+/// Build a client TLS config that trusts the PEM CA certificate file
+pub fn client_tls_config(ca_path: &Path) -> Result<ClientTlsConfig, TlsError> {
+    let ca = std::fs::read(ca_path)?;
+    Ok(ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca)))
+}
+
+/// Connect a channel to `endpoint`, verifying the server against `tls_ca` if given
+pub async fn connect_channel(endpoint: &str, tls_ca: Option<&Path>) -> Result<Channel, TlsError> {
+    let mut endpoint = Channel::from_shared(endpoint.to_string())
+        .map_err(|e| TlsError::InvalidEndpoint(e.to_string()))?;
+
+    if let Some(ca_path) = tls_ca {
+        endpoint = endpoint.tls_config(client_tls_config(ca_path)?)?;
+    }
+
+    Ok(endpoint.connect().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::{HealthCheckRequest, HealthCheckResponse, ServingStatus};
+    use crate::{HealthService, HealthServiceClient, HealthServiceServer};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::{Request, Response, Status};
+
+    struct AlwaysServing;
+
+    #[tonic::async_trait]
+    impl HealthService for AlwaysServing {
+        async fn check(
+            &self,
+            _request: Request<HealthCheckRequest>,
+        ) -> Result<Response<HealthCheckResponse>, Status> {
+            Ok(Response::new(HealthCheckResponse {
+                status: ServingStatus::Serving as i32,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tls_server_accepts_client_with_matching_ca() {
+        let dir = std::env::temp_dir().join(format!("gix_proto_tls_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // A self-signed certificate is its own CA
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_path = dir.join("server.pem");
+        let key_path = dir.join("server.key");
+        std::fs::write(&cert_path, cert.serialize_pem().unwrap()).unwrap();
+        std::fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .tls_config(server_tls_config(&cert_path, &key_path).unwrap())
+                .unwrap()
+                .add_service(HealthServiceServer::new(AlwaysServing))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let endpoint = format!("https://localhost:{}", port);
+        let channel = connect_channel(&endpoint, Some(&cert_path)).await.unwrap();
+        let response = HealthServiceClient::new(channel)
+            .check(Request::new(HealthCheckRequest { service: String::new() }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.status, ServingStatus::Serving as i32);
+
+        // A plaintext client cannot talk to the TLS server
+        let plaintext = format!("http://localhost:{}", port);
+        let result = match connect_channel(&plaintext, None).await {
+            Ok(channel) => HealthServiceClient::new(channel)
+                .check(Request::new(HealthCheckRequest { service: String::new() }))
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        assert!(result.is_err());
+
+        server.abort();
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,64 @@
+//! Correlation ids carried across GIX services
+//!
+//! A client attaches one trace id to every request it makes for a job, in the
+//! `x-gix-trace-id` metadata entry. Servers record it on their request spans so
+//! a job's path through AJR, GCAM and GSEE can be followed in the logs.
+
+use tonic::metadata::errors::InvalidMetadataValue;
+use tonic::metadata::MetadataValue;
+use tonic::Request;
+
+/// Metadata key holding the trace id
+pub const TRACE_ID_METADATA: &str = "x-gix-trace-id";
+
+/// Generate a fresh random trace id (32 hex characters)
+pub fn new_trace_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// Attach `trace_id` to an outgoing request
+pub fn set_trace_id<T>(request: &mut Request<T>, trace_id: &str) -> Result<(), InvalidMetadataValue> {
+    let value = MetadataValue::try_from(trace_id)?;
+    request.metadata_mut().insert(TRACE_ID_METADATA, value);
+    Ok(())
+}
+
+/// Read the trace id from an incoming request, if the client sent one
+pub fn trace_id<T>(request: &Request<T>) -> Option<String> {
+    request
+        .metadata()
+        .get(TRACE_ID_METADATA)?
+        .to_str()
+        .ok()
+        .map(str::to_string)
+}
+
+/// Read the request's trace id, generating one if the client sent none
+pub fn trace_id_or_new<T>(request: &Request<T>) -> String {
+    trace_id(request).unwrap_or_else(new_trace_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_id_round_trip() {
+        let id = new_trace_id();
+        assert_eq!(id.len(), 32);
+
+        let mut request = Request::new(());
+        assert_eq!(trace_id(&request), None);
+
+        set_trace_id(&mut request, &id).unwrap();
+        assert_eq!(trace_id(&request), Some(id.clone()));
+        assert_eq!(trace_id_or_new(&request), id);
+    }
+
+    #[test]
+    fn test_invalid_trace_id_rejected() {
+        let mut request = Request::new(());
+        assert!(set_trace_id(&mut request, "bad\nid").is_err());
+        assert_eq!(trace_id(&request), None);
+    }
+}
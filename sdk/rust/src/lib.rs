@@ -1,26 +1,103 @@
 //! GIX Rust SDK
 //!
 //! Thin wrapper library for Rust clients to interact with GIX services.
+//!
+//! Tenants that need reproducible job ids derive them with
+//! [`JobId::derive`] from their tenant name and a per-job nonce.
 
 pub use gix_common::{GixError, JobId, LaneId};
 pub use gix_crypto;
-pub use gix_gxf::{GxfEnvelope, GxfMetadata};
+pub use gix_gxf::{GxfEnvelope, GxfJob, GxfMetadata};
+
+use gix_proto::v1::RunAuctionRequest;
+use gix_proto::AuctionServiceClient;
+use std::path::PathBuf;
+use tonic::transport::Channel;
+
+/// Default GCAM node endpoint
+const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:50052";
+
+/// Client configuration
+#[derive(Debug, Clone)]
+pub struct GixClientConfig {
+    /// GCAM node endpoint (use `https://` with `tls_ca`)
+    pub endpoint: String,
+    /// PEM CA certificate used to verify the node; plaintext when `None`
+    pub tls_ca: Option<PathBuf>,
+}
+
+impl Default for GixClientConfig {
+    fn default() -> Self {
+        GixClientConfig {
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            tls_ca: None,
+        }
+    }
+}
 
 /// Client for interacting with GIX services
 pub struct GixClient {
-    // TODO: Add client configuration
+    /// Client configuration
+    config: GixClientConfig,
 }
 
 impl GixClient {
     /// Create a new GIX client
     pub fn new() -> Self {
-        GixClient {}
+        Self::with_config(GixClientConfig::default())
+    }
+
+    /// Create a new GIX client with the given configuration
+    pub fn with_config(config: GixClientConfig) -> Self {
+        GixClient { config }
+    }
+
+    /// Get the client configuration
+    pub fn config(&self) -> &GixClientConfig {
+        &self.config
+    }
+
+    /// Open a gRPC channel to the configured endpoint
+    pub async fn connect(&self) -> Result<Channel, GixError> {
+        gix_proto::tls::connect_channel(&self.config.endpoint, self.config.tls_ca.as_deref())
+            .await
+            .map_err(|e| GixError::Protocol(format!("Failed to connect to {}: {}", self.config.endpoint, e)))
     }
 
     /// Submit a job to the GIX network
-    pub async fn submit_job(&self, _envelope: GxfEnvelope) -> Result<JobId, GixError> {
-        // TODO: Implement job submission
-        Err(GixError::InternalError("Not yet implemented".to_string()))
+    ///
+    /// Auctions the envelope on the configured node and returns its job id
+    /// once a provider has been matched.
+    pub async fn submit_job(&self, envelope: GxfEnvelope) -> Result<JobId, GixError> {
+        let job = envelope
+            .deserialize_job()
+            .map_err(|e| GixError::Protocol(format!("Invalid envelope: {}", e)))?;
+        let envelope_bytes = envelope
+            .to_json()
+            .map_err(|e| GixError::Protocol(format!("Failed to serialize envelope: {}", e)))?;
+
+        let mut client = AuctionServiceClient::new(self.connect().await?);
+        let response = client
+            .run_auction(RunAuctionRequest {
+                job: Vec::new(),
+                priority: envelope.meta.priority as u32,
+                source_slp: envelope.meta.source_slp.clone().unwrap_or_default(),
+                envelope: envelope_bytes,
+            })
+            .await
+            .map_err(|status| GixError::Protocol(format!("Auction failed: {}", status.message())))?
+            .into_inner();
+
+        if !response.success {
+            return Err(GixError::NoMatch(response.error));
+        }
+        Ok(job.job_id)
+    }
+}
+
+impl Default for GixClient {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -32,6 +109,37 @@ mod tests {
     fn test_client_creation() {
         let _client = GixClient::new();
     }
+
+    #[test]
+    fn test_default_config_is_plaintext() {
+        let client = GixClient::new();
+        assert_eq!(client.config().endpoint, DEFAULT_ENDPOINT);
+        assert!(client.config().tls_ca.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_against_mock_auction() {
+        let server = gix_proto::mock::spawn_mock_server().await.unwrap();
+        let client = GixClient::with_config(GixClientConfig {
+            endpoint: server.addr.clone(),
+            tls_ca: None,
+        });
+
+        let job = gix_gxf::GxfJob::new(JobId([5; 16]), gix_gxf::PrecisionLevel::BF16, 1024);
+        let envelope = GxfEnvelope::from_job(job, 128).unwrap();
+
+        assert_eq!(client.submit_job(envelope).await.unwrap(), JobId([5; 16]));
+        assert_eq!(server.services.counters().auctions.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connect_reports_missing_ca() {
+        let client = GixClient::with_config(GixClientConfig {
+            endpoint: "https://localhost:1".to_string(),
+            tls_ca: Some(PathBuf::from("/nonexistent/ca.pem")),
+        });
+        assert!(matches!(client.connect().await, Err(GixError::Protocol(_))));
+    }
 }
 
 
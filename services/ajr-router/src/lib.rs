@@ -2,23 +2,90 @@
 //!
 //! Provides router state and envelope processing functionality.
 
-use anyhow::Result;
-use gix_common::{GixError, LaneId};
-use gix_gxf::{GxfEnvelope, GxfJob};
-use metrics::{counter, gauge, increment_counter};
+use anyhow::{Context, Result};
+use gix_common::{GixError, LaneId, SlpId};
+use gix_gxf::{GxfEnvelope, GxfJob, GxfMetadata, JobPriority};
+use metrics::{gauge, increment_counter};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// Job parameter naming a session whose jobs should share a lane
+pub const SESSION_ID_PARAM: &str = "session_id";
+
+/// How long a session keeps its lane after its last routed job
+pub const DEFAULT_AFFINITY_TTL: Duration = Duration::from_secs(300);
+
+/// Most sessions whose lane assignment is remembered at once
+pub const DEFAULT_MAX_AFFINITIES: usize = 10_000;
+
+/// Lane selection strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LaneSelectionStrategy {
+    /// High priority jobs prefer Flash, others prefer Deep; fall back when full
+    #[default]
+    PriorityThreshold,
+    /// Cycle through lanes with spare capacity
+    RoundRobin,
+    /// Pick the lane with the lowest active_jobs / capacity ratio
+    LeastLoaded,
+}
+
+/// Limits applied to envelope priorities before lane selection
+///
+/// The default allows every priority and changes nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriorityPolicy {
+    /// Highest priority accepted; higher values are clamped down to it
+    pub max_allowed: u8,
+    /// Priority given to every envelope regardless of what it carries
+    pub override_priority: Option<u8>,
+}
+
+impl Default for PriorityPolicy {
+    fn default() -> Self {
+        PriorityPolicy {
+            max_allowed: u8::MAX,
+            override_priority: None,
+        }
+    }
+}
+
+impl PriorityPolicy {
+    /// Priority an envelope submitted with `priority` is routed with
+    pub fn apply(&self, priority: u8) -> u8 {
+        self.override_priority.unwrap_or(priority).min(self.max_allowed)
+    }
+}
 
 /// AJR Router state
 #[derive(Clone)]
 pub struct RouterState {
     /// Active routing lanes
     lanes: Vec<LaneInfo>,
+    /// Lane selection strategy
+    strategy: LaneSelectionStrategy,
+    /// Next lane index to try for round-robin selection
+    round_robin_cursor: Arc<RwLock<usize>>,
     /// Statistics: jobs routed per lane
     stats: Arc<RwLock<HashMap<LaneId, u64>>>,
     /// Total jobs routed
     total_routed: Arc<RwLock<u64>>,
+    /// Grace window for envelope expiry, in seconds
+    clock_skew_secs: u64,
+    /// Source SLPs accepted under strict validation (None keeps validation lenient)
+    known_slps: Option<Vec<SlpId>>,
+    /// Lane last assigned to each session, with when the session last used it (on the tokio clock)
+    affinity: Arc<RwLock<HashMap<String, (LaneId, Instant)>>>,
+    /// How long an affinity entry stays valid after its last use
+    affinity_ttl: Duration,
+    /// Most affinity entries kept before the oldest is evicted
+    max_affinities: usize,
+    /// Limits on the priorities envelopes are routed with
+    priority_policy: PriorityPolicy,
 }
 
 /// Lane information
@@ -40,11 +107,18 @@ struct LaneInfo {
 pub struct RouterStats {
     pub total_routed: u64,
     pub lane_stats: HashMap<LaneId, u64>,
+    /// Current (active jobs, capacity) per lane
+    pub lane_utilization: HashMap<LaneId, (u32, u32)>,
 }
 
 impl RouterState {
     /// Create a new router state with default lanes
     pub fn new() -> Self {
+        Self::with_strategy(LaneSelectionStrategy::default())
+    }
+
+    /// Create a new router state with default lanes and the given selection strategy
+    pub fn with_strategy(strategy: LaneSelectionStrategy) -> Self {
         let lanes = vec![
             LaneInfo {
                 id: LaneId(0),
@@ -62,14 +136,177 @@ impl RouterState {
 
         RouterState {
             lanes,
+            strategy,
+            round_robin_cursor: Arc::new(RwLock::new(0)),
             stats: Arc::new(RwLock::new(HashMap::new())),
             total_routed: Arc::new(RwLock::new(0)),
+            clock_skew_secs: 0,
+            known_slps: None,
+            affinity: Arc::new(RwLock::new(HashMap::new())),
+            affinity_ttl: DEFAULT_AFFINITY_TTL,
+            max_affinities: DEFAULT_MAX_AFFINITIES,
+            priority_policy: PriorityPolicy::default(),
+        }
+    }
+
+    /// Accept envelopes that expired up to `secs` seconds ago
+    pub fn with_clock_skew(mut self, secs: u64) -> Self {
+        self.clock_skew_secs = secs;
+        self
+    }
+
+    /// Require envelope routing hints to name one of the router's lanes and one of `slps`
+    pub fn with_known_slps(mut self, slps: Vec<SlpId>) -> Self {
+        self.known_slps = Some(slps);
+        self
+    }
+
+    /// Remember session lanes for `ttl` after their last use, keeping at most `max_entries` sessions
+    pub fn with_affinity_limits(mut self, ttl: Duration, max_entries: usize) -> Self {
+        self.affinity_ttl = ttl;
+        self.max_affinities = max_entries;
+        self
+    }
+
+    /// Clamp or override envelope priorities before lanes are selected
+    pub fn with_priority_policy(mut self, policy: PriorityPolicy) -> Self {
+        self.priority_policy = policy;
+        self
+    }
+
+    /// Get the priority policy
+    pub fn priority_policy(&self) -> PriorityPolicy {
+        self.priority_policy
+    }
+
+    /// Identifiers of the router's lanes
+    pub fn lane_ids(&self) -> Vec<LaneId> {
+        self.lanes.iter().map(|lane| lane.id.clone()).collect()
+    }
+
+    /// Get the lane selection strategy
+    pub fn strategy(&self) -> LaneSelectionStrategy {
+        self.strategy
+    }
+
+    /// Select a lane for routing according to the configured strategy
+    ///
+    /// An envelope's `target_lane` wins while that lane has capacity. Jobs
+    /// carrying a `session_id` parameter stay on their session's lane while
+    /// it has capacity, regardless of strategy. Either pin is ignored when the
+    /// priority policy keeps the envelope out of the lane (see `pin_allowed`).
+    async fn select_lane(&self, job: &GxfJob, meta: &GxfMetadata) -> Result<LaneId, GixError> {
+        if let Some(lane_id) = self.pinned_lane(meta).await {
+            return Ok(lane_id);
+        }
+
+        let priority = meta.priority_class();
+        let session_id = job.parameters.get(SESSION_ID_PARAM);
+        if let Some(session_id) = session_id {
+            let affine = self.affine_lane(session_id).await;
+            if let Some(lane_id) = affine.filter(|lane_id| self.pin_allowed(lane_id, meta.priority)) {
+                increment_counter!("gix_router_affinity_hits_total");
+                // An active session keeps its lane for another TTL
+                self.record_affinity(session_id, lane_id.clone()).await;
+                return Ok(lane_id);
+            }
+        }
+
+        let lane_id = match self.strategy {
+            LaneSelectionStrategy::PriorityThreshold => self.select_lane_by_priority(priority).await,
+            LaneSelectionStrategy::RoundRobin => self.select_lane_round_robin().await,
+            LaneSelectionStrategy::LeastLoaded => self.select_lane_least_loaded().await,
+        }?;
+
+        if let Some(session_id) = session_id {
+            self.record_affinity(session_id, lane_id.clone()).await;
+        }
+        Ok(lane_id)
+    }
+
+    /// Lane named by the envelope's `target_lane`, if it exists and has room
+    ///
+    /// Unknown lanes are ignored with a warning; strict routers reject them
+    /// earlier, in routing hint validation.
+    async fn pinned_lane(&self, meta: &GxfMetadata) -> Option<LaneId> {
+        let target = meta.target_lane.as_deref()?;
+        let lane = target
+            .parse::<LaneId>()
+            .ok()
+            .and_then(|lane_id| self.lanes.iter().find(|l| l.id == lane_id));
+        let Some(lane) = lane else {
+            warn!(target_lane = target, "Ignoring unknown target lane");
+            return None;
+        };
+        if !self.pin_allowed(&lane.id, meta.priority) {
+            warn!(target_lane = target, priority = meta.priority, "Target lane not allowed by priority policy");
+            increment_counter!("gix_router_pins_refused_total");
+            return None;
+        }
+
+        if *lane.active_jobs.read().await >= lane.capacity {
+            return None;
+        }
+        Some(lane.id.clone())
+    }
+
+    /// Lane a session is pinned to, if the entry is live and the lane has room
+    async fn affine_lane(&self, session_id: &str) -> Option<LaneId> {
+        let lane_id = {
+            let affinity = self.affinity.read().await;
+            let (lane_id, last_used) = affinity.get(session_id)?;
+            if last_used.elapsed() >= self.affinity_ttl {
+                return None;
+            }
+            lane_id.clone()
+        };
+
+        let lane = self.lanes.iter().find(|l| l.id == lane_id)?;
+        if *lane.active_jobs.read().await >= lane.capacity {
+            return None;
+        }
+        Some(lane_id)
+    }
+
+    /// Whether an envelope routed with `priority` may be pinned to `lane_id`
+    ///
+    /// With a priority policy configured under `PriorityThreshold`, the Flash
+    /// lane only takes urgent priorities, pinned or not; otherwise a clamped
+    /// envelope could name the lane its priority was clamped out of.
+    fn pin_allowed(&self, lane_id: &LaneId, priority: u8) -> bool {
+        self.strategy != LaneSelectionStrategy::PriorityThreshold
+            || self.priority_policy == PriorityPolicy::default()
+            || self.lanes.first().is_none_or(|flash| &flash.id != lane_id)
+            || JobPriority::from_u8(priority).is_urgent()
+    }
+
+    /// Pin a session to a lane as of now, evicting expired entries and then the
+    /// least recently used when full
+    async fn record_affinity(&self, session_id: &str, lane_id: LaneId) {
+        if self.max_affinities == 0 {
+            return;
+        }
+
+        let mut affinity = self.affinity.write().await;
+        if !affinity.contains_key(session_id) && affinity.len() >= self.max_affinities {
+            let ttl = self.affinity_ttl;
+            affinity.retain(|_, (_, last_used)| last_used.elapsed() < ttl);
+            if affinity.len() >= self.max_affinities {
+                let oldest = affinity
+                    .iter()
+                    .min_by_key(|(_, (_, last_used))| *last_used)
+                    .map(|(session, _)| session.clone());
+                if let Some(oldest) = oldest {
+                    affinity.remove(&oldest);
+                }
+            }
         }
+        affinity.insert(session_id.to_string(), (lane_id, Instant::now()));
     }
 
-    /// Select a lane for routing based on job priority and lane capacity
-    async fn select_lane(&self, _job: &GxfJob, _priority: u8) -> Result<LaneId, GixError> {
-        let lane_index = if _priority >= 128 {
+    /// Select a lane based on job priority, falling back to the other lane when full
+    async fn select_lane_by_priority(&self, priority: JobPriority) -> Result<LaneId, GixError> {
+        let lane_index = if priority.is_urgent() {
             0 // Flash lane for high priority
         } else {
             1 // Deep lane for normal/low priority
@@ -92,12 +329,47 @@ impl RouterState {
                     return Ok(fallback_lane.id.clone());
                 }
             }
-            return Err(GixError::InternalError("All lanes at capacity".to_string()));
+            return Err(GixError::Overloaded("All lanes at capacity".to_string()));
         }
 
         Ok(lane.id.clone())
     }
 
+    /// Cycle through lanes, skipping those at capacity
+    async fn select_lane_round_robin(&self) -> Result<LaneId, GixError> {
+        let mut cursor = self.round_robin_cursor.write().await;
+
+        for offset in 0..self.lanes.len() {
+            let index = (*cursor + offset) % self.lanes.len();
+            let lane = &self.lanes[index];
+            if *lane.active_jobs.read().await < lane.capacity {
+                *cursor = (index + 1) % self.lanes.len();
+                return Ok(lane.id.clone());
+            }
+        }
+
+        Err(GixError::Overloaded("All lanes at capacity".to_string()))
+    }
+
+    /// Select the lane with the lowest active_jobs / capacity ratio
+    async fn select_lane_least_loaded(&self) -> Result<LaneId, GixError> {
+        let mut best: Option<(&LaneInfo, f64)> = None;
+
+        for lane in &self.lanes {
+            let active = *lane.active_jobs.read().await;
+            if active >= lane.capacity {
+                continue;
+            }
+            let load = active as f64 / lane.capacity as f64;
+            if best.is_none_or(|(_, best_load)| load < best_load) {
+                best = Some((lane, load));
+            }
+        }
+
+        best.map(|(lane, _)| lane.id.clone())
+            .ok_or_else(|| GixError::Overloaded("All lanes at capacity".to_string()))
+    }
+
     /// Route an envelope through the selected lane
     async fn route_envelope(
         &self,
@@ -132,6 +404,128 @@ impl RouterState {
         Ok(())
     }
 
+    /// Route a batch of envelopes all-or-nothing
+    ///
+    /// Every envelope is admitted and the batch size checked against the free
+    /// capacity of all lanes before anything is reserved. Lanes are chosen as
+    /// for single envelopes, without session affinity, while holding every
+    /// lane's lock; if any job cannot be placed, no lane is touched.
+    pub async fn route_batch(&self, envelopes: &[GxfEnvelope]) -> Result<Vec<LaneId>, GixError> {
+        for (index, envelope) in envelopes.iter().enumerate() {
+            admit_envelope(self, envelope)
+                .map_err(|e| GixError::Protocol(format!("Batch envelope {}: {:#}", index, e)))?;
+        }
+
+        // Cursor before lanes, the order round-robin selection takes them in
+        let mut cursor = self.round_robin_cursor.write().await;
+        let mut reserved = Vec::with_capacity(self.lanes.len());
+        for lane in &self.lanes {
+            reserved.push(lane.active_jobs.write().await);
+        }
+
+        let free: u64 = self
+            .lanes
+            .iter()
+            .zip(&reserved)
+            .map(|(lane, active)| lane.capacity.saturating_sub(**active) as u64)
+            .sum();
+        if envelopes.len() as u64 > free {
+            increment_counter!("gix_router_batches_rejected_total");
+            return Err(GixError::Overloaded(format!(
+                "Batch of {} jobs exceeds remaining lane capacity {}",
+                envelopes.len(),
+                free
+            )));
+        }
+
+        // Assign against a scratch copy so a failure commits nothing
+        let mut active: Vec<u32> = reserved.iter().map(|active| **active).collect();
+        let mut next_cursor = *cursor;
+        let mut assigned = Vec::with_capacity(envelopes.len());
+        for envelope in envelopes {
+            let index = self
+                .batch_lane(&envelope.meta, &active, &mut next_cursor)
+                .ok_or_else(|| GixError::Overloaded("All lanes at capacity".to_string()))?;
+            active[index] += 1;
+            assigned.push(index);
+        }
+
+        for ((lane, guard), count) in self.lanes.iter().zip(reserved.iter_mut()).zip(&active) {
+            **guard = *count;
+            gauge!("gix_router_active_jobs", *count as f64, "lane" => format!("{}", lane.id.0));
+        }
+        *cursor = next_cursor;
+        drop(reserved);
+        drop(cursor);
+
+        let lane_ids: Vec<LaneId> = assigned.into_iter().map(|index| self.lanes[index].id.clone()).collect();
+        {
+            let mut stats = self.stats.write().await;
+            for lane_id in &lane_ids {
+                increment_counter!("gix_packets_routed_total", "lane" => format!("{}", lane_id.0));
+                *stats.entry(lane_id.clone()).or_insert(0) += 1;
+            }
+        }
+        {
+            let mut total = self.total_routed.write().await;
+            *total += lane_ids.len() as u64;
+            gauge!("gix_router_total_routed", *total as f64);
+        }
+
+        debug!(jobs = lane_ids.len(), "Batch routed");
+        Ok(lane_ids)
+    }
+
+    /// Lane index for one batch entry, given the active jobs reserved so far
+    ///
+    /// Mirrors `select_lane` without session affinity.
+    fn batch_lane(&self, meta: &GxfMetadata, active: &[u32], cursor: &mut usize) -> Option<usize> {
+        let has_room = |index: usize| active[index] < self.lanes[index].capacity;
+        let priority = self.priority_policy.apply(meta.priority);
+
+        let pinned = meta
+            .target_lane
+            .as_deref()
+            .and_then(|target| target.parse::<LaneId>().ok())
+            .and_then(|lane_id| self.lanes.iter().position(|l| l.id == lane_id))
+            .filter(|&index| self.pin_allowed(&self.lanes[index].id, priority));
+        if let Some(index) = pinned.filter(|&index| has_room(index)) {
+            return Some(index);
+        }
+
+        let lanes = self.lanes.len();
+        match self.strategy {
+            LaneSelectionStrategy::PriorityThreshold => {
+                let preferred = if JobPriority::from_u8(priority).is_urgent() { 0 } else { 1 };
+                [preferred, 1 - preferred]
+                    .into_iter()
+                    .filter(|&index| index < lanes)
+                    .find(|&index| has_room(index))
+            }
+            LaneSelectionStrategy::RoundRobin => {
+                let index = (0..lanes).map(|offset| (*cursor + offset) % lanes).find(|&index| has_room(index))?;
+                *cursor = (index + 1) % lanes;
+                Some(index)
+            }
+            LaneSelectionStrategy::LeastLoaded => (0..lanes)
+                .filter(|&index| has_room(index))
+                .min_by(|&a, &b| {
+                    let load = |index: usize| active[index] as f64 / self.lanes[index].capacity as f64;
+                    load(a).total_cmp(&load(b))
+                }),
+        }
+    }
+
+    /// Read each lane's current (active jobs, capacity)
+    async fn lane_utilization(&self) -> HashMap<LaneId, (u32, u32)> {
+        let mut utilization = HashMap::new();
+        for lane in &self.lanes {
+            let active = *lane.active_jobs.read().await;
+            utilization.insert(lane.id.clone(), (active, lane.capacity));
+        }
+        utilization
+    }
+
     /// Get routing statistics
     pub async fn get_stats(&self) -> RouterStats {
         let stats = self.stats.read().await;
@@ -140,38 +534,442 @@ impl RouterState {
         RouterStats {
             total_routed: total,
             lane_stats: stats.clone(),
+            lane_utilization: self.lane_utilization().await,
         }
     }
+
+    /// Snapshot the current routing statistics
+    pub async fn snapshot_stats(&self) -> RouterStats {
+        self.get_stats().await
+    }
+
+    /// Zero the routing counters, returning their values before the reset
+    pub async fn reset_stats(&self) -> RouterStats {
+        let mut stats = self.stats.write().await;
+        let mut total = self.total_routed.write().await;
+
+        // Lane load is live state rather than a counter, so it is reported but kept
+        let snapshot = RouterStats {
+            total_routed: std::mem::take(&mut *total),
+            lane_stats: std::mem::take(&mut *stats),
+            lane_utilization: self.lane_utilization().await,
+        };
+        gauge!("gix_router_total_routed", 0.0);
+
+        snapshot
+    }
+}
+
+impl Default for RouterState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Process a GXF envelope through the router
+#[tracing::instrument(
+    skip_all,
+    fields(job_id = tracing::field::Empty, precision = tracing::field::Empty, priority = envelope.meta.priority)
+)]
 pub async fn process_envelope(
     router: &RouterState,
-    envelope: GxfEnvelope,
+    mut envelope: GxfEnvelope,
 ) -> Result<LaneId> {
-    envelope.validate().map_err(|e| anyhow::anyhow!("Envelope validation failed: {}", e))?;
+    let job = admit_envelope(router, &envelope)?;
 
-    if envelope.meta.is_expired() {
-        return Err(anyhow::anyhow!("Envelope expired"));
+    let priority = router.priority_policy.apply(envelope.meta.priority);
+    if priority != envelope.meta.priority {
+        warn!(submitted = envelope.meta.priority, priority, "Envelope priority clamped by policy");
+        increment_counter!("gix_router_priorities_clamped_total");
+        tracing::Span::current().record("priority", priority);
+        envelope.meta.priority = priority;
     }
 
-    let job = envelope
-        .deserialize_job()
-        .map_err(|e| anyhow::anyhow!("Failed to deserialize job: {}", e))?;
-
-    job.validate()
-        .map_err(|e| anyhow::anyhow!("Job validation failed: {}", e))?;
-
     let lane_id = router
-        .select_lane(&job, envelope.meta.priority)
+        .select_lane(&job, &envelope.meta)
         .await
-        .map_err(|e| anyhow::anyhow!("Lane selection failed: {}", e))?;
+        .context("Lane selection failed")?;
 
+    let age_secs = envelope.age_secs();
     router
         .route_envelope(envelope, lane_id.clone())
         .await
-        .map_err(|e| anyhow::anyhow!("Routing failed: {}", e))?;
+        .context("Routing failed")?;
 
+    debug!(lane = lane_id.0, age_secs, "Envelope routed");
     Ok(lane_id)
 }
 
+/// Validate an envelope and extract its job, recording the job on the current span
+fn admit_envelope(router: &RouterState, envelope: &GxfEnvelope) -> Result<GxfJob> {
+    envelope
+        .validate_with_skew(router.clock_skew_secs)
+        .context("Envelope validation failed")?;
+
+    if let Some(known_slps) = &router.known_slps {
+        envelope
+            .meta
+            .validate_routing_hints(&router.lane_ids(), known_slps)
+            .context("Routing hint validation failed")?;
+    }
+
+    envelope
+        .meta
+        .check_expiry_with_skew(router.clock_skew_secs)
+        .with_context(|| format!("Envelope expired ({}s old)", envelope.age_secs()))?;
+
+    let job = envelope
+        .deserialize_job()
+        .context("Failed to deserialize job")?;
+    let span = tracing::Span::current();
+    span.record("job_id", tracing::field::display(hex::encode(job.job_id.0)));
+    span.record("precision", tracing::field::debug(job.precision));
+
+    job.validate()
+        .context("Job validation failed")?;
+    Ok(job)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gix_common::JobId;
+    use gix_gxf::PrecisionLevel;
+
+    fn equal_lanes_router(strategy: LaneSelectionStrategy) -> RouterState {
+        let mut router = RouterState::with_strategy(strategy);
+        for lane in router.lanes.iter_mut() {
+            lane.capacity = 10;
+        }
+        router
+    }
+
+    fn test_envelope(priority: u8) -> GxfEnvelope {
+        let job = GxfJob::new(JobId([7u8; 16]), PrecisionLevel::BF16, 1024);
+        GxfEnvelope::from_job(job, priority).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_default_strategy_is_priority_threshold() {
+        let router = RouterState::new();
+        assert_eq!(router.strategy(), LaneSelectionStrategy::PriorityThreshold);
+
+        assert_eq!(process_envelope(&router, test_envelope(200)).await.unwrap(), LaneId(0));
+        assert_eq!(process_envelope(&router, test_envelope(50)).await.unwrap(), LaneId(1));
+    }
+
+    #[tokio::test]
+    async fn test_priority_policy_clamps_critical_envelopes() {
+        let policy = PriorityPolicy { max_allowed: 127, override_priority: None };
+        let router = RouterState::new().with_priority_policy(policy);
+
+        // Critical is clamped to Normal and misses the Flash lane
+        assert_eq!(process_envelope(&router, test_envelope(200)).await.unwrap(), LaneId(1));
+        assert_eq!(process_envelope(&router, test_envelope(50)).await.unwrap(), LaneId(1));
+
+        let policy = PriorityPolicy { max_allowed: u8::MAX, override_priority: Some(200) };
+        assert_eq!(policy.apply(10), 200);
+        assert_eq!(PriorityPolicy::default().apply(200), 200);
+    }
+
+    #[tokio::test]
+    async fn test_priority_policy_applies_to_pinned_lanes() {
+        let policy = PriorityPolicy { max_allowed: 127, override_priority: None };
+        let router = RouterState::new().with_priority_policy(policy);
+        let job = GxfJob::new(JobId([7u8; 16]), PrecisionLevel::BF16, 1024);
+
+        // A clamped envelope cannot name the Flash lane instead
+        let mut meta = gix_gxf::GxfMetadata::new(200).unwrap();
+        meta.target_lane = Some("0".to_string());
+        let pinned = GxfEnvelope::from_job_with_meta(job.clone(), meta.clone()).unwrap();
+        assert_eq!(process_envelope(&router, pinned.clone()).await.unwrap(), LaneId(1));
+        assert_eq!(router.route_batch(&[pinned]).await.unwrap(), vec![LaneId(1)]);
+
+        // Other lanes can still be pinned
+        meta.target_lane = Some("1".to_string());
+        let deep = GxfEnvelope::from_job_with_meta(job.clone(), meta).unwrap();
+        assert_eq!(process_envelope(&router, deep).await.unwrap(), LaneId(1));
+
+        // A session cannot stay on the Flash lane either
+        let router = RouterState::new();
+        process_envelope(&router, session_envelope(200, "s1")).await.unwrap();
+        let router = router.with_priority_policy(policy);
+        assert_eq!(process_envelope(&router, session_envelope(200, "s1")).await.unwrap(), LaneId(1));
+
+        // Without a policy the pin wins as before
+        let mut meta = gix_gxf::GxfMetadata::new(50).unwrap();
+        meta.target_lane = Some("0".to_string());
+        let unclamped = GxfEnvelope::from_job_with_meta(job, meta).unwrap();
+        assert_eq!(process_envelope(&RouterState::new(), unclamped).await.unwrap(), LaneId(0));
+    }
+
+    #[tokio::test]
+    async fn test_least_loaded_spreads_jobs_evenly() {
+        let router = equal_lanes_router(LaneSelectionStrategy::LeastLoaded);
+
+        for _ in 0..10 {
+            process_envelope(&router, test_envelope(200)).await.unwrap();
+        }
+
+        let stats = router.get_stats().await;
+        assert_eq!(stats.total_routed, 10);
+        assert_eq!(stats.lane_stats.get(&LaneId(0)), Some(&5));
+        assert_eq!(stats.lane_stats.get(&LaneId(1)), Some(&5));
+    }
+
+    #[tokio::test]
+    async fn test_full_lanes_report_overload() {
+        for strategy in [LaneSelectionStrategy::LeastLoaded, LaneSelectionStrategy::RoundRobin] {
+            let router = equal_lanes_router(strategy);
+            for _ in 0..20 {
+                process_envelope(&router, test_envelope(200)).await.unwrap();
+            }
+
+            let err = process_envelope(&router, test_envelope(200)).await.unwrap_err();
+            assert!(matches!(err.downcast_ref::<GixError>(), Some(GixError::Overloaded(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reset_stats_returns_snapshot() {
+        let router = equal_lanes_router(LaneSelectionStrategy::RoundRobin);
+        for _ in 0..4 {
+            process_envelope(&router, test_envelope(200)).await.unwrap();
+        }
+
+        let snapshot = router.reset_stats().await;
+        assert_eq!(snapshot.total_routed, 4);
+        assert_eq!(snapshot.lane_stats.values().sum::<u64>(), 4);
+
+        let stats = router.snapshot_stats().await;
+        assert_eq!(stats.total_routed, 0);
+        assert!(stats.lane_stats.is_empty());
+
+        process_envelope(&router, test_envelope(200)).await.unwrap();
+        assert_eq!(router.snapshot_stats().await.total_routed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stats_report_lane_utilization() {
+        let router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold);
+
+        let stats = router.get_stats().await;
+        assert_eq!(stats.lane_utilization.get(&LaneId(0)), Some(&(0, 10)));
+        assert_eq!(stats.lane_utilization.get(&LaneId(1)), Some(&(0, 10)));
+
+        for _ in 0..3 {
+            process_envelope(&router, test_envelope(200)).await.unwrap();
+        }
+
+        let stats = router.get_stats().await;
+        assert_eq!(stats.lane_utilization.get(&LaneId(0)), Some(&(3, 10)));
+        assert_eq!(stats.lane_utilization.get(&LaneId(1)), Some(&(0, 10)));
+    }
+
+    #[tokio::test]
+    async fn test_clock_skew_tolerates_recent_expiry() {
+        let mut envelope = test_envelope(200);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        envelope.meta.created_at = now - 60;
+        envelope.meta.expires_at = Some(now - 2);
+
+        let strict = RouterState::new();
+        assert!(process_envelope(&strict, envelope.clone()).await.is_err());
+
+        let tolerant = RouterState::new().with_clock_skew(30);
+        assert_eq!(process_envelope(&tolerant, envelope).await.unwrap(), LaneId(0));
+    }
+
+    #[tokio::test]
+    async fn test_strict_router_rejects_unknown_lane() {
+        let router = RouterState::new().with_known_slps(vec![SlpId("slp-1".to_string())]);
+        let job = GxfJob::new(JobId([7u8; 16]), PrecisionLevel::BF16, 1024);
+
+        let mut meta = gix_gxf::GxfMetadata::new(200).unwrap();
+        meta.target_lane = Some("0".to_string());
+        meta.source_slp = Some("slp-1".to_string());
+        let envelope = GxfEnvelope::from_job_with_meta(job.clone(), meta.clone()).unwrap();
+        assert_eq!(process_envelope(&router, envelope.clone()).await.unwrap(), LaneId(0));
+
+        meta.target_lane = Some("9".to_string());
+        let unknown = GxfEnvelope::from_job_with_meta(job, meta).unwrap();
+        assert!(process_envelope(&router, unknown.clone()).await.is_err());
+
+        // Lenient routers ignore the hints
+        assert!(process_envelope(&RouterState::new(), unknown).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_route_batch_is_all_or_nothing() {
+        let router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold);
+        for _ in 0..17 {
+            process_envelope(&router, test_envelope(200)).await.unwrap();
+        }
+        let before = router.get_stats().await;
+        assert_eq!(before.lane_utilization[&LaneId(0)], (10, 10));
+        assert_eq!(before.lane_utilization[&LaneId(1)], (7, 10));
+
+        // Four jobs do not fit in the three free slots
+        let batch: Vec<GxfEnvelope> = (0..4).map(|_| test_envelope(50)).collect();
+        let err = router.route_batch(&batch).await.unwrap_err();
+        assert!(matches!(err, GixError::Overloaded(_)));
+
+        // Nor does a batch holding an invalid envelope, even though it would fit
+        let mut invalid = test_envelope(50);
+        invalid.payload = b"not a job".to_vec();
+        assert!(router.route_batch(&[test_envelope(50), invalid]).await.is_err());
+
+        let after = router.get_stats().await;
+        assert_eq!(after.lane_utilization, before.lane_utilization);
+        assert_eq!(after.total_routed, 17);
+
+        // A batch that fits spills over from the full Flash lane
+        let lanes = router.route_batch(&batch[..3]).await.unwrap();
+        assert_eq!(lanes, vec![LaneId(1); 3]);
+        let urgent = router.route_batch(&[test_envelope(200)]).await;
+        assert!(matches!(urgent, Err(GixError::Overloaded(_))));
+
+        let stats = router.get_stats().await;
+        assert_eq!(stats.lane_utilization[&LaneId(1)], (10, 10));
+        assert_eq!(stats.total_routed, 20);
+        assert_eq!(stats.lane_stats[&LaneId(1)], 10);
+    }
+
+    #[tokio::test]
+    async fn test_target_lane_overrides_priority() {
+        let mut router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold);
+        let job = GxfJob::new(JobId([7u8; 16]), PrecisionLevel::BF16, 1024);
+
+        let mut meta = gix_gxf::GxfMetadata::new(200).unwrap();
+        meta.target_lane = Some("1".to_string());
+        let pinned = GxfEnvelope::from_job_with_meta(job.clone(), meta.clone()).unwrap();
+        assert_eq!(process_envelope(&router, pinned.clone()).await.unwrap(), LaneId(1));
+
+        // An unknown lane is ignored and the priority default applies
+        meta.target_lane = Some("9".to_string());
+        let unknown = GxfEnvelope::from_job_with_meta(job, meta).unwrap();
+        assert_eq!(process_envelope(&router, unknown).await.unwrap(), LaneId(0));
+
+        // A full target lane falls back to normal selection
+        router.lanes[1].capacity = 1;
+        assert_eq!(process_envelope(&router, pinned).await.unwrap(), LaneId(0));
+    }
+
+    fn session_envelope(priority: u8, session_id: &str) -> GxfEnvelope {
+        let mut job = GxfJob::new(JobId([7u8; 16]), PrecisionLevel::BF16, 1024);
+        job.parameters.insert(SESSION_ID_PARAM.to_string(), session_id.to_string());
+        GxfEnvelope::from_job(job, priority).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_session_affinity_overrides_priority() {
+        let router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold);
+
+        assert_eq!(process_envelope(&router, session_envelope(200, "chat-1")).await.unwrap(), LaneId(0));
+        // Low priority would normally go to Deep, but the session is pinned to Flash
+        assert_eq!(process_envelope(&router, session_envelope(50, "chat-1")).await.unwrap(), LaneId(0));
+
+        // Jobs without a session id are routed as usual
+        assert_eq!(process_envelope(&router, test_envelope(50)).await.unwrap(), LaneId(1));
+        assert_eq!(process_envelope(&router, session_envelope(50, "chat-2")).await.unwrap(), LaneId(1));
+    }
+
+    #[tokio::test]
+    async fn test_session_affinity_falls_back_when_lane_full() {
+        let mut router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold);
+        router.lanes[0].capacity = 1;
+
+        assert_eq!(process_envelope(&router, session_envelope(200, "chat-1")).await.unwrap(), LaneId(0));
+        // Flash is full, so the session moves to Deep and stays there
+        assert_eq!(process_envelope(&router, session_envelope(200, "chat-1")).await.unwrap(), LaneId(1));
+        assert_eq!(router.affine_lane("chat-1").await, Some(LaneId(1)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_session_affinity_is_bounded_and_expires() {
+        let router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold)
+            .with_affinity_limits(Duration::from_secs(60), 2);
+
+        router.record_affinity("a", LaneId(0)).await;
+        tokio::time::advance(Duration::from_millis(5)).await;
+        router.record_affinity("b", LaneId(0)).await;
+        router.record_affinity("c", LaneId(1)).await;
+        assert_eq!(router.affinity.read().await.len(), 2);
+        assert_eq!(router.affine_lane("a").await, None);
+        assert_eq!(router.affine_lane("c").await, Some(LaneId(1)));
+
+        let expiring = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold)
+            .with_affinity_limits(Duration::ZERO, 2);
+        expiring.record_affinity("a", LaneId(0)).await;
+        assert_eq!(expiring.affine_lane("a").await, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_session_affinity_ttl_counts_from_last_use() {
+        let router = equal_lanes_router(LaneSelectionStrategy::PriorityThreshold)
+            .with_affinity_limits(Duration::from_millis(200), 16);
+
+        assert_eq!(process_envelope(&router, session_envelope(200, "chat-1")).await.unwrap(), LaneId(0));
+        // Each hit renews the entry, so an active session outlives the TTL
+        for _ in 0..2 {
+            tokio::time::advance(Duration::from_millis(120)).await;
+            assert_eq!(process_envelope(&router, session_envelope(50, "chat-1")).await.unwrap(), LaneId(0));
+        }
+
+        // An idle session expires and is routed as usual
+        tokio::time::advance(Duration::from_millis(250)).await;
+        assert_eq!(process_envelope(&router, session_envelope(50, "chat-1")).await.unwrap(), LaneId(1));
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_cycles_lanes() {
+        let router = equal_lanes_router(LaneSelectionStrategy::RoundRobin);
+
+        let mut lanes = Vec::new();
+        for _ in 0..4 {
+            lanes.push(process_envelope(&router, test_envelope(50)).await.unwrap());
+        }
+
+        assert_eq!(lanes, vec![LaneId(0), LaneId(1), LaneId(0), LaneId(1)]);
+    }
+
+    /// Writer that collects formatted log output in memory
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_envelope_span_records_job_id() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let router = RouterState::new();
+        process_envelope(&router, test_envelope(200)).await.unwrap();
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("process_envelope{"), "{}", output);
+        assert!(output.contains(&format!("job_id={}", hex::encode([7u8; 16]))), "{}", output);
+        assert!(output.contains("precision=BF16"), "{}", output);
+        assert!(output.contains("priority=200"), "{}", output);
+    }
+}
@@ -5,21 +5,69 @@
 
 use ajr_router::RouterState;
 use anyhow::{Context, Result};
-use gix_gxf::GxfEnvelope;
-use gix_proto::v1::{GetRouterStatsRequest, GetRouterStatsResponse, LaneId as ProtoLaneId, RouteEnvelopeRequest, RouteEnvelopeResponse};
-use gix_proto::{RouterService, RouterServiceServer};
+use clap::Parser;
+use gix_common::config::{env_lookup, setting};
+use gix_common::SlpId;
+use gix_gxf::{GxfEnvelope, DEFAULT_MAX_ENVELOPE_BYTES};
+use gix_proto::v1::{HealthCheckRequest, HealthCheckResponse, ServingStatus, GetRouterStatsRequest, GetRouterStatsResponse, LaneId as ProtoLaneId, LaneUtilization, RouteEnvelopeRequest, RouteEnvelopeResponse};
+use gix_proto::errors::{chain_to_status, decoding_limit, draining, oversized_envelope, to_status};
+use gix_proto::reflection::reflection_service;
+use gix_proto::tls::server_tls_config;
+use gix_proto::trace::trace_id_or_new;
+use gix_proto::{HealthService, HealthServiceServer, RouterService, RouterServiceServer};
 use metrics_exporter_prometheus::PrometheusBuilder;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::signal;
 use tonic::{Request, Response, Status};
-use tracing::info;
+use tracing::{info, info_span, Instrument};
 
 const AJR_SERVER_ADDR: &str = "0.0.0.0:50051";
 const METRICS_ADDR: &str = "0.0.0.0:9001";
+const SERVICE_NAME: &str = "gix.v1.RouterService";
+
+/// Bind addresses and limits, overridable through the environment
+struct ServiceConfig {
+    /// gRPC listen address
+    addr: SocketAddr,
+    /// Prometheus metrics listen address
+    metrics_addr: SocketAddr,
+    /// Largest encoded envelope accepted, also sizing the gRPC decode limit
+    ///
+    /// Decompressed payloads are bounded separately by `MAX_PAYLOAD_BYTES`.
+    max_envelope_bytes: usize,
+}
+
+impl ServiceConfig {
+    /// Read `GIX_AJR_ADDR`, `GIX_AJR_METRICS_ADDR` and `GIX_AJR_MAX_ENVELOPE_BYTES`, defaulting unset ones
+    fn load(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        Ok(ServiceConfig {
+            addr: setting(&lookup, "GIX_AJR_ADDR", AJR_SERVER_ADDR)?,
+            metrics_addr: setting(&lookup, "GIX_AJR_METRICS_ADDR", METRICS_ADDR)?,
+            max_envelope_bytes: setting(&lookup, "GIX_AJR_MAX_ENVELOPE_BYTES", &DEFAULT_MAX_ENVELOPE_BYTES.to_string())?,
+        })
+    }
+}
 
 /// Router service implementation
 struct RouterServiceImpl {
     router: Arc<RouterState>,
+    /// Largest encoded envelope accepted
+    max_envelope_bytes: usize,
+    /// Set on shutdown to turn away new envelopes
+    draining: Arc<AtomicBool>,
+}
+
+impl RouterServiceImpl {
+    /// Serve over gRPC, decoding requests up to the envelope limit
+    fn into_server(self) -> RouterServiceServer<Self> {
+        let limit = decoding_limit(self.max_envelope_bytes);
+        RouterServiceServer::new(self).max_decoding_message_size(limit)
+    }
 }
 
 #[tonic::async_trait]
@@ -28,19 +76,27 @@ impl RouterService for RouterServiceImpl {
         &self,
         request: Request<RouteEnvelopeRequest>,
     ) -> Result<Response<RouteEnvelopeResponse>, Status> {
+        if let Some(status) = draining(&self.draining) {
+            return Err(status);
+        }
+        let span = info_span!("route_envelope", trace_id = %trace_id_or_new(&request));
         let req = request.into_inner();
+        if let Some(status) = oversized_envelope(req.envelope.len(), self.max_envelope_bytes) {
+            return Err(status);
+        }
         
         // Deserialize GXF envelope from bytes
         let envelope = GxfEnvelope::from_json(&req.envelope)
-            .map_err(|e| Status::invalid_argument(format!("Invalid envelope: {}", e)))?;
+            .map_err(|e| to_status(&e))?;
         
         // Process through router
         let lane_id = ajr_router::process_envelope(&self.router, envelope)
+            .instrument(span)
             .await
-            .map_err(|e| Status::internal(format!("Routing failed: {}", e)))?;
+            .map_err(|e| chain_to_status(e.as_ref()))?;
         
         Ok(Response::new(RouteEnvelopeResponse {
-            lane_id: Some(ProtoLaneId { id: lane_id.0 as u32 }),
+            lane_id: Some(ProtoLaneId { id: lane_id.as_u32() }),
             success: true,
             error: String::new(),
         }))
@@ -54,18 +110,79 @@ impl RouterService for RouterServiceImpl {
         
         let mut lane_stats = std::collections::HashMap::new();
         for (lane_id, count) in stats.lane_stats.iter() {
-            lane_stats.insert(lane_id.0 as u32, *count);
+            lane_stats.insert(lane_id.as_u32(), *count);
         }
         
+        let lane_utilization = stats
+            .lane_utilization
+            .iter()
+            .map(|(lane_id, (active_jobs, capacity))| {
+                (lane_id.as_u32(), LaneUtilization { active_jobs: *active_jobs, capacity: *capacity })
+            })
+            .collect();
+        
         Ok(Response::new(GetRouterStatsResponse {
             total_routed: stats.total_routed,
             lane_stats,
+            lane_utilization,
+        }))
+    }
+}
+
+/// Health service implementation
+///
+/// The server only starts once the router is initialized, so a reachable
+/// daemon is always serving.
+struct HealthServiceImpl;
+
+#[tonic::async_trait]
+impl HealthService for HealthServiceImpl {
+    async fn check(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        let status = match request.into_inner().service.as_str() {
+            "" | SERVICE_NAME => ServingStatus::Serving,
+            _ => ServingStatus::ServiceUnknown,
+        };
+        
+        Ok(Response::new(HealthCheckResponse {
+            status: status as i32,
         }))
     }
 }
 
+/// Command-line arguments
+#[derive(Parser)]
+#[command(about = "AJR router service")]
+struct Args {
+    /// PEM certificate chain for serving gRPC over TLS
+    #[arg(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key for serving gRPC over TLS
+    #[arg(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// Accept envelopes that expired up to this many seconds ago (clock skew)
+    #[arg(long, default_value_t = 0)]
+    clock_skew_secs: u64,
+
+    /// Known source SLP (repeatable); enables strict routing hint validation
+    #[arg(long = "known-slp")]
+    known_slps: Vec<String>,
+
+    /// Keep serving for this many seconds after a shutdown signal, rejecting
+    /// new requests as draining, before waiting out in-flight ones
+    #[arg(long, default_value_t = 5)]
+    drain_grace_secs: u64,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = ServiceConfig::load(env_lookup)?;
+
     // Initialize tracing
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -77,8 +194,7 @@ async fn main() -> Result<()> {
     info!("AJR Router Service starting...");
 
     // Initialize Prometheus metrics exporter
-    let metrics_addr: SocketAddr = METRICS_ADDR.parse()
-        .context("Invalid metrics address")?;
+    let metrics_addr = config.metrics_addr;
     
     info!("Starting Prometheus metrics endpoint on {}", metrics_addr);
     
@@ -88,25 +204,172 @@ async fn main() -> Result<()> {
         .context("Failed to install Prometheus recorder")?;
 
     // Initialize router state
-    let router = Arc::new(RouterState::new());
+    let mut router = RouterState::new().with_clock_skew(args.clock_skew_secs);
+    if !args.known_slps.is_empty() {
+        info!("Strict routing hint validation enabled for {} SLP(s)", args.known_slps.len());
+        router = router.with_known_slps(args.known_slps.into_iter().map(SlpId).collect());
+    }
+    let router = Arc::new(router);
     info!("Router initialized");
 
     // Create service implementation
+    let draining = Arc::new(AtomicBool::new(false));
     let service = RouterServiceImpl {
         router: router.clone(),
+        max_envelope_bytes: config.max_envelope_bytes,
+        draining: draining.clone(),
     };
 
     // Start gRPC server
-    let addr = AJR_SERVER_ADDR.parse()
-        .context("Invalid server address")?;
+    let addr = config.addr;
     
     info!("Starting gRPC server on {}", addr);
     
-    tonic::transport::Server::builder()
-        .add_service(RouterServiceServer::new(service))
-        .serve(addr)
+    let mut server = tonic::transport::Server::builder();
+    if let (Some(cert), Some(key)) = (&args.tls_cert, &args.tls_key) {
+        info!("Serving gRPC over TLS");
+        server = server
+            .tls_config(server_tls_config(cert, key)?)
+            .context("Invalid TLS configuration")?;
+    }
+    
+    server
+        .add_service(service.into_server())
+        .add_service(HealthServiceServer::new(HealthServiceImpl))
+        .add_service(reflection_service().context("Failed to build reflection service")?)
+        .serve_with_shutdown(
+            addr,
+            shutdown_signal(draining, Duration::from_secs(args.drain_grace_secs), ctrl_c()),
+        )
         .await
         .context("Server error")?;
 
+    let stats = router.get_stats().await;
+    info!("Router drained: {} envelopes routed", stats.total_routed);
+
+    info!("AJR Router Service stopped");
     Ok(())
 }
+
+/// Wait for CTRL+C
+async fn ctrl_c() {
+    signal::ctrl_c()
+        .await
+        .expect("Failed to install CTRL+C signal handler");
+}
+
+/// Wait for shutdown signal and start draining
+///
+/// New envelopes are rejected from the signal on, for `grace` before this
+/// future resolves. In-flight routing requests are then allowed to complete
+/// by `serve_with_shutdown`. Router state is in memory only, so there is
+/// nothing to flush.
+async fn shutdown_signal<S: Future<Output = ()>>(draining: Arc<AtomicBool>, grace: Duration, signal: S) {
+    signal.await;
+    draining.store(true, Ordering::SeqCst);
+
+    info!("Shutdown signal received, draining for {:?}...", grace);
+    tokio::time::sleep(grace).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_signal_returns_on_signal() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+        let draining = Arc::new(AtomicBool::new(false));
+        let shutdown = tokio::spawn(shutdown_signal(draining.clone(), Duration::ZERO, async {
+            rx.await.ok();
+        }));
+        tx.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown)
+            .await
+            .expect("shutdown future did not return")
+            .unwrap();
+        assert!(draining.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signal_waits_out_the_grace_period() {
+        let draining = Arc::new(AtomicBool::new(false));
+        let shutdown = tokio::spawn(shutdown_signal(
+            draining.clone(),
+            Duration::from_millis(300),
+            std::future::ready(()),
+        ));
+
+        // Draining starts at the signal while the server keeps running
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(draining.load(Ordering::SeqCst));
+        assert!(!shutdown.is_finished());
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown)
+            .await
+            .expect("shutdown future did not return after the grace period")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oversized_envelope_rejected_before_parsing() {
+        let config = ServiceConfig::load(|name| {
+            (name == "GIX_AJR_MAX_ENVELOPE_BYTES").then(|| "1024".to_string())
+        })
+        .unwrap();
+        assert_eq!(config.max_envelope_bytes, 1024);
+
+        let router = Arc::new(RouterState::new());
+        let service = RouterServiceImpl {
+            router: router.clone(),
+            max_envelope_bytes: config.max_envelope_bytes,
+            draining: Arc::new(AtomicBool::new(false)),
+        };
+
+        // Not valid JSON either; the size check must fire first
+        let status = service
+            .route_envelope(Request::new(RouteEnvelopeRequest { envelope: vec![b'['; 1025] }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().starts_with("envelope too large"), "{}", status.message());
+
+        // At the limit the envelope reaches the parser
+        let status = service
+            .route_envelope(Request::new(RouteEnvelopeRequest { envelope: vec![b'['; 1024] }))
+            .await
+            .unwrap_err();
+        assert!(!status.message().starts_with("envelope too large"), "{}", status.message());
+        assert_eq!(router.get_stats().await.total_routed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_envelope_limit_above_grpc_default_is_served() {
+        let max_envelope_bytes = 6 << 20;
+        let service = RouterServiceImpl {
+            router: Arc::new(RouterState::new()),
+            max_envelope_bytes,
+            draining: Arc::new(AtomicBool::new(false)),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let incoming = tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(incoming),
+        );
+
+        // Larger than tonic's 4 MiB default, within the configured limit: the
+        // request reaches the service and fails parsing rather than decoding
+        let mut client = gix_proto::RouterServiceClient::connect(addr).await.unwrap();
+        let status = client
+            .route_envelope(RouteEnvelopeRequest { envelope: vec![b'['; 5 << 20] })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument, "{}", status.message());
+        assert!(!status.message().starts_with("envelope too large"), "{}", status.message());
+    }
+}
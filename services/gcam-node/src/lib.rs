@@ -2,21 +2,49 @@
 //!
 //! Provides auction engine state with persistence using the sled embedded database.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use gix_common::{GixError, JobId, LaneId, SlpId};
-use gix_gxf::{GxfEnvelope, GxfJob, PrecisionLevel};
+use gix_crypto::pqc::dilithium::{self, KeyPair, PublicKey, Signature};
+use gix_crypto::{hash_blake3, verify_merkle_proof, MerkleProof, MerkleTree};
+use gix_gxf::{GxfEnvelope, GxfJob, JobPriority, PrecisionLevel};
 use metrics::{counter, gauge, increment_counter, increment_gauge};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sled::Transactional;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{oneshot, Notify, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
 /// Price in micro-tokens (smallest unit)
 pub type Price = u64;
 
+/// Default number of auction records kept in history
+pub const DEFAULT_HISTORY_CAPACITY: usize = 1000;
+
+/// Default weight given to each new latency measurement
+pub const DEFAULT_LATENCY_ALPHA: f64 = 0.2;
+
+/// Default time an idempotency key keeps its auction result
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(600);
+
+/// Default number of idempotency keys remembered at once
+pub const DEFAULT_MAX_IDEMPOTENCY_KEYS: usize = 10_000;
+
+/// Default number of clients a `RateLimiter` tracks at once
+pub const DEFAULT_MAX_RATE_LIMITED_CLIENTS: usize = 10_000;
+
+/// Job parameter that opts a job into precision fallback
+pub const ALLOW_PRECISION_FALLBACK_PARAM: &str = "allow_precision_fallback";
+
 /// Auction match result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AuctionMatch {
     /// Job ID
     pub job_id: JobId,
@@ -28,6 +56,302 @@ pub struct AuctionMatch {
     pub price: Price,
     /// Route path (sequence of nodes)
     pub route: Vec<String>,
+    /// Precision the provider runs the job at (differs from the job's on fallback)
+    pub precision_used: PrecisionLevel,
+    /// Region of the matched provider, checked against residency at execution
+    pub provider_region: String,
+}
+
+impl AuctionMatch {
+    /// Bincode encoding of the match, the bytes hashed and signed in receipts
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("auction match serializes")
+    }
+
+    /// Blake3 hash of the match, used as its leaf in batch receipts
+    pub fn hash(&self) -> [u8; 32] {
+        hash_blake3(&self.canonical_bytes())
+    }
+}
+
+/// Auction match signed by the node that ran the auction
+///
+/// Stored as JSON so a submitter can keep it and check it later against
+/// the node's published public key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuctionReceipt {
+    /// The signed match
+    pub auction_match: AuctionMatch,
+    /// Dilithium signature over `auction_match.canonical_bytes()`
+    pub signature: Signature,
+}
+
+impl AuctionReceipt {
+    /// Sign `auction_match` with the node's key
+    pub fn sign(auction_match: AuctionMatch, keypair: &KeyPair) -> Result<Self, GixError> {
+        let signature = dilithium::sign_detached(&auction_match.canonical_bytes(), &keypair.secret)
+            .map_err(|e| GixError::InternalError(format!("Failed to sign receipt: {}", e)))?;
+        Ok(AuctionReceipt { auction_match, signature })
+    }
+
+    /// Check the signature against the node's public key
+    pub fn verify(&self, node_key: &PublicKey) -> Result<(), GixError> {
+        dilithium::verify_detached(&self.auction_match.canonical_bytes(), &self.signature, node_key)
+            .map_err(|_| GixError::CryptoFailure)
+    }
+
+    /// Encode the receipt as JSON
+    pub fn to_json(&self) -> Result<Vec<u8>, GixError> {
+        serde_json::to_vec_pretty(self)
+            .map_err(|e| GixError::InternalError(format!("Failed to encode receipt: {}", e)))
+    }
+
+    /// Decode a receipt from JSON
+    pub fn from_json(data: &[u8]) -> Result<Self, GixError> {
+        serde_json::from_slice(data)
+            .map_err(|e| GixError::Protocol(format!("Malformed receipt: {}", e)))
+    }
+}
+
+/// Merkle commitment to the matches of one batch auction
+#[derive(Debug, Clone)]
+pub struct BatchReceipt {
+    /// Root over the hashes of `matches`, in order
+    pub root: [u8; 32],
+    /// Jobs that cleared, in submission order
+    pub matches: Vec<AuctionMatch>,
+    /// Inclusion proof for each entry of `matches`
+    pub proofs: Vec<MerkleProof>,
+    /// Jobs that did not clear
+    pub unmatched: Vec<JobId>,
+}
+
+/// Check that `matched` is committed to by a batch receipt's `root`
+///
+/// Needs only the one match and its proof, not the rest of the batch.
+pub fn verify_batch_inclusion(root: &[u8; 32], matched: &AuctionMatch, proof: &MerkleProof) -> bool {
+    verify_merkle_proof(root, &matched.hash(), proof)
+}
+
+/// Read-only preview of an auction
+#[derive(Debug, Clone)]
+pub struct AuctionQuote {
+    /// Capable providers and their current prices, cheapest first
+    pub candidates: Vec<(SlpId, Price)>,
+    /// Route the auction would select, if any
+    pub route: Option<Route>,
+}
+
+/// Completed auction record kept for auditing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuctionRecord {
+    /// Job ID
+    pub job_id: JobId,
+    /// Winning SLP ID
+    pub slp_id: SlpId,
+    /// Clearing price
+    pub price: Price,
+    /// Selected lane ID
+    pub lane_id: LaneId,
+    /// Completion timestamp (Unix epoch in seconds)
+    pub timestamp: u64,
+    /// Number of providers that could handle the job
+    pub candidate_count: u32,
+}
+
+/// Schema version of persisted provider records
+///
+/// Bincode is not self-describing, so a record cannot be decoded into a struct
+/// with more fields than it was written with. Every change to the
+/// `ComputeProvider` layout must bump this version and keep the previous
+/// layout decodable in [`ComputeProvider::from_record`].
+pub const PROVIDER_SCHEMA_VERSION: u16 = 3;
+
+/// Prefix of versioned provider records
+///
+/// Unversioned records start with the little-endian length of the SLP id,
+/// which never begins with these bytes in practice.
+const PROVIDER_RECORD_MAGIC: [u8; 4] = [0xFF, b'G', b'P', b'R'];
+
+/// Schema version of the persisted auction stats record
+///
+/// As with providers, every change to the `AuctionStats` layout must bump
+/// this version and keep the previous layout decodable in
+/// [`AuctionStats::from_record`].
+pub const STATS_SCHEMA_VERSION: u16 = 2;
+
+/// Prefix of versioned stats records
+///
+/// Unversioned records start with `total_auctions`, which would need an
+/// implausible count to begin with these bytes.
+const STATS_RECORD_MAGIC: [u8; 4] = [0xFF, b'G', b'S', b'T'];
+
+/// Schema version of persisted route records
+///
+/// Schema 2 stores the latency estimate as a fractional average; schema 1
+/// records, written without the storage wrapper, rounded it to whole
+/// milliseconds.
+pub const ROUTE_SCHEMA_VERSION: u16 = 2;
+
+/// Prefix of versioned route records
+///
+/// Unversioned records start with the little-endian length of the route id,
+/// which never begins with these bytes in practice.
+const ROUTE_RECORD_MAGIC: [u8; 4] = [0xFF, b'G', b'R', b'T'];
+
+/// Format version of exported engine snapshots
+///
+/// Version 2 stores providers and stats as their versioned storage records,
+/// so snapshots stay importable across changes to those layouts. Version 3
+/// does the same for routes.
+pub const SNAPSHOT_VERSION: u16 = 3;
+
+/// Prefix of exported engine snapshots
+const SNAPSHOT_MAGIC: [u8; 4] = [0xFF, b'G', b'S', b'N'];
+
+/// Full engine state exported by [`AuctionEngine::export_snapshot`]
+#[derive(Debug, Clone)]
+struct EngineSnapshot {
+    providers: Vec<ComputeProvider>,
+    routes: Vec<Route>,
+    stats: AuctionStats,
+    /// Auction history, oldest first
+    history: Vec<AuctionRecord>,
+}
+
+/// Snapshot body as written, with providers, routes and stats as storage records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotRecords {
+    providers: Vec<Vec<u8>>,
+    routes: Vec<Vec<u8>>,
+    stats: Vec<u8>,
+    history: Vec<AuctionRecord>,
+}
+
+/// Snapshot body written by version 1, in the layouts of the time
+#[derive(Deserialize)]
+struct EngineSnapshotV1 {
+    providers: Vec<ComputeProvider>,
+    routes: Vec<RouteV1>,
+    stats: AuctionStats,
+    history: Vec<AuctionRecord>,
+}
+
+impl From<EngineSnapshotV1> for EngineSnapshot {
+    fn from(old: EngineSnapshotV1) -> Self {
+        EngineSnapshot {
+            providers: old.providers,
+            routes: old.routes.into_iter().map(Route::from).collect(),
+            stats: old.stats,
+            history: old.history,
+        }
+    }
+}
+
+/// Snapshot body written by version 2, before routes were storage records
+#[derive(Deserialize)]
+struct SnapshotRecordsV2 {
+    providers: Vec<Vec<u8>>,
+    routes: Vec<RouteV1>,
+    stats: Vec<u8>,
+    history: Vec<AuctionRecord>,
+}
+
+impl EngineSnapshot {
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let records = SnapshotRecords {
+            providers: self.providers.iter().map(ComputeProvider::to_record).collect::<Result<_>>()?,
+            routes: self.routes.iter().map(Route::to_record).collect::<Result<_>>()?,
+            stats: self.stats.to_record()?,
+            history: self.history.clone(),
+        };
+        let mut bytes = SNAPSHOT_MAGIC.to_vec();
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bytes.extend(bincode::serialize(&records)?);
+        Ok(bytes)
+    }
+
+    fn from_records(records: SnapshotRecords) -> Result<Self, GixError> {
+        let corrupt = |e: anyhow::Error| GixError::Protocol(format!("Corrupt snapshot: {}", e));
+        let providers = records
+            .providers
+            .iter()
+            .map(|record| ComputeProvider::from_record(record).map(|(provider, _)| provider))
+            .collect::<Result<_>>()
+            .map_err(corrupt)?;
+        let routes = records
+            .routes
+            .iter()
+            .map(|record| Route::from_record(record).map(|(route, _)| route))
+            .collect::<Result<_>>()
+            .map_err(corrupt)?;
+        Ok(EngineSnapshot {
+            providers,
+            routes,
+            stats: AuctionStats::from_record(&records.stats).map_err(corrupt)?,
+            history: records.history,
+        })
+    }
+
+    /// Decode and check a snapshot without touching any engine state
+    fn from_bytes(bytes: &[u8]) -> Result<Self, GixError> {
+        let body = bytes
+            .strip_prefix(&SNAPSHOT_MAGIC[..])
+            .ok_or_else(|| GixError::Protocol("Not an auction engine snapshot".to_string()))?;
+        if body.len() < 2 {
+            return Err(GixError::Protocol("Truncated snapshot header".to_string()));
+        }
+        let version = u16::from_le_bytes([body[0], body[1]]);
+        let corrupt = |e: bincode::Error| GixError::Protocol(format!("Corrupt snapshot: {}", e));
+        let snapshot = match version {
+            1 => bincode::deserialize::<EngineSnapshotV1>(&body[2..]).map_err(corrupt)?.into(),
+            2 => {
+                let old: SnapshotRecordsV2 = bincode::deserialize(&body[2..]).map_err(corrupt)?;
+                let snapshot = Self::from_records(SnapshotRecords {
+                    providers: old.providers,
+                    routes: Vec::new(),
+                    stats: old.stats,
+                    history: old.history,
+                })?;
+                EngineSnapshot {
+                    routes: old.routes.into_iter().map(Route::from).collect(),
+                    ..snapshot
+                }
+            }
+            SNAPSHOT_VERSION => {
+                Self::from_records(bincode::deserialize(&body[2..]).map_err(corrupt)?)?
+            }
+            _ => return Err(GixError::Protocol(format!("Unsupported snapshot version {}", version))),
+        };
+
+        let mut slp_ids = HashSet::new();
+        for provider in &snapshot.providers {
+            provider.validate()?;
+            if !slp_ids.insert(&provider.slp_id) {
+                return Err(GixError::Protocol(format!("Duplicate provider {} in snapshot", provider.slp_id)));
+            }
+        }
+        let mut route_ids = HashSet::new();
+        for route in &snapshot.routes {
+            if !route_ids.insert(route.id.as_str()) {
+                return Err(GixError::Protocol(format!("Duplicate route {} in snapshot", route.id)));
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Whether a provider is offered new jobs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderStatus {
+    /// Matched by auctions
+    #[default]
+    Active,
+    /// Finishing the jobs it holds; not matched to new ones
+    Draining,
+    /// Out of service, e.g. for maintenance; not matched
+    Disabled,
 }
 
 /// Compute resource provider
@@ -45,34 +369,225 @@ pub struct ComputeProvider {
     pub utilization: u32,
     /// Region/location
     pub region: String,
+    /// Longest KV-cache sequence the provider's hardware supports
+    pub max_seq_len: u32,
+    /// Whether auctions may match the provider
+    #[serde(default)]
+    pub status: ProviderStatus,
+}
+
+/// Provider record layout written before `max_seq_len` existed (schema 1)
+#[derive(Deserialize)]
+struct ComputeProviderV1 {
+    slp_id: SlpId,
+    supported_precisions: Vec<PrecisionLevel>,
+    base_price: Price,
+    capacity: u32,
+    utilization: u32,
+    region: String,
+}
+
+impl From<ComputeProviderV1> for ComputeProvider {
+    /// Providers stored before sequence limits were tracked accept any length
+    fn from(old: ComputeProviderV1) -> Self {
+        ComputeProvider {
+            slp_id: old.slp_id,
+            supported_precisions: old.supported_precisions,
+            base_price: old.base_price,
+            capacity: old.capacity,
+            utilization: old.utilization,
+            region: old.region,
+            max_seq_len: u32::MAX,
+            status: ProviderStatus::Active,
+        }
+    }
+}
+
+/// Provider record layout written before `status` existed (schema 2)
+#[derive(Deserialize)]
+struct ComputeProviderV2 {
+    slp_id: SlpId,
+    supported_precisions: Vec<PrecisionLevel>,
+    base_price: Price,
+    capacity: u32,
+    utilization: u32,
+    region: String,
+    max_seq_len: u32,
+}
+
+impl From<ComputeProviderV2> for ComputeProvider {
+    /// Providers stored before maintenance existed are in service
+    fn from(old: ComputeProviderV2) -> Self {
+        ComputeProvider {
+            slp_id: old.slp_id,
+            supported_precisions: old.supported_precisions,
+            base_price: old.base_price,
+            capacity: old.capacity,
+            utilization: old.utilization,
+            region: old.region,
+            max_seq_len: old.max_seq_len,
+            status: ProviderStatus::Active,
+        }
+    }
 }
 
 impl ComputeProvider {
+    /// Encode the provider as a versioned storage record
+    pub fn to_record(&self) -> Result<Vec<u8>> {
+        let mut record = PROVIDER_RECORD_MAGIC.to_vec();
+        record.extend_from_slice(&PROVIDER_SCHEMA_VERSION.to_le_bytes());
+        record.extend(bincode::serialize(self)?);
+        Ok(record)
+    }
+
+    /// Decode a storage record, upgrading older layouts
+    ///
+    /// Returns the provider and the schema version the record was written
+    /// with. Unversioned records predate the storage wrapper and are tried in
+    /// the schema 2 layout, then the schema 1 layout.
+    pub fn from_record(bytes: &[u8]) -> Result<(ComputeProvider, u16)> {
+        let Some(rest) = bytes.strip_prefix(&PROVIDER_RECORD_MAGIC[..]) else {
+            return bincode::deserialize::<ComputeProviderV2>(bytes)
+                .map(|old| (old.into(), 2))
+                .or_else(|e| {
+                    bincode::deserialize::<ComputeProviderV1>(bytes)
+                        .map(|old| (old.into(), 1))
+                        .map_err(|_| e.into())
+                });
+        };
+
+        if rest.len() < 2 {
+            anyhow::bail!("Truncated provider record header");
+        }
+        let (version, body) = rest.split_at(2);
+        let version = u16::from_le_bytes([version[0], version[1]]);
+        let provider = match version {
+            1 => bincode::deserialize::<ComputeProviderV1>(body)?.into(),
+            2 => bincode::deserialize::<ComputeProviderV2>(body)?.into(),
+            PROVIDER_SCHEMA_VERSION => bincode::deserialize(body)?,
+            _ => anyhow::bail!("Unsupported provider schema version {}", version),
+        };
+        Ok((provider, version))
+    }
+
+    /// Check that the provider description is usable by auctions
+    pub fn validate(&self) -> Result<(), GixError> {
+        if self.slp_id.0.is_empty() {
+            return Err(GixError::Protocol("Provider SLP ID cannot be empty".to_string()));
+        }
+        if self.supported_precisions.is_empty() {
+            return Err(GixError::Protocol(format!("Provider {} supports no precisions", self.slp_id)));
+        }
+        if self.utilization > self.capacity {
+            return Err(GixError::Protocol(format!(
+                "Provider {} utilization {} exceeds capacity {}",
+                self.slp_id, self.utilization, self.capacity
+            )));
+        }
+        if self.max_seq_len == 0 {
+            return Err(GixError::Protocol(format!("Provider {} has a zero max_seq_len", self.slp_id)));
+        }
+        Ok(())
+    }
+
     /// Check if provider can handle a job
     pub fn can_handle(&self, job: &GxfJob) -> bool {
+        if self.status != ProviderStatus::Active {
+            return false;
+        }
         if !self.supported_precisions.contains(&job.precision) {
             return false;
         }
+        if job.kv_cache_seq_len > self.max_seq_len {
+            return false;
+        }
         if self.utilization >= self.capacity {
             return false;
         }
         true
     }
 
-    /// Calculate price for a job
-    pub fn calculate_price(&self, job: &GxfJob) -> Price {
-        let mut price = self.base_price;
-        price += (job.kv_cache_seq_len as u64) * 10;
-        let precision_multiplier = match job.precision {
-            PrecisionLevel::INT8 => 1.0,
-            PrecisionLevel::E5M2 => 1.2,
-            PrecisionLevel::FP8 => 1.5,
-            PrecisionLevel::BF16 => 2.0,
-        };
-        price = (price as f64 * precision_multiplier) as u64;
-        let utilization_factor = 1.0 + (self.utilization as f64 / self.capacity as f64) * 0.5;
-        price = (price as f64 * utilization_factor) as u64;
-        price
+    /// Calculate price for a job using the default pricing model
+    pub fn calculate_price(&self, job: &GxfJob) -> Result<Price, GixError> {
+        DefaultPricingModel::default().price(self, job)
+    }
+}
+
+/// Pricing formula used to quote a provider for a job
+pub trait PricingModel: Send + Sync {
+    /// Quote the price `provider` charges for `job`
+    ///
+    /// Fails with `GixError::Protocol` when the price cannot be represented,
+    /// rather than returning a wrapped or saturated value.
+    fn price(&self, provider: &ComputeProvider, job: &GxfJob) -> Result<Price, GixError>;
+}
+
+/// Scale a price by `factor`, failing if the result is negative or overflows
+fn scale_price(price: Price, factor: f64) -> Result<Price, GixError> {
+    let scaled = price as f64 * factor;
+    if scaled.is_nan() || scaled < 0.0 {
+        return Err(GixError::Protocol("negative price".to_string()));
+    }
+    // u64::MAX rounds up to 2^64 as f64, the first value that does not fit
+    if scaled >= u64::MAX as f64 {
+        return Err(GixError::Protocol("price overflow".to_string()));
+    }
+    Ok(scaled as Price)
+}
+
+/// Base price plus sequence-length surcharge, scaled by precision and utilization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DefaultPricingModel {
+    /// Price added per KV-cache sequence position
+    pub seq_len_rate: Price,
+    /// Multiplier for INT8 jobs
+    pub int8_multiplier: f64,
+    /// Multiplier for E5M2 jobs
+    pub e5m2_multiplier: f64,
+    /// Multiplier for FP8 jobs
+    pub fp8_multiplier: f64,
+    /// Multiplier for BF16 jobs
+    pub bf16_multiplier: f64,
+    /// Surcharge at full utilization (0.5 = +50%)
+    pub utilization_weight: f64,
+}
+
+impl Default for DefaultPricingModel {
+    fn default() -> Self {
+        DefaultPricingModel {
+            seq_len_rate: gix_gxf::DEFAULT_SEQ_LEN_RATE,
+            int8_multiplier: PrecisionLevel::INT8.default_price_multiplier(),
+            e5m2_multiplier: PrecisionLevel::E5M2.default_price_multiplier(),
+            fp8_multiplier: PrecisionLevel::FP8.default_price_multiplier(),
+            bf16_multiplier: PrecisionLevel::BF16.default_price_multiplier(),
+            utilization_weight: 0.5,
+        }
+    }
+}
+
+impl DefaultPricingModel {
+    /// Multiplier applied for a precision level
+    pub fn precision_multiplier(&self, precision: PrecisionLevel) -> f64 {
+        match precision {
+            PrecisionLevel::INT8 => self.int8_multiplier,
+            PrecisionLevel::E5M2 => self.e5m2_multiplier,
+            PrecisionLevel::FP8 => self.fp8_multiplier,
+            PrecisionLevel::BF16 => self.bf16_multiplier,
+        }
+    }
+}
+
+impl PricingModel for DefaultPricingModel {
+    fn price(&self, provider: &ComputeProvider, job: &GxfJob) -> Result<Price, GixError> {
+        let overflow = || GixError::Protocol("price overflow".to_string());
+        let mut price = job
+            .surcharged_price(provider.base_price, self.seq_len_rate)
+            .ok_or_else(overflow)?;
+        price = scale_price(price, self.precision_multiplier(job.precision))?;
+        let utilization_factor = 1.0
+            + (provider.utilization as f64 / provider.capacity as f64) * self.utilization_weight;
+        scale_price(price, utilization_factor)
     }
 }
 
@@ -85,19 +600,79 @@ pub struct Route {
     pub lane_id: LaneId,
     /// Route path (sequence of node IDs)
     pub path: Vec<String>,
-    /// Route latency estimate (ms)
-    pub latency_ms: u64,
+    /// Route latency estimate (ms), an average of reported latencies
+    pub latency_ms: f64,
     /// Route cost
     pub cost: Price,
 }
 
+/// Route record layout with the latency rounded to whole milliseconds (schema 1)
+#[derive(Deserialize)]
+struct RouteV1 {
+    id: String,
+    lane_id: LaneId,
+    path: Vec<String>,
+    latency_ms: u64,
+    cost: Price,
+}
+
+impl From<RouteV1> for Route {
+    fn from(old: RouteV1) -> Self {
+        Route {
+            id: old.id,
+            lane_id: old.lane_id,
+            path: old.path,
+            latency_ms: old.latency_ms as f64,
+            cost: old.cost,
+        }
+    }
+}
+
 impl Route {
+    /// Encode the route as a versioned storage record
+    pub fn to_record(&self) -> Result<Vec<u8>> {
+        let mut record = ROUTE_RECORD_MAGIC.to_vec();
+        record.extend_from_slice(&ROUTE_SCHEMA_VERSION.to_le_bytes());
+        record.extend(bincode::serialize(self)?);
+        Ok(record)
+    }
+
+    /// Decode a storage record, upgrading older layouts
+    ///
+    /// Returns the route and the schema version the record was written with.
+    /// Unversioned records predate the storage wrapper and are read in the
+    /// schema 1 layout.
+    pub fn from_record(bytes: &[u8]) -> Result<(Route, u16)> {
+        let Some(rest) = bytes.strip_prefix(&ROUTE_RECORD_MAGIC[..]) else {
+            return Ok((bincode::deserialize::<RouteV1>(bytes)?.into(), 1));
+        };
+
+        if rest.len() < 2 {
+            anyhow::bail!("Truncated route record header");
+        }
+        let (version, body) = rest.split_at(2);
+        let version = u16::from_le_bytes([version[0], version[1]]);
+        let route = match version {
+            1 => bincode::deserialize::<RouteV1>(body)?.into(),
+            ROUTE_SCHEMA_VERSION => bincode::deserialize(body)?,
+            _ => anyhow::bail!("Unsupported route schema version {}", version),
+        };
+        Ok((route, version))
+    }
+
     /// Calculate route score (lower is better)
     pub fn score(&self) -> f64 {
-        let latency_score = self.latency_ms as f64 / 1000.0;
+        let latency_score = self.latency_ms / 1000.0;
         let cost_score = self.cost as f64 / 1000000.0;
         latency_score + cost_score
     }
+
+    /// Order routes by score, breaking ties on route ID
+    pub fn cmp_score(&self, other: &Route) -> std::cmp::Ordering {
+        self.score()
+            .total_cmp(&other.score())
+            .then_with(|| self.id.cmp(&other.id))
+    }
 }
 
 /// Auction statistics
@@ -111,12 +686,199 @@ pub struct AuctionStats {
     pub total_unmatched: u64,
     /// Total volume (sum of all prices)
     pub total_volume: u64,
+    /// Lowest clearing price (0 until the first match)
+    pub min_price: Price,
+    /// Highest clearing price
+    pub max_price: Price,
+    /// Mean clearing price (0.0 until the first match)
+    pub avg_price: f64,
     /// Matches by precision
     pub matches_by_precision: HashMap<PrecisionLevel, u64>,
     /// Matches by lane
     pub matches_by_lane: HashMap<LaneId, u64>,
 }
 
+/// Stats record layout written before clearing prices were tracked (schema 1)
+#[derive(Deserialize)]
+struct AuctionStatsV1 {
+    total_auctions: u64,
+    total_matches: u64,
+    total_unmatched: u64,
+    total_volume: u64,
+    matches_by_precision: HashMap<PrecisionLevel, u64>,
+    matches_by_lane: HashMap<LaneId, u64>,
+}
+
+impl From<AuctionStatsV1> for AuctionStats {
+    /// The mean follows from the volume; the extremes are unknown until the next match
+    fn from(old: AuctionStatsV1) -> Self {
+        let avg_price = if old.total_matches == 0 {
+            0.0
+        } else {
+            old.total_volume as f64 / old.total_matches as f64
+        };
+        AuctionStats {
+            total_auctions: old.total_auctions,
+            total_matches: old.total_matches,
+            total_unmatched: old.total_unmatched,
+            total_volume: old.total_volume,
+            min_price: 0,
+            max_price: 0,
+            avg_price,
+            matches_by_precision: old.matches_by_precision,
+            matches_by_lane: old.matches_by_lane,
+        }
+    }
+}
+
+impl AuctionStats {
+    /// Encode the stats as a versioned storage record
+    pub fn to_record(&self) -> Result<Vec<u8>> {
+        let mut record = STATS_RECORD_MAGIC.to_vec();
+        record.extend_from_slice(&STATS_SCHEMA_VERSION.to_le_bytes());
+        record.extend(bincode::serialize(self)?);
+        Ok(record)
+    }
+
+    /// Decode a storage record, upgrading older layouts
+    ///
+    /// Unversioned records predate the storage wrapper and are read in the
+    /// schema 1 layout.
+    pub fn from_record(bytes: &[u8]) -> Result<AuctionStats> {
+        let Some(rest) = bytes.strip_prefix(&STATS_RECORD_MAGIC[..]) else {
+            return Ok(bincode::deserialize::<AuctionStatsV1>(bytes)?.into());
+        };
+
+        if rest.len() < 2 {
+            anyhow::bail!("Truncated stats record header");
+        }
+        let (version, body) = rest.split_at(2);
+        let version = u16::from_le_bytes([version[0], version[1]]);
+        match version {
+            1 => Ok(bincode::deserialize::<AuctionStatsV1>(body)?.into()),
+            STATS_SCHEMA_VERSION => Ok(bincode::deserialize(body)?),
+            _ => anyhow::bail!("Unsupported stats schema version {}", version),
+        }
+    }
+
+    /// Fold a matched auction's clearing price into the price statistics
+    fn record_price(&mut self, price: Price) {
+        if self.total_matches == 0 {
+            return;
+        }
+        // A zero minimum is unknown, as after upgrading schema 1 stats
+        self.min_price = if self.total_matches == 1 || self.min_price == 0 {
+            price
+        } else {
+            self.min_price.min(price)
+        };
+        self.max_price = self.max_price.max(price);
+        self.avg_price += (price as f64 - self.avg_price) / self.total_matches as f64;
+    }
+}
+
+/// Token-bucket limit applied to each auction client
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// Sustained requests per second
+    pub requests_per_sec: f64,
+    /// Requests a client may make at once after being idle
+    pub burst: u32,
+}
+
+/// Remaining tokens for one client
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Per-client token-bucket rate limiter
+///
+/// Buckets start full and refill continuously at `requests_per_sec`, up to
+/// `burst` tokens. Each admitted request spends one token. Clients should be
+/// keyed on something the caller cannot choose freely, such as the peer
+/// address or an authenticated identity.
+///
+/// At most `max_clients` buckets are kept. When a new client arrives at the
+/// cap, buckets that have refilled completely are dropped (a full bucket is
+/// the same as none), then the longest idle one.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    max_clients: usize,
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter applying `limit` to every client
+    pub fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            limit,
+            max_clients: DEFAULT_MAX_RATE_LIMITED_CLIENTS,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Set the most clients tracked at once (at least one)
+    pub fn with_max_clients(mut self, max_clients: usize) -> Self {
+        self.max_clients = max_clients.max(1);
+        self
+    }
+
+    /// Number of clients currently tracked
+    pub fn tracked_clients(&self) -> usize {
+        self.buckets.lock().expect("rate limiter poisoned").len()
+    }
+
+    /// Spend a token for `client`, returning false if its bucket is empty
+    pub fn try_acquire(&self, client: &str) -> bool {
+        let now = Instant::now();
+        let burst = self.limit.burst as f64;
+        let mut buckets = self.buckets.lock().expect("rate limiter poisoned");
+        if !buckets.contains_key(client) && buckets.len() >= self.max_clients {
+            self.evict(&mut buckets, now);
+        }
+        let bucket = buckets.entry(client.to_string()).or_insert(TokenBucket {
+            tokens: burst,
+            refilled_at: now,
+        });
+
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.refilled_at = now;
+
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+
+    /// Tokens `bucket` holds at `now`
+    fn refilled(&self, bucket: &TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        (bucket.tokens + elapsed * self.limit.requests_per_sec).min(self.limit.burst as f64)
+    }
+
+    /// Make room for one more client
+    fn evict(&self, buckets: &mut HashMap<String, TokenBucket>, now: Instant) {
+        let burst = self.limit.burst as f64;
+        buckets.retain(|_, bucket| self.refilled(bucket, now) < burst);
+        if buckets.len() < self.max_clients {
+            return;
+        }
+
+        let idlest = buckets
+            .iter()
+            .min_by_key(|(_, bucket)| bucket.refilled_at)
+            .map(|(client, _)| client.clone());
+        if let Some(client) = idlest {
+            buckets.remove(&client);
+            increment_counter!("gix_auction_rate_limit_evictions_total");
+        }
+    }
+}
+
 /// GCAM Auction Engine state with persistent storage
 #[derive(Clone)]
 pub struct AuctionEngine {
@@ -128,29 +890,236 @@ pub struct AuctionEngine {
     routes: Arc<RwLock<Vec<Route>>>,
     /// In-memory stats (synced with DB)
     stats: Arc<RwLock<AuctionStats>>,
+    /// Maximum number of auction records kept in history
+    history_capacity: usize,
+    /// Records in the history tree, counted so trimming never scans it
+    history_len: Arc<AtomicUsize>,
+    /// Auction records awaiting a flush (deferred persistence)
+    pending_history: Arc<Mutex<PendingHistory>>,
+    /// Pricing formula used to rank and charge providers
+    pricing: Arc<dyn PricingModel>,
+    /// Grace window for envelope expiry, in seconds
+    clock_skew_secs: u64,
+    /// Precision substitution order tried when no provider matches exactly
+    precision_fallback: Vec<PrecisionLevel>,
+    /// Jobs with an auction currently running
+    in_flight: Arc<Mutex<HashSet<JobId>>>,
+    /// Results of recent auctions by idempotency key, with when they ran
+    idempotency: Arc<Mutex<HashMap<String, (AuctionMatch, Instant)>>>,
+    /// How long an idempotency key keeps its result
+    idempotency_ttl: Duration,
+    /// Most idempotency keys remembered at once (0 disables the cache)
+    max_idempotency_keys: usize,
+    /// Weight of each new sample in the route latency moving average
+    latency_alpha: f64,
+    /// Wakes auctions waiting for provider capacity
+    capacity_freed: Arc<Notify>,
+    /// Node key used to sign auction receipts (None disables receipts)
+    receipt_key: Option<Arc<KeyPair>>,
+    /// Whether auctions persist their changes immediately
+    persistence: PersistenceMode,
+    /// Auction state changed since the last flush (deferred persistence)
+    dirty: Arc<AtomicBool>,
+    /// How auctions pick among capable providers
+    mode: AuctionMode,
+    /// Randomness for `AuctionMode::WeightedRandom`
+    rng: Arc<Mutex<StdRng>>,
+    /// Minimum VDF iterations required on envelopes (None disables the gate)
+    #[cfg(feature = "vdf")]
+    vdf_min_iterations: Option<u64>,
+}
+
+/// Auction records queued for the history tree, oldest first, keyed as they will be stored
+type PendingHistory = VecDeque<([u8; 8], AuctionRecord)>;
+
+/// When auctions write their state changes to the database
+///
+/// Measured with `test_persistence_mode_throughput` (500 auctions, debug
+/// build): `Deferred` ran about 2x as many auctions per second as `Immediate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PersistenceMode {
+    /// Write and flush providers, stats and history after every auction
+    #[default]
+    Immediate,
+    /// Mark state dirty and leave writing it to `flush`, periodic or on shutdown
+    ///
+    /// A crash loses the auctions since the last flush.
+    Deferred,
+}
+
+/// How an auction picks among providers that can run the job
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AuctionMode {
+    /// Always the cheapest provider with capacity
+    #[default]
+    Cheapest,
+    /// A random provider priced within `price_band` of the cheapest
+    ///
+    /// `price_band` is a fraction of the lowest price: 0.1 admits providers
+    /// up to 10% pricier. Chances are weighted by inverse price, so cheaper
+    /// providers still win more often.
+    WeightedRandom { price_band: f64 },
+}
+
+/// Background task flushing the engine on an interval
+///
+/// Dropping the handle stops the task after any flush in progress.
+pub struct FlushHandle {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl FlushHandle {
+    /// Stop the task and wait for it to finish
+    pub async fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.task.await;
+    }
+}
+
+/// Marks a job as in flight until dropped
+struct InFlightGuard {
+    in_flight: Arc<Mutex<HashSet<JobId>>>,
+    job_id: JobId,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight
+            .lock()
+            .expect("in-flight set poisoned")
+            .remove(&self.job_id);
+    }
+}
+
+/// Attempts to acquire the database lock before giving up
+const DB_LOCK_ATTEMPTS: u32 = 10;
+/// Delay between database lock attempts
+const DB_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Whether another handle holds the lock on the database at `path`
+///
+/// sled reports a held lock only through its error message, so the lock file
+/// is probed directly, with the same advisory lock sled takes on it.
+fn is_locked(path: &Path) -> std::io::Result<bool> {
+    let file = match std::fs::File::open(path.join("db")) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    match file.try_lock() {
+        Ok(()) => Ok(false),
+        Err(std::fs::TryLockError::WouldBlock) => Ok(true),
+        Err(std::fs::TryLockError::Error(e)) => Err(e),
+    }
 }
 
 /// Helper function to open the database
+///
+/// Fails with `GixError::StorageLocked` if another handle holds the lock;
+/// [`AuctionEngine::open`] waits for a lock held by a handle shutting down.
 pub fn open_db<P: AsRef<Path>>(path: P) -> Result<sled::Db> {
-    let db = sled::open(path)?;
-    Ok(db)
+    let path = path.as_ref();
+    if is_locked(path)? {
+        return Err(GixError::StorageLocked(format!("{} is in use by another process", path.display())).into());
+    }
+    Ok(sled::open(path)?)
+}
+
+/// Decode every intact record in a tree with `decode`, quarantining corrupt ones
+///
+/// A record that fails to decode is moved under the same key into the
+/// `<tree>_quarantine` tree, for inspection, and the rest load as usual.
+fn decode_tree_with<T>(
+    db: &sled::Db,
+    tree: &sled::Tree,
+    decode: impl Fn(&[u8]) -> Result<T>,
+) -> Result<Vec<T>> {
+    let mut records = Vec::new();
+    let mut corrupt = Vec::new();
+
+    for item in tree.iter() {
+        let (key, value) = item?;
+        match decode(&value) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                warn!(
+                    "Corrupt record {:?} in {} tree ({}); quarantining it",
+                    String::from_utf8_lossy(&key),
+                    String::from_utf8_lossy(&tree.name()),
+                    e
+                );
+                increment_counter!("gix_db_quarantined_records_total");
+                corrupt.push((key, value));
+            }
+        }
+    }
+
+    if !corrupt.is_empty() {
+        let quarantine = db.open_tree(quarantine_tree_name(tree))?;
+        for (key, value) in corrupt {
+            quarantine.insert(&key, value)?;
+            tree.remove(&key)?;
+        }
+        quarantine.flush()?;
+        tree.flush()?;
+    }
+
+    Ok(records)
+}
+
+/// Name of the tree holding the corrupt records moved out of `tree`
+fn quarantine_tree_name(tree: &sled::Tree) -> String {
+    format!("{}_quarantine", String::from_utf8_lossy(&tree.name()))
 }
 
 impl AuctionEngine {
     /// Create new auction engine with persistent storage
     pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
-        let db = open_db(db_path)?;
-        
+        Self::from_db(open_db(db_path)?)
+    }
+
+    /// Create an auction engine, briefly waiting out a database lock
+    ///
+    /// A lock held by a handle that is still shutting down is retried without
+    /// blocking the runtime; one that stays held fails with
+    /// `GixError::StorageLocked`.
+    pub async fn open<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        let path = db_path.as_ref();
+        let mut attempt = 1;
+        loop {
+            match open_db(path) {
+                Err(e)
+                    if attempt < DB_LOCK_ATTEMPTS
+                        && matches!(e.downcast_ref::<GixError>(), Some(GixError::StorageLocked(_))) =>
+                {
+                    attempt += 1;
+                    tokio::time::sleep(DB_LOCK_RETRY_INTERVAL).await;
+                }
+                db => return Self::from_db(db?),
+            }
+        }
+    }
+
+    /// Create an auction engine backed by a temporary database
+    ///
+    /// Starts from the default providers and routes; nothing outlives the engine.
+    pub fn temporary() -> Result<Self> {
+        Self::from_db(sled::Config::new().temporary(true).open()?)
+    }
+
+    fn from_db(db: sled::Db) -> Result<Self> {
         // Open/create specific trees
         let providers_tree = db.open_tree("providers")?;
         let routes_tree = db.open_tree("routes")?;
         let stats_tree = db.open_tree("stats")?;
+        let history_len = db.open_tree("history")?.len();
         
         // Load providers from DB or initialize default
-        let providers = Self::load_providers(&providers_tree)?;
+        let providers = Self::load_providers(&db, &providers_tree)?;
         
         // Load routes from DB or initialize default
-        let routes = Self::load_routes(&routes_tree)?;
+        let routes = Self::load_routes(&db, &routes_tree)?;
         
         // Load stats from DB or initialize default
         let stats = Self::load_stats(&stats_tree)?;
@@ -160,26 +1129,166 @@ impl AuctionEngine {
             providers: Arc::new(RwLock::new(providers)),
             routes: Arc::new(RwLock::new(routes)),
             stats: Arc::new(RwLock::new(stats)),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            history_len: Arc::new(AtomicUsize::new(history_len)),
+            pending_history: Arc::new(Mutex::new(VecDeque::new())),
+            pricing: Arc::new(DefaultPricingModel::default()),
+            clock_skew_secs: 0,
+            precision_fallback: Vec::new(),
+            in_flight: Arc::new(Mutex::new(HashSet::new())),
+            idempotency: Arc::new(Mutex::new(HashMap::new())),
+            idempotency_ttl: DEFAULT_IDEMPOTENCY_TTL,
+            max_idempotency_keys: DEFAULT_MAX_IDEMPOTENCY_KEYS,
+            latency_alpha: DEFAULT_LATENCY_ALPHA,
+            capacity_freed: Arc::new(Notify::new()),
+            receipt_key: None,
+            persistence: PersistenceMode::default(),
+            dirty: Arc::new(AtomicBool::new(false)),
+            mode: AuctionMode::default(),
+            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
+            #[cfg(feature = "vdf")]
+            vdf_min_iterations: None,
         })
     }
-    
-    /// Load providers from database
-    fn load_providers(tree: &sled::Tree) -> Result<Vec<ComputeProvider>> {
-        let mut providers = Vec::new();
-        
-        for item in tree.iter() {
-            let (_key, value) = item?;
-            let provider: ComputeProvider = bincode::deserialize(&value)?;
-            providers.push(provider);
-        }
-        
-        // If no providers in DB, initialize with default providers
-        if providers.is_empty() {
-            providers = vec![
-                ComputeProvider {
-                    slp_id: SlpId("slp-us-east-1".to_string()),
-                    supported_precisions: vec![
-                        PrecisionLevel::BF16,
+
+    /// Set the maximum number of auction records kept in history
+    pub fn with_history_capacity(mut self, capacity: usize) -> Self {
+        self.history_capacity = capacity;
+        self
+    }
+
+    /// Replace the pricing formula used by auctions
+    pub fn with_pricing_model(mut self, model: impl PricingModel + 'static) -> Self {
+        self.pricing = Arc::new(model);
+        self
+    }
+
+    /// Accept envelopes that expired up to `secs` seconds ago
+    pub fn with_clock_skew(mut self, secs: u64) -> Self {
+        self.clock_skew_secs = secs;
+        self
+    }
+
+    /// Expiry tolerance applied to incoming envelopes
+    pub fn clock_skew_secs(&self) -> u64 {
+        self.clock_skew_secs
+    }
+
+    /// Bound how long and how many idempotency keys keep their results
+    pub fn with_idempotency_limits(mut self, ttl: Duration, max_keys: usize) -> Self {
+        self.idempotency_ttl = ttl;
+        self.max_idempotency_keys = max_keys;
+        self
+    }
+
+    /// Choose when auctions write their state changes to the database
+    ///
+    /// With `PersistenceMode::Deferred`, run `start_periodic_flush` to bound
+    /// what a crash can lose.
+    pub fn with_persistence_mode(mut self, mode: PersistenceMode) -> Self {
+        self.persistence = mode;
+        self
+    }
+
+    /// Sign a receipt for every auction match with `keypair`
+    pub fn with_receipt_key(mut self, keypair: KeyPair) -> Self {
+        self.receipt_key = Some(Arc::new(keypair));
+        self
+    }
+
+    /// Signed receipt for `auction_match`, or `None` if no receipt key is set
+    pub fn receipt_for(&self, auction_match: &AuctionMatch) -> Result<Option<AuctionReceipt>, GixError> {
+        self.receipt_key
+            .as_ref()
+            .map(|keypair| AuctionReceipt::sign(auction_match.clone(), keypair))
+            .transpose()
+    }
+
+    /// Allow opted-in jobs to fall back along `order` when no provider
+    /// supports their exact precision
+    ///
+    /// A job at `order[i]` may be matched at any later entry, tried in turn.
+    pub fn with_precision_fallback(mut self, order: Vec<PrecisionLevel>) -> Self {
+        self.precision_fallback = order;
+        self
+    }
+
+    /// Set the weight of each new sample in `report_latency` (clamped to 0.0..=1.0)
+    ///
+    /// Higher values track recent measurements more closely; 1.0 replaces the
+    /// estimate with the latest sample.
+    pub fn with_latency_alpha(mut self, alpha: f64) -> Self {
+        self.latency_alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Choose how auctions pick among capable providers
+    pub fn with_auction_mode(mut self, mode: AuctionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Seed the randomness used by `AuctionMode::WeightedRandom`
+    ///
+    /// The same seed and auction sequence always pick the same providers.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(StdRng::seed_from_u64(seed)));
+        self
+    }
+
+    /// Require envelopes to carry a VDF proof of at least `iterations`
+    #[cfg(feature = "vdf")]
+    pub fn with_vdf_min_iterations(mut self, iterations: u64) -> Self {
+        self.vdf_min_iterations = Some(iterations);
+        self
+    }
+
+    /// Whether a VDF difficulty floor is configured
+    ///
+    /// Bare jobs carry no proof, so a node with a floor accepts only envelopes.
+    #[cfg(feature = "vdf")]
+    pub fn requires_vdf(&self) -> bool {
+        self.vdf_min_iterations.is_some()
+    }
+
+    /// Reject envelopes whose VDF proof is missing, invalid, or below the difficulty floor
+    #[cfg(feature = "vdf")]
+    pub fn check_vdf(&self, envelope: &GxfEnvelope) -> Result<(), GixError> {
+        let Some(min_iterations) = self.vdf_min_iterations else {
+            return Ok(());
+        };
+
+        envelope.verify_vdf_proof(min_iterations).map_err(|e| {
+            increment_counter!("gix_auction_vdf_rejected_total");
+            GixError::Protocol(e.to_string())
+        })
+    }
+    
+    /// Load providers from database
+    fn load_providers(db: &sled::Db, tree: &sled::Tree) -> Result<Vec<ComputeProvider>> {
+        let records = decode_tree_with(db, tree, ComputeProvider::from_record)?;
+        
+        // Rewrite only the records stored in an older layout, in the current one
+        let mut outdated = 0;
+        for (provider, version) in &records {
+            if *version != PROVIDER_SCHEMA_VERSION {
+                tree.insert(provider.slp_id.0.as_bytes(), provider.to_record()?)?;
+                outdated += 1;
+            }
+        }
+        if outdated > 0 {
+            info!("Upgraded {} provider record(s) to schema {}", outdated, PROVIDER_SCHEMA_VERSION);
+            tree.flush()?;
+        }
+        let mut providers: Vec<ComputeProvider> = records.into_iter().map(|(provider, _)| provider).collect();
+        
+        // If no providers in DB, initialize with default providers
+        if providers.is_empty() {
+            providers = vec![
+                ComputeProvider {
+                    slp_id: SlpId("slp-us-east-1".to_string()),
+                    supported_precisions: vec![
+                        PrecisionLevel::BF16,
                         PrecisionLevel::FP8,
                         PrecisionLevel::E5M2,
                         PrecisionLevel::INT8,
@@ -188,6 +1297,8 @@ impl AuctionEngine {
                     capacity: 100,
                     utilization: 30,
                     region: "US".to_string(),
+                    max_seq_len: 32768,
+                    status: ProviderStatus::Active,
                 },
                 ComputeProvider {
                     slp_id: SlpId("slp-eu-west-1".to_string()),
@@ -200,13 +1311,15 @@ impl AuctionEngine {
                     capacity: 80,
                     utilization: 20,
                     region: "EU".to_string(),
+                    max_seq_len: 16384,
+                    status: ProviderStatus::Active,
                 },
             ];
             
             // Save default providers to DB
             for provider in &providers {
                 let key = provider.slp_id.0.as_bytes();
-                let value = bincode::serialize(provider)?;
+                let value = provider.to_record()?;
                 tree.insert(key, value)?;
             }
             tree.flush()?;
@@ -216,14 +1329,22 @@ impl AuctionEngine {
     }
     
     /// Load routes from database
-    fn load_routes(tree: &sled::Tree) -> Result<Vec<Route>> {
-        let mut routes = Vec::new();
+    fn load_routes(db: &sled::Db, tree: &sled::Tree) -> Result<Vec<Route>> {
+        let records = decode_tree_with(db, tree, Route::from_record)?;
         
-        for item in tree.iter() {
-            let (_key, value) = item?;
-            let route: Route = bincode::deserialize(&value)?;
-            routes.push(route);
+        // Rewrite only the records stored in an older layout, in the current one
+        let mut outdated = 0;
+        for (route, version) in &records {
+            if *version != ROUTE_SCHEMA_VERSION {
+                tree.insert(route.id.as_bytes(), route.to_record()?)?;
+                outdated += 1;
+            }
+        }
+        if outdated > 0 {
+            info!("Upgraded {} route record(s) to schema {}", outdated, ROUTE_SCHEMA_VERSION);
+            tree.flush()?;
         }
+        let mut routes: Vec<Route> = records.into_iter().map(|(route, _)| route).collect();
         
         // If no routes in DB, initialize with default routes
         if routes.is_empty() {
@@ -232,23 +1353,21 @@ impl AuctionEngine {
                     id: "route-flash-1".to_string(),
                     lane_id: LaneId(0),
                     path: vec!["node-1".to_string(), "node-2".to_string()],
-                    latency_ms: 50,
+                    latency_ms: 50.0,
                     cost: 100,
                 },
                 Route {
                     id: "route-deep-1".to_string(),
                     lane_id: LaneId(1),
                     path: vec!["node-3".to_string(), "node-4".to_string(), "node-5".to_string()],
-                    latency_ms: 150,
+                    latency_ms: 150.0,
                     cost: 80,
                 },
             ];
             
             // Save default routes to DB
             for route in &routes {
-                let key = route.id.as_bytes();
-                let value = bincode::serialize(route)?;
-                tree.insert(key, value)?;
+                tree.insert(route.id.as_bytes(), route.to_record()?)?;
             }
             tree.flush()?;
         }
@@ -258,11 +1377,17 @@ impl AuctionEngine {
     
     /// Load statistics from database
     fn load_stats(tree: &sled::Tree) -> Result<AuctionStats> {
-        if let Some(value) = tree.get("stats")? {
-            let stats: AuctionStats = bincode::deserialize(&value)?;
-            Ok(stats)
-        } else {
-            Ok(AuctionStats::default())
+        let Some(value) = tree.get("stats")? else {
+            return Ok(AuctionStats::default());
+        };
+
+        match AuctionStats::from_record(&value) {
+            Ok(stats) => Ok(stats),
+            Err(e) => {
+                warn!("Unreadable auction stats ({}); starting from zero", e);
+                increment_counter!("gix_db_corrupt_trees_total");
+                Ok(AuctionStats::default())
+            }
         }
     }
     
@@ -273,7 +1398,7 @@ impl AuctionEngine {
         
         for provider in providers.iter() {
             let key = provider.slp_id.0.as_bytes();
-            let value = bincode::serialize(provider)?;
+            let value = provider.to_record()?;
             tree.insert(key, value)?;
         }
         
@@ -286,22 +1411,239 @@ impl AuctionEngine {
         let tree = self.db.open_tree("stats")?;
         let stats = self.stats.read().await;
         
-        let value = bincode::serialize(&*stats)?;
+        let value = stats.to_record()?;
         tree.insert("stats", value)?;
         tree.flush()?;
         
         Ok(())
     }
     
+    /// Save one provider's current state to the database
+    ///
+    /// Auctions and capacity changes touch a single provider, so this avoids
+    /// rewriting the whole set as `save_providers` does.
+    async fn save_provider(&self, slp_id: &SlpId) -> Result<()> {
+        let providers = self.providers.read().await;
+        let provider = providers
+            .iter()
+            .find(|p| &p.slp_id == slp_id)
+            .ok_or_else(|| GixError::NoMatch(format!("Unknown provider: {}", slp_id.0)))?;
+        self.write_provider(provider)?;
+        
+        self.db.open_tree("providers")?.flush()?;
+        Ok(())
+    }
+    
+    /// Write a single provider record without flushing
+    fn write_provider(&self, provider: &ComputeProvider) -> Result<()> {
+        let tree = self.db.open_tree("providers")?;
+        tree.insert(provider.slp_id.0.as_bytes(), provider.to_record()?)?;
+        Ok(())
+    }
+    
+    /// Write a single route to the database
+    fn save_route(&self, route: &Route) -> Result<()> {
+        let tree = self.db.open_tree("routes")?;
+        tree.insert(route.id.as_bytes(), route.to_record()?)?;
+        Ok(())
+    }
+    
+    /// Append an auction record to history, or queue it for the next flush if deferred
+    fn save_record(&self, record: &AuctionRecord) -> Result<()> {
+        // Monotonic IDs keep history ordered by completion, across restarts too
+        let key = self.db.generate_id()?.to_be_bytes();
+        
+        if self.persistence == PersistenceMode::Deferred {
+            let mut pending = self.pending_history.lock().expect("pending history poisoned");
+            pending.push_back((key, record.clone()));
+            if pending.len() > self.history_capacity {
+                pending.pop_front();
+            }
+            self.dirty.store(true, Ordering::Release);
+            return Ok(());
+        }
+        
+        let tree = self.db.open_tree("history")?;
+        self.write_history(&tree, [(key, record.clone())])?;
+        tree.flush()?;
+        Ok(())
+    }
+    
+    /// Insert history records, evicting the oldest beyond capacity
+    fn write_history(
+        &self,
+        tree: &sled::Tree,
+        records: impl IntoIterator<Item = ([u8; 8], AuctionRecord)>,
+    ) -> Result<()> {
+        for (key, record) in records {
+            if tree.insert(key, bincode::serialize(&record)?)?.is_none() {
+                self.history_len.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+        
+        let capacity = self.history_capacity;
+        while self
+            .history_len
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |len| (len > capacity).then(|| len - 1))
+            .is_ok()
+        {
+            if tree.pop_min()?.is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Persist the matched provider and stats after an auction, or mark them dirty if deferred
+    async fn persist_auction_state(&self, matched: Option<&SlpId>) -> Result<(), GixError> {
+        if self.persistence == PersistenceMode::Deferred {
+            self.dirty.store(true, Ordering::Release);
+            return Ok(());
+        }
+        
+        if let Some(slp_id) = matched {
+            self.save_provider(slp_id).await.map_err(|e| GixError::InternalError(format!("Failed to save provider: {}", e)))?;
+        }
+        self.save_stats().await.map_err(|e| GixError::InternalError(format!("Failed to save stats: {}", e)))
+    }
+
+    /// Iterate history records, most recent first, including those awaiting a flush
+    fn history_iter(&self) -> impl Iterator<Item = AuctionRecord> {
+        let pending: Vec<AuctionRecord> = self
+            .pending_history
+            .lock()
+            .expect("pending history poisoned")
+            .iter()
+            .rev()
+            .map(|(_key, record)| record.clone())
+            .collect();
+        let stored = self
+            .db
+            .open_tree("history")
+            .into_iter()
+            .flat_map(|tree| tree.iter().rev())
+            .filter_map(|item| item.ok())
+            .filter_map(|(_key, value)| bincode::deserialize(&value).ok());
+        pending.into_iter().chain(stored).take(self.history_capacity)
+    }
+
+    /// Get up to `limit` most recent auction records, newest first
+    pub fn get_history(&self, limit: usize) -> Vec<AuctionRecord> {
+        self.history_iter().take(limit).collect()
+    }
+
+    /// Get the most recent auction record for a job
+    pub fn get_record(&self, job_id: &JobId) -> Option<AuctionRecord> {
+        self.history_iter().find(|record| &record.job_id == job_id)
+    }
+
+    /// Whether the database was recovered from a previous run
+    pub fn was_recovered(&self) -> bool {
+        self.db.was_recovered()
+    }
+
+    /// Check that the database accepts writes
+    pub async fn health_check(&self) -> Result<()> {
+        self.db.flush_async().await?;
+        Ok(())
+    }
+
     /// Flush all data to disk
+    ///
+    /// Providers and stats are rewritten only when deferred changes are pending.
     pub async fn flush(&self) -> Result<()> {
+        // Changes made while this flush runs leave the engine dirty again
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            self.db.flush_async().await?;
+            return Ok(());
+        }
+        let result = self.write_all().await;
+        if result.is_err() {
+            self.dirty.store(true, Ordering::Release);
+        }
+        result
+    }
+
+    /// Write providers, stats and pending history and flush the database
+    async fn write_all(&self) -> Result<()> {
         self.save_providers().await?;
         self.save_stats().await?;
+        
+        let pending = std::mem::take(&mut *self.pending_history.lock().expect("pending history poisoned"));
+        if let Err(e) = self.write_history(&self.db.open_tree("history")?, pending.iter().cloned()) {
+            // Rewriting the same keys is harmless, so keep all of them for the retry
+            let mut queued = self.pending_history.lock().expect("pending history poisoned");
+            for entry in pending.into_iter().rev() {
+                queued.push_front(entry);
+            }
+            return Err(e);
+        }
+        
         self.db.flush_async().await?;
         Ok(())
     }
 
-    async fn match_job(&self, job: &GxfJob) -> Option<Vec<ComputeProvider>> {
+    /// Whether deferred auction state is waiting for a flush
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Acquire)
+    }
+
+    /// Flush every `interval` in the background, bounding data loss on a crash
+    ///
+    /// Flush errors are logged and retried on the next tick.
+    pub fn start_periodic_flush(&self, interval: Duration) -> FlushHandle {
+        let engine = self.clone();
+        let (stop, mut stopped) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    // Resolves on `stop` and when the handle is dropped
+                    _ = &mut stopped => break,
+                    _ = ticker.tick() => {
+                        if let Err(e) = engine.flush().await {
+                            warn!("Periodic flush failed: {}", e);
+                            increment_counter!("gix_db_flush_errors_total");
+                        }
+                    }
+                }
+            }
+        });
+
+        FlushHandle { stop, task }
+    }
+
+    /// Find capable providers, falling back to a substitute precision if allowed
+    ///
+    /// Returns the job as it will run (with the substituted precision, if any)
+    /// alongside the candidates.
+    async fn match_job(&self, job: &GxfJob) -> Option<(GxfJob, Vec<ComputeProvider>)> {
+        if let Some(matches) = self.match_exact(job).await {
+            return Some((job.clone(), matches));
+        }
+
+        let opted_in = job
+            .parameters
+            .get(ALLOW_PRECISION_FALLBACK_PARAM)
+            .is_some_and(|v| v == "true");
+        if !opted_in {
+            return None;
+        }
+
+        let start = self.precision_fallback.iter().position(|p| *p == job.precision)?;
+        for precision in &self.precision_fallback[start + 1..] {
+            let mut substitute = job.clone();
+            substitute.precision = *precision;
+            if let Some(matches) = self.match_exact(&substitute).await {
+                increment_counter!("gix_auction_precision_fallback_total");
+                return Some((substitute, matches));
+            }
+        }
+        None
+    }
+
+    async fn match_exact(&self, job: &GxfJob) -> Option<Vec<ComputeProvider>> {
         let providers = self.providers.read().await;
         let mut matches = Vec::new();
         for provider in providers.iter() {
@@ -309,7 +1651,9 @@ impl AuctionEngine {
                 matches.push(provider.clone());
             }
         }
-        matches.sort_by_key(|p| p.calculate_price(job));
+        // Providers that cannot be priced sort last; claiming one reports the error.
+        // Equal prices fall back to the SLP ID so the order never depends on the table.
+        matches.sort_by_cached_key(|p| (self.pricing.price(p, job).unwrap_or(Price::MAX), p.slp_id.0.clone()));
         if matches.is_empty() {
             None
         } else {
@@ -317,61 +1661,183 @@ impl AuctionEngine {
         }
     }
 
-    async fn select_route(&self, _job: &GxfJob, _priority: u8) -> Option<Route> {
+    async fn select_route(&self, _job: &GxfJob, priority: JobPriority) -> Option<Route> {
         let routes = self.routes.read().await;
-        let filtered_routes: Vec<&Route> = if _priority >= 128 {
+        let filtered_routes: Vec<&Route> = if priority.is_urgent() {
             routes.iter().filter(|r| r.lane_id == LaneId(0)).collect()
         } else {
             routes.iter().filter(|r| r.lane_id == LaneId(1)).collect()
         };
         if filtered_routes.is_empty() {
-            routes.iter().min_by(|a, b| a.score().partial_cmp(&b.score()).unwrap())
+            routes.iter().min_by(|a, b| a.cmp_score(b))
         } else {
-            filtered_routes
-                .iter()
-                .min_by(|a, b| a.score().partial_cmp(&b.score()).unwrap())
-                .copied()
+            filtered_routes.iter().min_by(|a, b| a.cmp_score(b)).copied()
         }
         .cloned()
     }
 
+    /// Claim one unit of capacity from the cheapest candidate that still has room
+    ///
+    /// The capacity check and utilization increment happen under a single write
+    /// lock, so concurrent auctions cannot oversell a provider. Returns the
+    /// provider's state after the claim and the price quoted before it.
+    async fn claim_capacity(
+        &self,
+        job: &GxfJob,
+        candidates: &[ComputeProvider],
+    ) -> Result<Option<(ComputeProvider, Price)>, GixError> {
+        let mut providers = self.providers.write().await;
+
+        for candidate in candidates {
+            if let Some(p) = providers.iter_mut().find(|p| p.slp_id == candidate.slp_id) {
+                if !p.can_handle(job) {
+                    continue;
+                }
+                let price = self.pricing.price(p, job)?;
+                p.utilization += 1;
+                return Ok(Some((p.clone(), price)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Move the provider picked by the auction mode to the front of `candidates`
+    ///
+    /// `candidates` must be sorted cheapest first; the rest keep their order
+    /// as fallbacks should the pick be full.
+    fn order_candidates(&self, job: &GxfJob, candidates: &mut [ComputeProvider]) {
+        let AuctionMode::WeightedRandom { price_band } = self.mode else {
+            return;
+        };
+
+        let prices: Vec<Price> = candidates
+            .iter()
+            .map_while(|p| self.pricing.price(p, job).ok())
+            .collect();
+        let Some(&cheapest) = prices.first() else {
+            return;
+        };
+        let ceiling = cheapest as f64 * (1.0 + price_band.max(0.0));
+        let weights: Vec<f64> = prices
+            .iter()
+            .take_while(|&&price| price as f64 <= ceiling)
+            .map(|&price| 1.0 / price.max(1) as f64)
+            .collect();
+        if weights.len() < 2 {
+            return;
+        }
+
+        let Ok(distribution) = WeightedIndex::new(&weights) else {
+            return;
+        };
+        let chosen = distribution.sample(&mut *self.rng.lock().expect("auction rng poisoned"));
+        candidates[..=chosen].rotate_right(1);
+    }
+
+    /// Count an auction that found no capable provider
+    async fn record_unmatched(&self) -> Result<(), GixError> {
+        increment_counter!("gix_auctions_total");
+        increment_counter!("gix_auction_unmatched_total");
+
+        {
+            let mut stats = self.stats.write().await;
+            stats.total_auctions += 1;
+            stats.total_unmatched += 1;
+
+            gauge!("gix_total_auctions", stats.total_auctions as f64);
+            gauge!("gix_total_unmatched", stats.total_unmatched as f64);
+        }
+
+        self.persist_auction_state(None).await
+    }
+
+    /// Preview the candidates and route for a job without claiming capacity
+    ///
+    /// Leaves provider utilization and auction stats untouched. Fails if any
+    /// candidate's price cannot be represented.
+    pub async fn quote(&self, job: &GxfJob, priority: u8) -> Result<AuctionQuote, GixError> {
+        let (job, matches) = self.match_job(job).await.unwrap_or_else(|| (job.clone(), Vec::new()));
+        let candidates = matches
+            .into_iter()
+            .map(|p| {
+                let price = self.pricing.price(&p, &job)?;
+                Ok((p.slp_id, price))
+            })
+            .collect::<Result<_, GixError>>()?;
+        let route = self.select_route(&job, JobPriority::from_u8(priority)).await;
+
+        Ok(AuctionQuote { candidates, route })
+    }
+
+    /// Mark a job as in flight, failing if an auction for it is already running
+    fn begin_auction(&self, job_id: JobId) -> Result<InFlightGuard, GixError> {
+        let mut in_flight = self.in_flight.lock().expect("in-flight set poisoned");
+        if !in_flight.insert(job_id) {
+            increment_counter!("gix_auction_duplicate_total");
+            return Err(GixError::Protocol("job already in flight".to_string()));
+        }
+        Ok(InFlightGuard {
+            in_flight: self.in_flight.clone(),
+            job_id,
+        })
+    }
+
+    /// Run an auction for a job
+    ///
+    /// A second auction for a job id whose auction is still running fails
+    /// with `GixError::Protocol` without touching stats or providers.
+    #[tracing::instrument(
+        skip(self, job),
+        fields(job_id = %hex::encode(job.job_id.0), precision = ?job.precision)
+    )]
     pub async fn run_auction(
         &self,
         job: &GxfJob,
         priority: u8,
     ) -> Result<AuctionMatch, GixError> {
-        let matches = self
-            .match_job(job)
-            .await
-            .ok_or_else(|| GixError::InternalError("No matching providers found".to_string()))?;
+        let _in_flight = self.begin_auction(job.job_id)?;
 
-        if matches.is_empty() {
-            return Err(GixError::InternalError("No providers can handle this job".to_string()));
-        }
+        let (job, mut matches) = match self.match_job(job).await {
+            Some(matched) => matched,
+            None => {
+                self.record_unmatched().await?;
+                return Err(GixError::NoMatch("No providers can handle this job".to_string()));
+            }
+        };
+        self.order_candidates(&job, &mut matches);
 
-        let provider = &matches[0];
-        let price = provider.calculate_price(job);
         let route = self
-            .select_route(job, priority)
+            .select_route(&job, JobPriority::from_u8(priority))
             .await
             .ok_or_else(|| GixError::InternalError("No route available".to_string()))?;
 
+        // Another auction may have filled a candidate since matching
+        let (provider, price) = match self.claim_capacity(&job, &matches).await? {
+            Some(claimed) => claimed,
+            None => {
+                self.record_unmatched().await?;
+                return Err(GixError::NoMatch("All matching providers are at capacity".to_string()));
+            }
+        };
+
         // Record metrics
         let slp_id_str = provider.slp_id.0.clone();
-        let precision_str = format!("{:?}", job.precision);
         
         increment_counter!("gix_auctions_total");
         increment_counter!("gix_auction_matches_total", "slp" => slp_id_str.clone());
         gauge!("gix_clearing_price", price as f64, "slp" => slp_id_str.clone());
         increment_gauge!("gix_auction_volume_total", price as f64);
-        increment_counter!("gix_matches_by_precision", "precision" => precision_str);
+        increment_counter!("gix_matches_by_precision", "precision" => job.precision.metric_label());
+        gauge!("gix_provider_utilization", provider.utilization as f64, "slp" => slp_id_str);
 
         // Update stats
         {
             let mut stats = self.stats.write().await;
             stats.total_auctions += 1;
             stats.total_matches += 1;
-            stats.total_volume += price;
+            stats.total_volume = stats.total_volume.saturating_add(price);
+            stats.record_price(price);
             *stats.matches_by_precision.entry(job.precision).or_insert(0) += 1;
             *stats.matches_by_lane.entry(route.lane_id.clone()).or_insert(0) += 1;
             
@@ -379,22 +1845,28 @@ impl AuctionEngine {
             gauge!("gix_total_auctions", stats.total_auctions as f64);
             gauge!("gix_total_matches", stats.total_matches as f64);
             gauge!("gix_total_volume", stats.total_volume as f64);
-        }
-
-        // Update provider utilization
-        {
-            let mut providers = self.providers.write().await;
-            if let Some(p) = providers.iter_mut().find(|p| p.slp_id == provider.slp_id) {
-                p.utilization += 1;
-                
-                // Update utilization gauge
-                gauge!("gix_provider_utilization", p.utilization as f64, "slp" => slp_id_str);
-            }
+            gauge!("gix_min_clearing_price", stats.min_price as f64);
+            gauge!("gix_max_clearing_price", stats.max_price as f64);
+            gauge!("gix_avg_clearing_price", stats.avg_price);
         }
 
         // Persist changes to database
-        self.save_providers().await.map_err(|e| GixError::InternalError(format!("Failed to save providers: {}", e)))?;
-        self.save_stats().await.map_err(|e| GixError::InternalError(format!("Failed to save stats: {}", e)))?;
+        self.persist_auction_state(Some(&provider.slp_id)).await?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let record = AuctionRecord {
+            job_id: job.job_id,
+            slp_id: provider.slp_id.clone(),
+            price,
+            lane_id: route.lane_id.clone(),
+            timestamp,
+            candidate_count: matches.len() as u32,
+        };
+        self.save_record(&record).map_err(|e| GixError::InternalError(format!("Failed to save auction record: {}", e)))?;
+        debug!(slp = %provider.slp_id.0, price, "Auction cleared");
 
         Ok(AuctionMatch {
             job_id: job.job_id,
@@ -402,32 +1874,1300 @@ impl AuctionEngine {
             lane_id: route.lane_id.clone(),
             price,
             route: route.path,
+            precision_used: job.precision,
+            provider_region: provider.region.clone(),
         })
     }
 
-    /// Get auction statistics
-    pub async fn get_stats(&self) -> AuctionStats {
-        self.stats.read().await.clone()
+    /// Run an auction unless `idempotency_key` already ran one recently
+    ///
+    /// A retry with the same key returns the original match without running
+    /// a second auction. Reusing a key for a different job fails with
+    /// `GixError::Protocol`. Without a key this is `run_auction`.
+    pub async fn run_idempotent_auction(
+        &self,
+        job: &GxfJob,
+        priority: u8,
+        idempotency_key: Option<&str>,
+    ) -> Result<AuctionMatch, GixError> {
+        let key = match idempotency_key {
+            Some(key) if self.max_idempotency_keys > 0 => key,
+            _ => return self.run_auction(job, priority).await,
+        };
+
+        if let Some(cached) = self.cached_match(key) {
+            if cached.job_id != job.job_id {
+                return Err(GixError::Protocol(format!(
+                    "Idempotency key '{}' was used for a different job",
+                    key
+                )));
+            }
+            increment_counter!("gix_auction_idempotent_replays_total");
+            debug!(key, "Returning cached auction result");
+            return Ok(cached);
+        }
+
+        let auction_match = self.run_auction(job, priority).await?;
+        self.remember_match(key, &auction_match);
+        Ok(auction_match)
     }
-}
 
-/// Process a GXF envelope through the auction
-pub async fn process_envelope(
-    engine: &AuctionEngine,
-    envelope: GxfEnvelope,
-) -> Result<AuctionMatch> {
-    envelope.validate().map_err(|e| anyhow::anyhow!("Envelope validation failed: {}", e))?;
-    if envelope.meta.is_expired() {
-        return Err(anyhow::anyhow!("Envelope expired"));
+    /// Unexpired result recorded for an idempotency key
+    fn cached_match(&self, key: &str) -> Option<AuctionMatch> {
+        let cache = self.idempotency.lock().expect("idempotency cache poisoned");
+        cache
+            .get(key)
+            .filter(|(_, ran_at)| ran_at.elapsed() < self.idempotency_ttl)
+            .map(|(auction_match, _)| auction_match.clone())
     }
-    let job = envelope
-        .deserialize_job()
-        .map_err(|e| anyhow::anyhow!("Failed to deserialize job: {}", e))?;
-    job.validate()
-        .map_err(|e| anyhow::anyhow!("Job validation failed: {}", e))?;
 
-    engine
-        .run_auction(&job, envelope.meta.priority)
-        .await
-        .map_err(|e| anyhow::anyhow!("Auction failed: {}", e))
+    /// Record a result, evicting expired keys and then the oldest when full
+    fn remember_match(&self, key: &str, auction_match: &AuctionMatch) {
+        let mut cache = self.idempotency.lock().expect("idempotency cache poisoned");
+        if !cache.contains_key(key) && cache.len() >= self.max_idempotency_keys {
+            let ttl = self.idempotency_ttl;
+            cache.retain(|_, (_, ran_at)| ran_at.elapsed() < ttl);
+            if cache.len() >= self.max_idempotency_keys {
+                let oldest = cache
+                    .iter()
+                    .min_by_key(|(_, (_, ran_at))| *ran_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    cache.remove(&oldest);
+                }
+            }
+        }
+        cache.insert(key.to_string(), (auction_match.clone(), Instant::now()));
+    }
+
+    /// Run an auction for each job and commit to the results with a Merkle root
+    ///
+    /// Jobs that fail to clear are listed in `unmatched` and left out of the
+    /// tree. Fails with `GixError::NoMatch` if no job clears. Any other error
+    /// aborts the batch and releases the capacity its earlier matches claimed,
+    /// since the caller never learns of them.
+    pub async fn run_batch_auction(&self, jobs: &[GxfJob], priority: u8) -> Result<BatchReceipt, GixError> {
+        let mut matches = Vec::new();
+        let mut unmatched = Vec::new();
+        for job in jobs {
+            match self.run_auction(job, priority).await {
+                Ok(matched) => matches.push(matched),
+                Err(GixError::NoMatch(reason)) => {
+                    debug!(job_id = %hex::encode(job.job_id.0), %reason, "Batch job did not clear");
+                    unmatched.push(job.job_id);
+                }
+                Err(e) => {
+                    self.release_batch(&matches).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        let tree = MerkleTree::new(matches.iter().map(AuctionMatch::hash).collect())
+            .map_err(|_| GixError::NoMatch("No job in the batch cleared".to_string()))?;
+        let proofs = (0..matches.len())
+            .map(|index| tree.proof(index).expect("index within batch"))
+            .collect();
+
+        Ok(BatchReceipt {
+            root: tree.root(),
+            matches,
+            proofs,
+            unmatched,
+        })
+    }
+
+    /// Release the capacity claimed by the matches of an aborted batch
+    async fn release_batch(&self, matches: &[AuctionMatch]) {
+        for matched in matches {
+            if let Err(e) = self.release_capacity(&matched.slp_id).await {
+                warn!(slp = %matched.slp_id.0, "Failed to release capacity of aborted batch: {}", e);
+            }
+        }
+    }
+
+    /// Run an auction, waiting up to `max_wait` for capacity if every capable provider is full
+    ///
+    /// Jobs no provider could ever handle fail immediately. Otherwise the
+    /// auction retries whenever `release_capacity` frees a slot, and reports
+    /// `NoMatch` once `max_wait` has elapsed.
+    pub async fn run_auction_waiting(
+        &self,
+        job: &GxfJob,
+        priority: u8,
+        max_wait: Duration,
+    ) -> Result<AuctionMatch, GixError> {
+        let deadline = tokio::time::Instant::now() + max_wait;
+        loop {
+            // Register before checking so a release in between is not missed
+            let freed = self.capacity_freed.notified();
+            tokio::pin!(freed);
+            freed.as_mut().enable();
+
+            if !self.waiting_for_capacity(job).await {
+                break;
+            }
+            debug!(job_id = %hex::encode(job.job_id.0), "All capable providers busy, waiting for capacity");
+            if tokio::time::timeout_at(deadline, freed).await.is_err() {
+                increment_counter!("gix_auction_capacity_wait_timeout_total");
+                break;
+            }
+        }
+        self.run_auction(job, priority).await
+    }
+
+    /// Whether the job only fails to match because capable providers are full
+    async fn waiting_for_capacity(&self, job: &GxfJob) -> bool {
+        if self.match_job(job).await.is_some() {
+            return false;
+        }
+        let providers = self.providers.read().await;
+        providers.iter().any(|provider| {
+            let idle = ComputeProvider { utilization: 0, ..provider.clone() };
+            idle.can_handle(job)
+        })
+    }
+
+    /// Release one unit of a provider's capacity once its job has finished
+    pub async fn release_capacity(&self, slp_id: &SlpId) -> Result<()> {
+        {
+            let mut providers = self.providers.write().await;
+            let provider = providers
+                .iter_mut()
+                .find(|p| &p.slp_id == slp_id)
+                .ok_or_else(|| GixError::NoMatch(format!("Unknown provider: {}", slp_id.0)))?;
+            provider.utilization = provider.utilization.saturating_sub(1);
+
+            gauge!("gix_provider_utilization", provider.utilization as f64, "slp" => slp_id.0.clone());
+        }
+        self.capacity_freed.notify_waiters();
+
+        self.save_provider(slp_id).await
+    }
+
+    /// Resize a provider's capacity, e.g. from an external autoscaler
+    ///
+    /// Shrinking below the current utilization clamps utilization to the new
+    /// capacity; growing wakes auctions waiting for capacity.
+    pub async fn set_capacity(&self, slp_id: &SlpId, capacity: u32) -> Result<()> {
+        {
+            let mut providers = self.providers.write().await;
+            let provider = providers
+                .iter_mut()
+                .find(|p| &p.slp_id == slp_id)
+                .ok_or_else(|| GixError::NoMatch(format!("Unknown provider: {}", slp_id.0)))?;
+            provider.capacity = capacity;
+            provider.utilization = provider.utilization.min(capacity);
+
+            gauge!("gix_provider_capacity", capacity as f64, "slp" => slp_id.0.clone());
+            gauge!("gix_provider_utilization", provider.utilization as f64, "slp" => slp_id.0.clone());
+        }
+        self.capacity_freed.notify_waiters();
+
+        self.save_provider(slp_id).await
+    }
+
+    /// Take a provider in or out of service without removing it
+    ///
+    /// Only `Active` providers are matched by auctions. Others keep their
+    /// stats and the jobs they hold, whose capacity `release_capacity` still
+    /// returns.
+    pub async fn set_provider_status(&self, slp_id: &SlpId, status: ProviderStatus) -> Result<()> {
+        {
+            let mut providers = self.providers.write().await;
+            let provider = providers
+                .iter_mut()
+                .find(|p| &p.slp_id == slp_id)
+                .ok_or_else(|| GixError::NoMatch(format!("Unknown provider: {}", slp_id.0)))?;
+            provider.status = status;
+        }
+        info!(slp = %slp_id.0, ?status, "Provider status changed");
+        if status == ProviderStatus::Active {
+            // A returning provider may admit auctions waiting for capacity
+            self.capacity_freed.notify_waiters();
+        }
+
+        self.save_provider(slp_id).await
+    }
+
+    /// Get the current provider set
+    pub async fn get_providers(&self) -> Vec<ComputeProvider> {
+        self.providers.read().await.clone()
+    }
+
+    /// Get the current state of one provider
+    pub async fn get_provider(&self, slp_id: &SlpId) -> Option<ComputeProvider> {
+        self.providers.read().await.iter().find(|p| &p.slp_id == slp_id).cloned()
+    }
+
+    /// Current `(utilization, capacity)` of one provider
+    pub async fn utilization(&self, slp_id: &SlpId) -> Option<(u32, u32)> {
+        self.providers
+            .read()
+            .await
+            .iter()
+            .find(|p| &p.slp_id == slp_id)
+            .map(|p| (p.utilization, p.capacity))
+    }
+
+    /// Add a provider, replacing any existing provider with the same SLP ID
+    pub async fn add_provider(&self, provider: ComputeProvider) -> Result<()> {
+        provider.validate()?;
+        {
+            let mut providers = self.providers.write().await;
+            self.write_provider(&provider)?;
+            gauge!("gix_provider_capacity", provider.capacity as f64, "slp" => provider.slp_id.0.clone());
+            gauge!("gix_provider_utilization", provider.utilization as f64, "slp" => provider.slp_id.0.clone());
+            match providers.iter_mut().find(|p| p.slp_id == provider.slp_id) {
+                Some(existing) => *existing = provider,
+                None => providers.push(provider),
+            }
+        }
+        // New capacity may admit auctions waiting for it
+        self.capacity_freed.notify_waiters();
+
+        self.db.flush_async().await?;
+        Ok(())
+    }
+
+    /// Get the current route table
+    pub async fn get_routes(&self) -> Vec<Route> {
+        self.routes.read().await.clone()
+    }
+
+    /// Add a route, replacing any existing route with the same ID
+    pub async fn add_route(&self, route: Route) -> Result<()> {
+        {
+            let mut routes = self.routes.write().await;
+            self.save_route(&route)?;
+            match routes.iter_mut().find(|r| r.id == route.id) {
+                Some(existing) => *existing = route,
+                None => routes.push(route),
+            }
+        }
+
+        self.db.flush_async().await?;
+        Ok(())
+    }
+
+    /// Remove a route by ID
+    pub async fn remove_route(&self, id: &str) -> Result<Route> {
+        let route = {
+            let mut routes = self.routes.write().await;
+            let index = routes
+                .iter()
+                .position(|r| r.id == id)
+                .ok_or_else(|| GixError::NoMatch(format!("Unknown route: {}", id)))?;
+            self.db.open_tree("routes")?.remove(id.as_bytes())?;
+            routes.remove(index)
+        };
+
+        self.db.flush_async().await?;
+        Ok(route)
+    }
+
+    /// Record a measured latency for a route, affecting future route selection
+    pub async fn update_route_latency(&self, id: &str, latency_ms: u64) -> Result<()> {
+        {
+            let mut routes = self.routes.write().await;
+            let route = routes
+                .iter_mut()
+                .find(|r| r.id == id)
+                .ok_or_else(|| GixError::NoMatch(format!("Unknown route: {}", id)))?;
+            route.latency_ms = latency_ms as f64;
+            self.save_route(route)?;
+
+            gauge!("gix_route_latency_ms", route.latency_ms, "route" => id.to_string());
+        }
+
+        self.db.flush_async().await?;
+        Ok(())
+    }
+
+    /// Fold an observed end-to-end latency into a route's estimate
+    ///
+    /// The estimate is an exponential moving average weighted by the latency
+    /// alpha, so consistently slow routes drift towards their measured
+    /// latency and lose out in route selection. Returns the new estimate.
+    ///
+    /// Reports arrive once per job, so the route is written without a flush;
+    /// the next periodic or shutdown flush makes it durable.
+    pub async fn report_latency(&self, id: &str, observed_ms: u64) -> Result<f64> {
+        let mut routes = self.routes.write().await;
+        let route = routes
+            .iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| GixError::NoMatch(format!("Unknown route: {}", id)))?;
+        route.latency_ms = self.latency_alpha * observed_ms as f64
+            + (1.0 - self.latency_alpha) * route.latency_ms;
+        self.save_route(route)?;
+
+        gauge!("gix_route_latency_ms", route.latency_ms, "route" => id.to_string());
+        Ok(route.latency_ms)
+    }
+
+    /// Get auction statistics
+    pub async fn get_stats(&self) -> AuctionStats {
+        self.stats.read().await.clone()
+    }
+
+    /// Snapshot the current auction statistics
+    pub async fn snapshot_stats(&self) -> AuctionStats {
+        self.get_stats().await
+    }
+
+    /// Zero the auction statistics and persist them, returning their values before the reset
+    pub async fn reset_stats(&self) -> Result<AuctionStats> {
+        let snapshot = std::mem::take(&mut *self.stats.write().await);
+        self.save_stats().await?;
+        Ok(snapshot)
+    }
+
+    /// Export providers, routes, stats and history as a single snapshot
+    ///
+    /// The result can be written to a file and loaded into another engine
+    /// with [`import_snapshot`](Self::import_snapshot). Unreadable history
+    /// records are left out and counted in `gix_snapshot_skipped_history_total`.
+    pub async fn export_snapshot(&self) -> Result<Vec<u8>> {
+        let providers = self.providers.read().await;
+        let routes = self.routes.read().await;
+        let stats = self.stats.read().await;
+
+        let mut history = Vec::new();
+        let mut skipped = 0u64;
+        for item in self.db.open_tree("history")?.iter() {
+            let (_key, value) = item?;
+            match bincode::deserialize(&value) {
+                Ok(record) => history.push(record),
+                Err(_) => skipped += 1,
+            }
+        }
+        history.extend(
+            self.pending_history
+                .lock()
+                .expect("pending history poisoned")
+                .iter()
+                .map(|(_key, record)| record.clone()),
+        );
+        if skipped > 0 {
+            warn!(skipped, "Left unreadable history records out of the snapshot");
+            counter!("gix_snapshot_skipped_history_total", skipped);
+        }
+
+        EngineSnapshot {
+            providers: providers.clone(),
+            routes: routes.clone(),
+            stats: stats.clone(),
+            history,
+        }
+        .to_bytes()
+    }
+
+    /// Replace the engine's providers, routes, stats and history with a snapshot
+    ///
+    /// The snapshot is decoded and validated before anything is written, and
+    /// the trees are replaced in a single transaction, so a corrupt snapshot
+    /// leaves the current state untouched.
+    pub async fn import_snapshot(&self, bytes: &[u8]) -> Result<()> {
+        let snapshot = EngineSnapshot::from_bytes(bytes)?;
+
+        let mut providers = self.providers.write().await;
+        let mut routes = self.routes.write().await;
+        let mut stats = self.stats.write().await;
+
+        let providers_tree = self.db.open_tree("providers")?;
+        let routes_tree = self.db.open_tree("routes")?;
+        let stats_tree = self.db.open_tree("stats")?;
+        let history_tree = self.db.open_tree("history")?;
+
+        let mut provider_records = Vec::with_capacity(snapshot.providers.len());
+        for provider in &snapshot.providers {
+            provider_records.push((provider.slp_id.0.as_bytes().to_vec(), provider.to_record()?));
+        }
+        let mut route_records = Vec::with_capacity(snapshot.routes.len());
+        for route in &snapshot.routes {
+            route_records.push((route.id.as_bytes().to_vec(), route.to_record()?));
+        }
+        let stats_record = snapshot.stats.to_record()?;
+        // Keep only the newest records the history capacity allows
+        let skip = snapshot.history.len().saturating_sub(self.history_capacity);
+        let mut history_records = Vec::new();
+        for record in &snapshot.history[skip..] {
+            history_records.push((self.db.generate_id()?.to_be_bytes(), bincode::serialize(record)?));
+        }
+
+        let stale_keys = |tree: &sled::Tree| -> Result<Vec<sled::IVec>> {
+            tree.iter().keys().map(|key| Ok(key?)).collect()
+        };
+        let stale_providers = stale_keys(&providers_tree)?;
+        let stale_routes = stale_keys(&routes_tree)?;
+        let stale_history = stale_keys(&history_tree)?;
+
+        let result: sled::transaction::TransactionResult<(), sled::Error> =
+            (&providers_tree, &routes_tree, &stats_tree, &history_tree).transaction(
+                |(providers_tx, routes_tx, stats_tx, history_tx)| {
+                    for key in &stale_providers {
+                        providers_tx.remove(key)?;
+                    }
+                    for (key, value) in &provider_records {
+                        providers_tx.insert(key.as_slice(), value.as_slice())?;
+                    }
+                    for key in &stale_routes {
+                        routes_tx.remove(key)?;
+                    }
+                    for (key, value) in &route_records {
+                        routes_tx.insert(key.as_slice(), value.as_slice())?;
+                    }
+                    stats_tx.insert("stats", stats_record.as_slice())?;
+                    for key in &stale_history {
+                        history_tx.remove(key)?;
+                    }
+                    for (key, value) in &history_records {
+                        history_tx.insert(&key[..], value.as_slice())?;
+                    }
+                    Ok(())
+                },
+            );
+        result?;
+
+        *providers = snapshot.providers;
+        *routes = snapshot.routes;
+        *stats = snapshot.stats;
+        self.history_len.store(history_records.len(), Ordering::Release);
+        self.pending_history.lock().expect("pending history poisoned").clear();
+        // Cached matches refer to the state being replaced
+        self.idempotency.lock().expect("idempotency cache poisoned").clear();
+        self.dirty.store(false, Ordering::Release);
+        drop((providers, routes, stats));
+
+        info!("Imported engine snapshot");
+        self.capacity_freed.notify_waiters();
+        self.db.flush_async().await?;
+        Ok(())
+    }
+}
+
+/// Process a GXF envelope through the auction
+///
+/// Errors keep the underlying `GxfError` or `GixError` in their source chain,
+/// so `chain_to_status` still maps them to the right gRPC status.
+pub async fn process_envelope(
+    engine: &AuctionEngine,
+    envelope: GxfEnvelope,
+) -> Result<AuctionMatch> {
+    envelope
+        .validate_with_skew(engine.clock_skew_secs)
+        .context("Envelope validation failed")?;
+    envelope
+        .meta
+        .check_expiry_with_skew(engine.clock_skew_secs)
+        .with_context(|| format!("Envelope expired ({}s old)", envelope.age_secs()))?;
+    #[cfg(feature = "vdf")]
+    engine.check_vdf(&envelope)?;
+    let job = envelope
+        .deserialize_job()
+        .context("Failed to deserialize job")?;
+    job.validate()
+        .context("Job validation failed")?;
+
+    engine
+        .run_idempotent_auction(&job, envelope.meta.priority, envelope.meta.idempotency_key.as_deref())
+        .await
+        .context("Auction failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gix_proto::errors::chain_to_status;
+    use tonic::Code;
+
+    fn envelope(i: u8) -> GxfEnvelope {
+        let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::BF16, 1024);
+        GxfEnvelope::from_job(job, 128).unwrap()
+    }
+
+    /// Engine over a temporary database holding only `providers`
+    fn engine_with_providers(providers: &[ComputeProvider]) -> Result<AuctionEngine> {
+        let db = sled::Config::new().temporary(true).open()?;
+        let tree = db.open_tree("providers")?;
+        for provider in providers {
+            tree.insert(provider.slp_id.0.as_bytes(), provider.to_record()?)?;
+        }
+        AuctionEngine::from_db(db)
+    }
+
+    #[tokio::test]
+    async fn test_process_envelope_errors_keep_their_status() {
+        let engine = AuctionEngine::temporary().unwrap();
+
+        let mut expired = envelope(1);
+        expired.meta.created_at = 1;
+        expired.meta.expires_at = Some(2);
+        let err = process_envelope(&engine, expired).await.unwrap_err();
+        assert_eq!(chain_to_status(err.as_ref()).code(), Code::DeadlineExceeded, "{:#}", err);
+
+        let mut invalid = envelope(2);
+        invalid.payload.clear();
+        let err = process_envelope(&engine, invalid).await.unwrap_err();
+        assert_eq!(chain_to_status(err.as_ref()).code(), Code::InvalidArgument, "{:#}", err);
+
+        let mut unmatched = envelope(3);
+        unmatched.meta.priority = 0;
+        let job = GxfJob::new(JobId([3; 16]), PrecisionLevel::BF16, u32::MAX);
+        unmatched.payload = serde_json::to_vec(&job).unwrap();
+        let err = process_envelope(&engine, unmatched).await.unwrap_err();
+        assert_eq!(chain_to_status(err.as_ref()).code(), Code::NotFound, "{:#}", err);
+    }
+
+    #[tokio::test]
+    async fn test_auction_history_records() -> Result<()> {
+        let engine = AuctionEngine::temporary()?;
+
+        let job1 = GxfJob::new(JobId([10; 16]), PrecisionLevel::BF16, 1024);
+        let job2 = GxfJob::new(JobId([20; 16]), PrecisionLevel::INT8, 256);
+        let match1 = engine.run_auction(&job1, 200).await?;
+        let match2 = engine.run_auction(&job2, 50).await?;
+
+        // History is returned newest first
+        let history = engine.get_history(10);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].job_id, job2.job_id);
+        assert_eq!(history[1].job_id, job1.job_id);
+
+        let record1 = engine.get_record(&job1.job_id).expect("record for job1");
+        assert_eq!(record1.price, match1.price);
+        assert_eq!(record1.slp_id, match1.slp_id);
+        assert_eq!(record1.lane_id, match1.lane_id);
+        assert_eq!(record1.candidate_count, 2);
+
+        let record2 = engine.get_record(&job2.job_id).expect("record for job2");
+        assert_eq!(record2.price, match2.price);
+
+        assert!(engine.get_record(&JobId([30; 16])).is_none());
+        assert_eq!(engine.get_history(1).len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auction_history_capacity() -> Result<()> {
+        let engine = AuctionEngine::temporary()?.with_history_capacity(3);
+
+        for i in 0..5 {
+            let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 128);
+            engine.run_auction(&job, 50).await?;
+        }
+
+        // Only the three most recent auctions are kept
+        let history = engine.get_history(10);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].job_id, JobId([4; 16]));
+        assert_eq!(history[2].job_id, JobId([2; 16]));
+        assert!(engine.get_record(&JobId([0; 16])).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_auction() -> Result<()> {
+        // Only an INT8 provider exists, so a BF16 job cannot match
+        let providers = [ComputeProvider {
+            slp_id: SlpId("slp-int8-only".to_string()),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: 1000,
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        }];
+
+        let engine = engine_with_providers(&providers)?;
+
+        let job = GxfJob::new(JobId([7; 16]), PrecisionLevel::BF16, 1024);
+        let result = engine.run_auction(&job, 200).await;
+        assert!(matches!(result, Err(GixError::NoMatch(_))));
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.total_unmatched, 1);
+        assert_eq!(stats.total_matches, 0);
+        assert!(engine.get_record(&job.job_id).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_auctions_do_not_oversell() -> Result<()> {
+        let providers = [("slp-small", 2), ("slp-medium", 3)].map(|(id, capacity)| ComputeProvider {
+            slp_id: SlpId(id.to_string()),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: 1000,
+            capacity,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        });
+
+        let engine = engine_with_providers(&providers)?;
+
+        // Twelve simultaneous auctions against five units of total capacity
+        let handles: Vec<_> = (0..12u8)
+            .map(|i| {
+                let engine = engine.clone();
+                tokio::spawn(async move {
+                    let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 128);
+                    engine.run_auction(&job, 50).await
+                })
+            })
+            .collect();
+
+        let mut per_provider = std::collections::HashMap::new();
+        let mut unmatched = 0;
+        for handle in handles {
+            match handle.await? {
+                Ok(result) => *per_provider.entry(result.slp_id.0).or_insert(0u32) += 1,
+                Err(GixError::NoMatch(_)) => unmatched += 1,
+                Err(e) => panic!("unexpected auction error: {}", e),
+            }
+        }
+
+        for provider in &providers {
+            let claimed = per_provider.get(&provider.slp_id.0).copied().unwrap_or(0);
+            assert!(claimed <= provider.capacity, "{} oversold: {} > {}", provider.slp_id.0, claimed, provider.capacity);
+        }
+        assert_eq!(per_provider.values().sum::<u32>(), 5);
+        assert_eq!(unmatched, 7);
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.total_matches, 5);
+        assert_eq!(stats.total_unmatched, 7);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_release_capacity_frees_provider() -> Result<()> {
+        let slp_id = SlpId("slp-single".to_string());
+        let providers = [ComputeProvider {
+            slp_id: slp_id.clone(),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: 1000,
+            capacity: 1,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        }];
+
+        let engine = engine_with_providers(&providers)?;
+        let job = |i: u8| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 128);
+
+        // Fill the provider
+        engine.run_auction(&job(1), 50).await?;
+        assert!(matches!(engine.run_auction(&job(2), 50).await, Err(GixError::NoMatch(_))));
+
+        // Releasing frees the slot for the next job
+        engine.release_capacity(&slp_id).await?;
+        let result = engine.run_auction(&job(3), 50).await?;
+        assert_eq!(result.slp_id, slp_id);
+
+        // Utilization saturates at zero
+        engine.release_capacity(&slp_id).await?;
+        engine.release_capacity(&slp_id).await?;
+        engine.run_auction(&job(4), 50).await?;
+        assert!(matches!(engine.run_auction(&job(5), 50).await, Err(GixError::NoMatch(_))));
+
+        assert!(engine.release_capacity(&SlpId("slp-unknown".to_string())).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_provider_reflects_auction() -> Result<()> {
+        let slp_id = SlpId("slp-single".to_string());
+        let providers = [ComputeProvider {
+            slp_id: slp_id.clone(),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: 1000,
+            capacity: 4,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        }];
+
+        let engine = engine_with_providers(&providers)?;
+        assert_eq!(engine.utilization(&slp_id).await, Some((0, 4)));
+
+        let job = GxfJob::new(JobId([1; 16]), PrecisionLevel::INT8, 128);
+        let result = engine.run_auction(&job, 50).await?;
+        assert_eq!(result.slp_id, slp_id);
+
+        let provider = engine.get_provider(&slp_id).await.expect("provider exists");
+        assert_eq!(provider.utilization, 1);
+        assert_eq!(provider.capacity, 4);
+        assert_eq!(engine.utilization(&slp_id).await, Some((1, 4)));
+
+        let unknown = SlpId("slp-unknown".to_string());
+        assert!(engine.get_provider(&unknown).await.is_none());
+        assert!(engine.utilization(&unknown).await.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_returns_original_match() -> Result<()> {
+        let engine = AuctionEngine::temporary()?;
+        let envelope = |i: u8, key: Option<&str>| -> Result<GxfEnvelope> {
+            let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::BF16, 1024);
+            let mut envelope = GxfEnvelope::from_job(job, 128)?;
+            envelope.meta.idempotency_key = key.map(str::to_string);
+            Ok(envelope)
+        };
+
+        let first = process_envelope(&engine, envelope(1, Some("submit-1"))?).await?;
+        let retry = process_envelope(&engine, envelope(1, Some("submit-1"))?).await?;
+        assert_eq!(first, retry);
+        assert_eq!(engine.get_stats().await.total_auctions, 1);
+
+        // A key belongs to the job it was first used with
+        assert!(process_envelope(&engine, envelope(2, Some("submit-1"))?).await.is_err());
+        assert_eq!(engine.get_stats().await.total_auctions, 1);
+
+        // Without a key every submission runs an auction
+        process_envelope(&engine, envelope(3, None)?).await?;
+        assert_eq!(engine.get_stats().await.total_auctions, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_clearing_price_stats() -> Result<()> {
+        // Large capacity keeps the utilization surcharge below one micro-token
+        let providers = [ComputeProvider {
+            slp_id: SlpId("slp-flat".to_string()),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: 1000,
+            capacity: 1_000_000,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        }];
+
+        let engine = engine_with_providers(&providers)?;
+
+        let stats = engine.get_stats().await;
+        assert_eq!((stats.min_price, stats.max_price, stats.avg_price), (0, 0, 0.0));
+
+        // Prices are base + seq_len * 10: 4000, 2000, 3000
+        for (i, seq_len) in [300, 100, 200].into_iter().enumerate() {
+            let job = GxfJob::new(JobId([i as u8; 16]), PrecisionLevel::INT8, seq_len);
+            let result = engine.run_auction(&job, 50).await?;
+            assert_eq!(result.price, 1000 + seq_len as u64 * 10);
+        }
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.min_price, 2000);
+        assert_eq!(stats.max_price, 4000);
+        assert!((stats.avg_price - 3000.0).abs() < f64::EPSILON);
+
+        // Unmatched auctions leave the price statistics alone
+        let job = GxfJob::new(JobId([9; 16]), PrecisionLevel::BF16, 100);
+        assert!(engine.run_auction(&job, 50).await.is_err());
+        assert!((engine.get_stats().await.avg_price - 3000.0).abs() < f64::EPSILON);
+
+        Ok(())
+    }
+
+    /// Charges the same price for every job
+    struct FlatRatePricing(u64);
+
+    impl PricingModel for FlatRatePricing {
+        fn price(&self, _provider: &ComputeProvider, _job: &GxfJob) -> Result<u64, GixError> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_pricing_model() -> Result<()> {
+        let engine = AuctionEngine::temporary()?.with_pricing_model(FlatRatePricing(777));
+
+        for (i, precision) in [PrecisionLevel::INT8, PrecisionLevel::BF16].into_iter().enumerate() {
+            let job = GxfJob::new(JobId([i as u8; 16]), precision, 4096);
+            let result = engine.run_auction(&job, 50).await?;
+            assert_eq!(result.price, 777);
+        }
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.total_volume, 777 * 2);
+
+        // The default model keeps the built-in formula
+        let provider = ComputeProvider {
+            slp_id: SlpId("slp-default".to_string()),
+            supported_precisions: vec![PrecisionLevel::FP8],
+            base_price: 1000,
+            capacity: 10,
+            utilization: 5,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        };
+        let job = GxfJob::new(JobId([7; 16]), PrecisionLevel::FP8, 100);
+        // (1000 + 100 * 10) * 1.5 * 1.25
+        assert_eq!(DefaultPricingModel::default().price(&provider, &job)?, 3750);
+        assert_eq!(provider.calculate_price(&job)?, 3750);
+
+        // Client-side estimates match an idle provider's quote
+        for precision in [PrecisionLevel::INT8, PrecisionLevel::E5M2, PrecisionLevel::FP8, PrecisionLevel::BF16] {
+            let idle = ComputeProvider { utilization: 0, supported_precisions: vec![precision], ..provider.clone() };
+            let job = GxfJob::new(JobId([8; 16]), precision, 100);
+            assert_eq!(job.estimated_cost(idle.base_price), idle.calculate_price(&job)?);
+        }
+
+        let tuned = DefaultPricingModel { seq_len_rate: 0, utilization_weight: 0.0, ..Default::default() };
+        assert_eq!(tuned.price(&provider, &job)?, 1500);
+
+        // A misconfigured negative multiplier is rejected rather than clamped to zero
+        let negative = DefaultPricingModel { fp8_multiplier: -1.0, ..Default::default() };
+        assert!(matches!(negative.price(&provider, &job), Err(GixError::Protocol(_))));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "vdf")]
+    #[tokio::test]
+    async fn test_vdf_gate_on_envelopes() -> Result<()> {
+        let engine = AuctionEngine::temporary()?.with_vdf_min_iterations(100);
+        let job = GxfJob::new(JobId([1; 16]), PrecisionLevel::INT8, 256);
+
+        // Missing proof
+        let envelope = GxfEnvelope::from_job(job.clone(), 64)?;
+        assert!(process_envelope(&engine, envelope.clone()).await.is_err());
+
+        // Correctly computed proof
+        let mut proven = envelope.clone();
+        proven.attach_vdf_proof(100)?;
+        let result = process_envelope(&engine, proven.clone()).await?;
+        assert_eq!(result.job_id, job.job_id);
+
+        // Tampered proof
+        let mut tampered = proven.clone();
+        tampered.vdf_proof.as_mut().unwrap().output[0] ^= 0xff;
+        assert!(process_envelope(&engine, tampered).await.is_err());
+
+        // Under the difficulty floor
+        let mut easy = envelope;
+        easy.attach_vdf_proof(10)?;
+        assert!(process_envelope(&engine, easy).await.is_err());
+
+        assert_eq!(engine.get_stats().await.total_matches, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_quote_has_no_side_effects() -> Result<()> {
+        let engine = AuctionEngine::temporary()?;
+        let job = GxfJob::new(JobId([7; 16]), PrecisionLevel::BF16, 1024);
+
+        let quote = engine.quote(&job, 200).await?;
+        assert!(!quote.candidates.is_empty());
+        assert!(quote.candidates.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(quote.route.is_some());
+
+        // Quoting twice gives the same answer since nothing was claimed
+        let again = engine.quote(&job, 200).await?;
+        assert_eq!(again.candidates, quote.candidates);
+
+        let stats = engine.get_stats().await;
+        assert_eq!((stats.total_auctions, stats.total_matches, stats.total_volume), (0, 0, 0));
+
+        // The auction clears at the cheapest quoted price
+        let result = engine.run_auction(&job, 200).await?;
+        assert_eq!((result.slp_id, result.price), quote.candidates[0].clone());
+        assert_eq!(result.lane_id, quote.route.unwrap().lane_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_precision_fallback_reports_substitution() -> Result<()> {
+        let providers = [ComputeProvider {
+            slp_id: SlpId("slp-bf16-only".to_string()),
+            supported_precisions: vec![PrecisionLevel::BF16],
+            base_price: 1000,
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        }];
+
+        let engine = engine_with_providers(&providers)?.with_precision_fallback(vec![
+            PrecisionLevel::FP8,
+            PrecisionLevel::E5M2,
+            PrecisionLevel::BF16,
+        ]);
+
+        // Without opting in, an FP8 job finds no provider
+        let mut job = GxfJob::new(JobId([8; 16]), PrecisionLevel::FP8, 1024);
+        let result = engine.run_auction(&job, 200).await;
+        assert!(matches!(result, Err(GixError::NoMatch(_))));
+
+        job.parameters.insert(ALLOW_PRECISION_FALLBACK_PARAM.to_string(), "true".to_string());
+        let result = engine.run_auction(&job, 200).await?;
+        assert_eq!(result.slp_id, SlpId("slp-bf16-only".to_string()));
+        assert_eq!(result.precision_used, PrecisionLevel::BF16);
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.matches_by_precision.get(&PrecisionLevel::BF16), Some(&1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_seq_len_limits_matching() -> Result<()> {
+        let providers = [("slp-8k", 8192), ("slp-32k", 32768)].map(|(id, max_seq_len)| ComputeProvider {
+            slp_id: SlpId(id.to_string()),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: if max_seq_len == 8192 { 100 } else { 1000 },
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len,
+            status: ProviderStatus::Active,
+        });
+
+        let engine = engine_with_providers(&providers)?;
+
+        // The cheaper 8k provider wins short jobs
+        let short = GxfJob::new(JobId([1; 16]), PrecisionLevel::INT8, 4096);
+        assert_eq!(engine.run_auction(&short, 50).await?.slp_id, SlpId("slp-8k".to_string()));
+
+        let long = GxfJob::new(JobId([2; 16]), PrecisionLevel::INT8, 32768);
+        let quote = engine.quote(&long, 50).await?;
+        assert_eq!(quote.candidates.len(), 1);
+        assert_eq!(engine.run_auction(&long, 50).await?.slp_id, SlpId("slp-32k".to_string()));
+
+        let too_long = GxfJob::new(JobId([3; 16]), PrecisionLevel::INT8, 65536);
+        assert!(matches!(engine.run_auction(&too_long, 50).await, Err(GixError::NoMatch(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_equal_prices_break_ties_on_slp_id() -> Result<()> {
+        let provider = |id: &str| ComputeProvider {
+            slp_id: SlpId(id.to_string()),
+            supported_precisions: vec![PrecisionLevel::BF16],
+            base_price: 1000,
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        };
+        let route = |id: &str| Route {
+            id: id.to_string(),
+            lane_id: LaneId(0),
+            path: vec![id.to_string()],
+            latency_ms: 0.0,
+            cost: 0,
+        };
+
+        // Every provider quotes the same price
+        let engine = AuctionEngine::temporary()?.with_pricing_model(FlatRatePricing(500));
+
+        // Appended after the defaults in reverse order, so table order alone would pick another
+        engine.add_provider(provider("slp-0-b")).await?;
+        engine.add_provider(provider("slp-0-a")).await?;
+        engine.add_route(route("route-0-b")).await?;
+        engine.add_route(route("route-0-a")).await?;
+
+        let quote = engine.quote(&GxfJob::new(JobId([0; 16]), PrecisionLevel::BF16, 1024), 200).await?;
+        assert_eq!(quote.candidates[0].0, SlpId("slp-0-a".to_string()));
+        assert_eq!(quote.candidates[1].0, SlpId("slp-0-b".to_string()));
+
+        for i in 1..=5 {
+            let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::BF16, 1024);
+            let result = engine.run_auction(&job, 200).await?;
+            assert_eq!(result.slp_id, SlpId("slp-0-a".to_string()));
+            assert_eq!(result.route, vec!["route-0-a".to_string()]);
+            engine.release_capacity(&result.slp_id).await?;
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_weighted_random_mode_spreads_load() -> Result<()> {
+        let provider = |id: &str, base_price: u64| ComputeProvider {
+            slp_id: SlpId(id.to_string()),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price,
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        };
+        let providers = [provider("slp-cheap", 1000), provider("slp-near", 1020)];
+
+        let mode = AuctionMode::WeightedRandom { price_band: 0.1 };
+        let run = |engine: AuctionEngine| async move {
+            let mut winners = Vec::new();
+            for i in 0..200u32 {
+                let mut id = [0u8; 16];
+                id[..4].copy_from_slice(&i.to_be_bytes());
+                let result = engine.run_auction(&GxfJob::new(JobId(id), PrecisionLevel::INT8, 128), 50).await?;
+                engine.release_capacity(&result.slp_id).await?;
+                winners.push(result.slp_id);
+            }
+            Ok::<_, anyhow::Error>(winners)
+        };
+
+        let winners = run(engine_with_providers(&providers)?.with_auction_mode(mode).with_seed(42)).await?;
+        let cheap = winners.iter().filter(|id| id.0 == "slp-cheap").count();
+        let near = winners.iter().filter(|id| id.0 == "slp-near").count();
+        assert_eq!(cheap + near, 200);
+        assert!(cheap > 0 && near > 0, "cheap {} near {}", cheap, near);
+        assert!(cheap >= near);
+
+        // The same seed picks the same providers
+        let replay = run(engine_with_providers(&providers)?.with_auction_mode(mode).with_seed(42)).await?;
+        assert_eq!(winners, replay);
+
+        // The default mode always takes the cheapest
+        let cheapest = run(engine_with_providers(&providers)?).await?;
+        assert!(cheapest.iter().all(|id| id.0 == "slp-cheap"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_price_overflow_is_reported() -> Result<()> {
+        let provider = ComputeProvider {
+            slp_id: SlpId("slp-long-context".to_string()),
+            supported_precisions: vec![PrecisionLevel::BF16],
+            base_price: 1000,
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: u32::MAX,
+            status: ProviderStatus::Active,
+        };
+
+        // The surcharge alone fits in a u64; the BF16 multiplier pushes it over
+        let job = GxfJob::new(JobId([42; 16]), PrecisionLevel::BF16, u32::MAX);
+        let pricing = DefaultPricingModel { seq_len_rate: u64::MAX / u32::MAX as u64 - 1, ..Default::default() };
+        assert!(matches!(pricing.price(&provider, &job), Err(GixError::Protocol(msg)) if msg == "price overflow"));
+
+        let engine = engine_with_providers(std::slice::from_ref(&provider))?.with_pricing_model(pricing);
+
+        assert!(matches!(engine.quote(&job, 50).await, Err(GixError::Protocol(_))));
+        let result = engine.run_auction(&job, 50).await;
+        assert!(matches!(result, Err(GixError::Protocol(msg)) if msg == "price overflow"));
+
+        // Nothing was claimed or recorded for the failed auction
+        assert_eq!(engine.get_providers().await[0].utilization, 0);
+        assert_eq!(engine.get_stats().await.total_volume, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_batch_releases_its_claims() -> Result<()> {
+        let provider = ComputeProvider {
+            slp_id: SlpId("slp-long-context".to_string()),
+            supported_precisions: vec![PrecisionLevel::BF16],
+            base_price: 1000,
+            capacity: 10,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: u32::MAX,
+            status: ProviderStatus::Active,
+        };
+
+        let pricing = DefaultPricingModel { seq_len_rate: u64::MAX / u32::MAX as u64 - 1, ..Default::default() };
+        let engine = engine_with_providers(std::slice::from_ref(&provider))?.with_pricing_model(pricing);
+
+        // The first two jobs clear; pricing the third overflows
+        let jobs = vec![
+            GxfJob::new(JobId([1; 16]), PrecisionLevel::BF16, 1024),
+            GxfJob::new(JobId([2; 16]), PrecisionLevel::BF16, 1024),
+            GxfJob::new(JobId([3; 16]), PrecisionLevel::BF16, u32::MAX),
+        ];
+        let result = engine.run_batch_auction(&jobs, 50).await;
+        assert!(matches!(result, Err(GixError::Protocol(msg)) if msg == "price overflow"));
+
+        // The aborted batch holds no capacity
+        assert_eq!(engine.get_providers().await[0].utilization, 0);
+
+        Ok(())
+    }
+
+    /// Holds each auction in pricing long enough for a duplicate to arrive
+    struct SlowPricing;
+
+    impl PricingModel for SlowPricing {
+        fn price(&self, provider: &ComputeProvider, job: &GxfJob) -> Result<u64, GixError> {
+            std::thread::sleep(Duration::from_millis(200));
+            DefaultPricingModel::default().price(provider, job)
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_duplicate_in_flight_job_is_rejected() -> Result<()> {
+        let engine = AuctionEngine::temporary()?.with_pricing_model(SlowPricing);
+        let job = GxfJob::new(JobId([4; 16]), PrecisionLevel::INT8, 128);
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let (engine, job) = (engine.clone(), job.clone());
+                tokio::spawn(async move { engine.run_auction(&job, 50).await })
+            })
+            .collect();
+
+        let mut matched = 0;
+        let mut duplicates = 0;
+        for handle in handles {
+            match handle.await? {
+                Ok(_) => matched += 1,
+                Err(GixError::Protocol(message)) => {
+                    assert_eq!(message, "job already in flight");
+                    duplicates += 1;
+                }
+                Err(e) => panic!("unexpected auction error: {}", e),
+            }
+        }
+        assert_eq!((matched, duplicates), (1, 1));
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.total_auctions, 1);
+        assert_eq!(stats.total_matches, 1);
+
+        // Once the first auction completes the id may be auctioned again
+        engine.run_auction(&job, 50).await?;
+        assert_eq!(engine.get_stats().await.total_auctions, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_batch_receipt_proves_each_match() -> Result<()> {
+        let engine = AuctionEngine::temporary()?;
+        let mut jobs: Vec<_> = (0..4u8)
+            .map(|i| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 1024))
+            .collect();
+        // Longer than any default provider accepts
+        jobs[1].kv_cache_seq_len = 1_000_000;
+
+        let receipt = engine.run_batch_auction(&jobs, 50).await?;
+        assert_eq!(receipt.matches.len(), 3);
+        assert_eq!(receipt.proofs.len(), 3);
+        assert_eq!(receipt.unmatched, vec![JobId([1; 16])]);
+
+        // A client holding one match and its proof can check it against the root
+        let matched = receipt.matches[2].clone();
+        let proof = receipt.proofs[2].clone();
+        assert_eq!(matched.job_id, JobId([3; 16]));
+        assert!(verify_batch_inclusion(&receipt.root, &matched, &proof));
+
+        let mut forged = matched.clone();
+        forged.price += 1;
+        assert!(!verify_batch_inclusion(&receipt.root, &forged, &proof));
+        assert!(!verify_batch_inclusion(&receipt.root, &receipt.matches[0], &proof));
+
+        // A batch where nothing clears has nothing to commit to
+        let err = engine.run_batch_auction(&jobs[1..2], 50).await.unwrap_err();
+        assert!(matches!(err, GixError::NoMatch(_)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_receipt_roundtrip_and_tamper() -> Result<()> {
+        let node_key = KeyPair::generate();
+        let engine = AuctionEngine::temporary()?;
+        let job = GxfJob::new(JobId([9; 16]), PrecisionLevel::INT8, 1024);
+        let matched = engine.run_auction(&job, 50).await?;
+
+        // Without a key the node issues no receipts
+        assert!(engine.receipt_for(&matched)?.is_none());
+
+        let engine = engine.with_receipt_key(node_key.clone());
+        let receipt = engine.receipt_for(&matched)?.expect("receipt key is set");
+
+        // The receipt survives a round trip through its file format
+        let decoded = AuctionReceipt::from_json(&receipt.to_json()?)?;
+        assert_eq!(decoded, receipt);
+        assert_eq!(decoded.auction_match, matched);
+        decoded.verify(&node_key.public)?;
+
+        // Any change to the match breaks the signature
+        let mut tampered = decoded.clone();
+        tampered.auction_match.price += 1;
+        assert!(matches!(tampered.verify(&node_key.public), Err(GixError::CryptoFailure)));
+
+        // So does checking against a different node's key
+        let other_key = KeyPair::generate();
+        assert!(decoded.verify(&other_key.public).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_waiting_auction_proceeds_when_capacity_frees() -> Result<()> {
+        let slp_id = SlpId("slp-single".to_string());
+        let providers = [ComputeProvider {
+            slp_id: slp_id.clone(),
+            supported_precisions: vec![PrecisionLevel::INT8],
+            base_price: 1000,
+            capacity: 1,
+            utilization: 0,
+            region: "US".to_string(),
+            max_seq_len: 32768,
+            status: ProviderStatus::Active,
+        }];
+
+        let engine = engine_with_providers(&providers)?;
+        let job = |i: u8| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 128);
+        engine.run_auction(&job(1), 50).await?;
+
+        // Blocked by full capacity until the running job releases its slot
+        let waiting = {
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                engine.run_auction_waiting(&job(2), 50, Duration::from_secs(5)).await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        engine.release_capacity(&slp_id).await?;
+        let result = waiting.await??;
+        assert_eq!(result.slp_id, slp_id);
+
+        // Without a release the wait gives up after the window
+        let err = engine
+            .run_auction_waiting(&job(3), 50, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GixError::NoMatch(_)));
+
+        // Jobs no provider could ever take fail without waiting
+        let bf16 = GxfJob::new(JobId([4; 16]), PrecisionLevel::BF16, 128);
+        let started = std::time::Instant::now();
+        assert!(engine.run_auction_waiting(&bf16, 50, Duration::from_secs(5)).await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.total_matches, 2);
+        assert_eq!(stats.total_unmatched, 2);
+
+        Ok(())
+    }
 }
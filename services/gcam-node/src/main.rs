@@ -3,25 +3,134 @@
 //! Clearing engine and bridge services for the global compute auction.
 //! Handles job matching, pricing, and route selection with persistent storage.
 
-use gcam_node::AuctionEngine;
+use gcam_node::{AuctionEngine, AuctionRecord, AuctionStats, ComputeProvider, DefaultPricingModel, PersistenceMode, RateLimit, RateLimiter};
 use anyhow::{Context, Result};
-use gix_gxf::GxfJob;
-use gix_proto::v1::{GetAuctionStatsRequest, GetAuctionStatsResponse, JobId as ProtoJobId, LaneId as ProtoLaneId, RunAuctionRequest, RunAuctionResponse, SlpId as ProtoSlpId};
-use gix_proto::{AuctionService, AuctionServiceServer};
+use clap::Parser;
+use gix_gxf::{GxfEnvelope, GxfJob, DEFAULT_MAX_ENVELOPE_BYTES};
+use gix_common::config::{env_lookup, setting};
+use gix_common::{JobId, SlpId};
+use gix_crypto::pqc::dilithium::{KeyPair, PublicKey as DilithiumPublicKey};
+use gix_crypto::wallet::decode_wallet;
+use gix_proto::v1::{AddProviderRequest, AddProviderResponse, HealthCheckRequest, HealthCheckResponse, ServingStatus, AuctionRecord as ProtoAuctionRecord, GetAuctionHistoryRequest, GetAuctionHistoryResponse, GetAuctionStatsRequest, GetAuctionStatsResponse, JobId as ProtoJobId, ListProvidersRequest, ListProvidersResponse, ReleaseCapacityRequest, ReleaseCapacityResponse, LaneId as ProtoLaneId, RunAuctionRequest, RunAuctionResponse, SlpId as ProtoSlpId, StreamStatsRequest};
+use gix_proto::auth::{AuthInterceptor, AuthenticatedKey, OptionalAuth};
+use gix_proto::errors::{chain_to_status, decoding_limit, draining, oversized_envelope, to_status};
+use gix_proto::reflection::reflection_service;
+use gix_proto::tls::server_tls_config;
+use gix_proto::trace::trace_id_or_new;
+use gix_proto::{AuctionService, AuctionServiceServer, HealthService, HealthServiceServer, ProviderAdminService, ProviderAdminServiceServer};
 use metrics_exporter_prometheus::PrometheusBuilder;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::service::interceptor::{InterceptedService, Interceptor};
 use tonic::{Request, Response, Status};
-use tracing::info;
+use tracing::{info, info_span, warn, Instrument};
 
 const GCAM_SERVER_ADDR: &str = "0.0.0.0:50052";
 const METRICS_ADDR: &str = "0.0.0.0:9002";
 const DB_PATH: &str = "./data/gcam_db";
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+const SERVICE_NAME: &str = "gix.v1.AuctionService";
+
+/// Bind addresses and storage location, overridable through the environment
+struct ServiceConfig {
+    /// gRPC listen address
+    addr: SocketAddr,
+    /// Prometheus metrics listen address
+    metrics_addr: SocketAddr,
+    /// Database directory
+    db_path: PathBuf,
+    /// Largest encoded envelope accepted, also sizing the gRPC decode limit
+    ///
+    /// Decompressed payloads are bounded separately by `MAX_PAYLOAD_BYTES`.
+    max_envelope_bytes: usize,
+}
+
+impl ServiceConfig {
+    /// Read `GIX_GCAM_ADDR`, `GIX_GCAM_METRICS_ADDR`, `GIX_GCAM_DB` and
+    /// `GIX_GCAM_MAX_ENVELOPE_BYTES`, defaulting unset ones
+    fn load(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        Ok(ServiceConfig {
+            addr: setting(&lookup, "GIX_GCAM_ADDR", GCAM_SERVER_ADDR)?,
+            metrics_addr: setting(&lookup, "GIX_GCAM_METRICS_ADDR", METRICS_ADDR)?,
+            db_path: setting(&lookup, "GIX_GCAM_DB", DB_PATH)?,
+            max_envelope_bytes: setting(&lookup, "GIX_GCAM_MAX_ENVELOPE_BYTES", &DEFAULT_MAX_ENVELOPE_BYTES.to_string())?,
+        })
+    }
+}
+
+/// Convert an auction record to its protobuf form
+fn record_to_proto(record: AuctionRecord) -> ProtoAuctionRecord {
+    ProtoAuctionRecord {
+        job_id: Some(ProtoJobId { id: record.job_id.0.to_vec() }),
+        slp_id: Some(ProtoSlpId { id: record.slp_id.0 }),
+        price: record.price,
+        lane_id: Some(ProtoLaneId { id: record.lane_id.as_u32() }),
+        timestamp: record.timestamp,
+        candidate_count: record.candidate_count,
+    }
+}
+
+/// Convert auction statistics to their protobuf form
+fn stats_to_proto(stats: &AuctionStats) -> GetAuctionStatsResponse {
+    let mut matches_by_precision = std::collections::HashMap::new();
+    for (precision, count) in stats.matches_by_precision.iter() {
+        matches_by_precision.insert(format!("{:?}", precision), *count);
+    }
+    
+    let mut matches_by_lane = std::collections::HashMap::new();
+    for (lane_id, count) in stats.matches_by_lane.iter() {
+        matches_by_lane.insert(lane_id.as_u32(), *count);
+    }
+    
+    GetAuctionStatsResponse {
+        total_auctions: stats.total_auctions,
+        total_matches: stats.total_matches,
+        total_volume: stats.total_volume,
+        matches_by_precision,
+        matches_by_lane,
+        min_price: stats.min_price,
+        max_price: stats.max_price,
+        avg_price: stats.avg_price,
+    }
+}
 
 /// Auction service implementation
 struct AuctionServiceImpl {
     engine: Arc<AuctionEngine>,
+    /// Per-client auction throttle (None disables rate limiting)
+    rate_limiter: Option<RateLimiter>,
+    /// Largest encoded job accepted
+    max_envelope_bytes: usize,
+    /// Set on shutdown to turn away new auctions
+    draining: Arc<AtomicBool>,
+}
+
+/// Rate-limiting key for an auction request: its authenticated caller, else the peer address
+///
+/// The `source_slp` field is chosen by the submitter and would let one
+/// client spread its requests across any number of buckets.
+fn rate_limit_key<T>(request: &Request<T>) -> String {
+    if let Some(AuthenticatedKey(key)) = request.extensions().get::<AuthenticatedKey>() {
+        return format!("key:{}", key);
+    }
+    request
+        .remote_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+impl AuctionServiceImpl {
+    /// Serve over gRPC behind `interceptor`, decoding requests up to the envelope limit
+    fn into_server<I: Interceptor>(self, interceptor: I) -> InterceptedService<AuctionServiceServer<Self>, I> {
+        let limit = decoding_limit(self.max_envelope_bytes);
+        InterceptedService::new(AuctionServiceServer::new(self).max_decoding_message_size(limit), interceptor)
+    }
 }
 
 #[tonic::async_trait]
@@ -30,26 +139,66 @@ impl AuctionService for AuctionServiceImpl {
         &self,
         request: Request<RunAuctionRequest>,
     ) -> Result<Response<RunAuctionResponse>, Status> {
+        if let Some(status) = draining(&self.draining) {
+            return Err(status);
+        }
+        let span = info_span!("auction_request", trace_id = %trace_id_or_new(&request));
+        if let Some(limiter) = &self.rate_limiter {
+            let client = rate_limit_key(&request);
+            if !limiter.try_acquire(&client) {
+                metrics::increment_counter!("gix_auction_rate_limited_total");
+                return Err(Status::resource_exhausted(format!("Auction rate limit exceeded for {}", client)));
+            }
+        }
         let req = request.into_inner();
+        if let Some(status) = oversized_envelope(req.job.len().max(req.envelope.len()), self.max_envelope_bytes) {
+            return Err(status);
+        }
         
-        // Deserialize GXF job from bytes
-        let job: GxfJob = serde_json::from_slice(&req.job)
-            .map_err(|e| Status::invalid_argument(format!("Invalid job: {}", e)))?;
+        let match_result = if req.envelope.is_empty() {
+            // A bare job would bypass the VDF gate
+            #[cfg(feature = "vdf")]
+            if self.engine.requires_vdf() {
+                return Err(Status::failed_precondition(
+                    "This node requires an envelope with a VDF proof; bare jobs are not accepted",
+                ));
+            }
+            
+            // Bare jobs get the same parse limits and validation as envelope payloads
+            let job = GxfJob::from_json(&req.job).map_err(|e| to_status(&e))?;
+            job.validate().map_err(|e| to_status(&e))?;
+            
+            // Run auction
+            self.engine
+                .run_auction(&job, req.priority as u8)
+                .instrument(span)
+                .await
+                .map_err(|e| to_status(&e))?
+        } else {
+            // The envelope's signed metadata, not the request, sets the priority
+            let envelope = GxfEnvelope::from_json(&req.envelope)
+                .map_err(|e| to_status(&e))?;
+            gcam_node::process_envelope(&self.engine, envelope)
+                .instrument(span)
+                .await
+                .map_err(|e| chain_to_status(e.as_ref()))?
+        };
         
-        // Run auction
-        let match_result = self.engine
-            .run_auction(&job, req.priority as u8)
-            .await
-            .map_err(|e| Status::internal(format!("Auction failed: {}", e)))?;
+        let receipt = match self.engine.receipt_for(&match_result).map_err(|e| to_status(&e))? {
+            Some(receipt) => receipt.to_json().map_err(|e| to_status(&e))?,
+            None => Vec::new(),
+        };
         
         Ok(Response::new(RunAuctionResponse {
             job_id: Some(ProtoJobId { id: match_result.job_id.0.to_vec() }),
             slp_id: Some(ProtoSlpId { id: match_result.slp_id.0 }),
-            lane_id: Some(ProtoLaneId { id: match_result.lane_id.0 as u32 }),
+            lane_id: Some(ProtoLaneId { id: match_result.lane_id.as_u32() }),
             price: match_result.price,
             route: match_result.route,
             success: true,
             error: String::new(),
+            receipt,
+            provider_region: match_result.provider_region,
         }))
     }
 
@@ -58,29 +207,258 @@ impl AuctionService for AuctionServiceImpl {
         _request: Request<GetAuctionStatsRequest>,
     ) -> Result<Response<GetAuctionStatsResponse>, Status> {
         let stats = self.engine.get_stats().await;
-        
-        let mut matches_by_precision = std::collections::HashMap::new();
-        for (precision, count) in stats.matches_by_precision.iter() {
-            matches_by_precision.insert(format!("{:?}", precision), *count);
+        Ok(Response::new(stats_to_proto(&stats)))
+    }
+
+    type StreamAuctionStatsStream = ReceiverStream<Result<GetAuctionStatsResponse, Status>>;
+
+    async fn stream_auction_stats(
+        &self,
+        request: Request<StreamStatsRequest>,
+    ) -> Result<Response<Self::StreamAuctionStatsStream>, Status> {
+        let interval_ms = request.into_inner().interval_ms;
+        if interval_ms == 0 {
+            return Err(Status::invalid_argument("Stats interval must be non-zero"));
         }
         
-        let mut matches_by_lane = std::collections::HashMap::new();
-        for (lane_id, count) in stats.matches_by_lane.iter() {
-            matches_by_lane.insert(lane_id.0 as u32, *count);
-        }
+        let (tx, rx) = mpsc::channel(1);
+        let engine = self.engine.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(interval_ms));
+            loop {
+                interval.tick().await;
+                let stats = engine.get_stats().await;
+                // The receiver is dropped once the client disconnects
+                if tx.send(Ok(stats_to_proto(&stats))).await.is_err() {
+                    break;
+                }
+            }
+        });
+        
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn get_auction_history(
+        &self,
+        request: Request<GetAuctionHistoryRequest>,
+    ) -> Result<Response<GetAuctionHistoryResponse>, Status> {
+        let req = request.into_inner();
         
-        Ok(Response::new(GetAuctionStatsResponse {
-            total_auctions: stats.total_auctions,
-            total_matches: stats.total_matches,
-            total_volume: stats.total_volume,
-            matches_by_precision,
-            matches_by_lane,
+        let records = if let Some(job_id) = req.job_id {
+            let job_id: [u8; 16] = job_id.id.as_slice().try_into()
+                .map_err(|_| Status::invalid_argument("Job ID must be 16 bytes"))?;
+            self.engine.get_record(&JobId(job_id)).into_iter().collect()
+        } else {
+            let limit = match req.limit {
+                0 => DEFAULT_HISTORY_LIMIT,
+                n => n as usize,
+            };
+            self.engine.get_history(limit)
+        };
+        
+        Ok(Response::new(GetAuctionHistoryResponse {
+            records: records.into_iter().map(record_to_proto).collect(),
+        }))
+    }
+
+    async fn release_capacity(
+        &self,
+        request: Request<ReleaseCapacityRequest>,
+    ) -> Result<Response<ReleaseCapacityResponse>, Status> {
+        let span = info_span!("release_capacity", trace_id = %trace_id_or_new(&request));
+        let slp_id = request
+            .into_inner()
+            .slp_id
+            .ok_or_else(|| Status::invalid_argument("Missing SLP ID"))?;
+        
+        self.engine
+            .release_capacity(&SlpId(slp_id.id))
+            .instrument(span)
+            .await
+            .map_err(|e| chain_to_status(e.as_ref()))?;
+        
+        Ok(Response::new(ReleaseCapacityResponse {
+            success: true,
+            error: String::new(),
         }))
     }
 }
 
+/// Provider administration service implementation
+struct ProviderAdminServiceImpl {
+    engine: Arc<AuctionEngine>,
+}
+
+#[tonic::async_trait]
+impl ProviderAdminService for ProviderAdminServiceImpl {
+    async fn add_provider(
+        &self,
+        request: Request<AddProviderRequest>,
+    ) -> Result<Response<AddProviderResponse>, Status> {
+        let span = info_span!("add_provider", trace_id = %trace_id_or_new(&request));
+        let provider: ComputeProvider = serde_json::from_slice(&request.into_inner().provider)
+            .map_err(|e| Status::invalid_argument(format!("Invalid provider: {}", e)))?;
+        let slp_id = provider.slp_id.clone();
+        
+        self.engine
+            .add_provider(provider)
+            .instrument(span)
+            .await
+            .map_err(|e| chain_to_status(e.as_ref()))?;
+        info!("Provider {} added", slp_id);
+        
+        Ok(Response::new(AddProviderResponse {
+            success: true,
+            error: String::new(),
+        }))
+    }
+
+    async fn list_providers(
+        &self,
+        _request: Request<ListProvidersRequest>,
+    ) -> Result<Response<ListProvidersResponse>, Status> {
+        let providers = self.engine
+            .get_providers()
+            .await
+            .iter()
+            .map(serde_json::to_vec)
+            .collect::<Result<_, _>>()
+            .map_err(|e| Status::internal(format!("Failed to encode provider: {}", e)))?;
+        
+        Ok(Response::new(ListProvidersResponse { providers }))
+    }
+}
+
+/// Health service implementation
+struct HealthServiceImpl {
+    engine: Arc<AuctionEngine>,
+}
+
+#[tonic::async_trait]
+impl HealthService for HealthServiceImpl {
+    async fn check(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        let status = match request.into_inner().service.as_str() {
+            "" | SERVICE_NAME => match self.engine.health_check().await {
+                Ok(()) => ServingStatus::Serving,
+                Err(e) => {
+                    warn!("Health check failed: {}", e);
+                    ServingStatus::NotServing
+                }
+            },
+            _ => ServingStatus::ServiceUnknown,
+        };
+        
+        Ok(Response::new(HealthCheckResponse {
+            status: status as i32,
+        }))
+    }
+}
+
+/// Command-line arguments
+#[derive(Parser)]
+#[command(about = "GCAM auction node service")]
+struct Args {
+    /// PEM certificate chain for serving gRPC over TLS
+    #[arg(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key for serving gRPC over TLS
+    #[arg(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// Armored Dilithium public key allowed to call the auction service
+    /// (repeatable; authentication is disabled when none are given)
+    #[arg(long = "authorized-key")]
+    authorized_keys: Vec<PathBuf>,
+
+    /// Wallet file (as written by `gix keygen`) whose key signs auction receipts
+    #[arg(long)]
+    receipt_key: Option<PathBuf>,
+
+    /// JSON file overriding the default pricing constants
+    #[arg(long)]
+    pricing: Option<PathBuf>,
+
+    /// Accept envelopes that expired up to this many seconds ago (clock skew)
+    #[arg(long, default_value_t = 0)]
+    clock_skew_secs: u64,
+
+    /// Flush the database in the background this often (0 disables)
+    #[arg(long, default_value_t = 5)]
+    flush_interval_secs: u64,
+
+    /// Leave auction state to the periodic and shutdown flushes instead of
+    /// writing it after every auction (a crash loses unflushed auctions)
+    #[arg(long)]
+    deferred_persistence: bool,
+
+    /// Sustained auctions per second allowed from each client (unlimited when unset)
+    ///
+    /// Clients are told apart by their auth key, or by peer address without auth.
+    #[arg(long)]
+    rate_limit_rps: Option<f64>,
+
+    /// Auctions a client may submit in a burst
+    #[arg(long, default_value_t = 10, requires = "rate_limit_rps")]
+    rate_limit_burst: u32,
+
+    /// Keep serving for this many seconds after a shutdown signal, rejecting
+    /// new requests as draining, before waiting out in-flight ones
+    #[arg(long, default_value_t = 5)]
+    drain_grace_secs: u64,
+
+    /// Require envelope submissions to carry a VDF proof of at least this many iterations
+    #[cfg(feature = "vdf")]
+    #[arg(long)]
+    vdf_min_iterations: Option<u64>,
+}
+
+/// Load pricing constants from a JSON file (missing fields keep their defaults)
+fn load_pricing(path: Option<&PathBuf>) -> Result<DefaultPricingModel> {
+    let Some(path) = path else {
+        return Ok(DefaultPricingModel::default());
+    };
+    
+    let contents = std::fs::read_to_string(path)
+        .context(format!("Failed to read pricing config: {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .context(format!("Invalid pricing config: {}", path.display()))
+}
+
+/// Load the receipt signing key from a `gix keygen` wallet file, JSON or `.gwl`
+fn load_receipt_key(path: &Path) -> Result<KeyPair> {
+    let contents = std::fs::read(path)
+        .context(format!("Failed to read receipt key: {}", path.display()))?;
+    decode_wallet(&contents)
+        .context(format!("Invalid receipt key: {}", path.display()))
+}
+
+/// Build the auction authenticator from armored public key files
+fn load_authenticator(paths: &[PathBuf]) -> Result<Option<AuthInterceptor>> {
+    if paths.is_empty() {
+        return Ok(None);
+    }
+    
+    let auth = AuthInterceptor::new();
+    for path in paths {
+        let armored = std::fs::read_to_string(path)
+            .context(format!("Failed to read authorized key: {}", path.display()))?;
+        let key = DilithiumPublicKey::from_armored(&armored)
+            .context(format!("Invalid authorized key: {}", path.display()))?;
+        auth.allow_key(key);
+    }
+    
+    Ok(Some(auth))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = ServiceConfig::load(env_lookup)?;
+
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -91,8 +469,7 @@ async fn main() -> Result<()> {
     info!("GCAM Node Service starting...");
     
     // Initialize Prometheus metrics exporter
-    let metrics_addr: SocketAddr = METRICS_ADDR.parse()
-        .context("Invalid metrics address")?;
+    let metrics_addr = config.metrics_addr;
     
     info!("Starting Prometheus metrics endpoint on {}", metrics_addr);
     
@@ -102,53 +479,417 @@ async fn main() -> Result<()> {
         .context("Failed to install Prometheus recorder")?;
     
     // Ensure data directory exists
-    std::fs::create_dir_all("./data")
-        .context("Failed to create data directory")?;
+    if let Some(data_dir) = config.db_path.parent() {
+        std::fs::create_dir_all(data_dir)
+            .context("Failed to create data directory")?;
+    }
 
     // Initialize auction engine with persistent storage
-    info!("Opening database at {}", DB_PATH);
-    let engine = Arc::new(
-        AuctionEngine::new(DB_PATH)
-            .context("Failed to initialize auction engine with database")?
+    let pricing = load_pricing(args.pricing.as_ref())?;
+    info!("Pricing model: {:?}", pricing);
+    
+    info!("Opening database at {}", config.db_path.display());
+    let engine = AuctionEngine::open(&config.db_path)
+        .await
+        .context("Failed to initialize auction engine with database")?
+        .with_pricing_model(pricing)
+        .with_clock_skew(args.clock_skew_secs);
+    #[cfg(feature = "vdf")]
+    let engine = match args.vdf_min_iterations {
+        Some(iterations) => {
+            info!("Envelope submissions require VDF proofs of {} iterations", iterations);
+            engine.with_vdf_min_iterations(iterations)
+        }
+        None => engine,
+    };
+    let engine = match &args.receipt_key {
+        Some(path) => {
+            let keypair = load_receipt_key(path)?;
+            info!("Signing auction receipts with {} key from {}", keypair.level(), path.display());
+            engine.with_receipt_key(keypair)
+        }
+        None => engine,
+    };
+    let engine = if args.deferred_persistence {
+        if args.flush_interval_secs == 0 {
+            warn!("Deferred persistence without a flush interval only writes auction state on shutdown");
+        }
+        info!("Deferring auction persistence to database flushes");
+        engine.with_persistence_mode(PersistenceMode::Deferred)
+    } else {
+        engine
+    };
+    let engine = Arc::new(engine);
+    info!(
+        "Auction engine initialized with persistent storage (recovered: {})",
+        engine.was_recovered()
     );
-    info!("Auction engine initialized with persistent storage");
+    let periodic_flush = (args.flush_interval_secs > 0).then(|| {
+        info!("Flushing database every {}s", args.flush_interval_secs);
+        engine.start_periodic_flush(Duration::from_secs(args.flush_interval_secs))
+    });
 
-    // Create service implementation
+    // Create service implementations
+    let rate_limiter = args.rate_limit_rps.map(|requests_per_sec| {
+        info!("Rate limiting auctions to {}/s per client (burst {})", requests_per_sec, args.rate_limit_burst);
+        RateLimiter::new(RateLimit { requests_per_sec, burst: args.rate_limit_burst })
+    });
+    let draining = Arc::new(AtomicBool::new(false));
     let service = AuctionServiceImpl {
         engine: engine.clone(),
+        rate_limiter,
+        max_envelope_bytes: config.max_envelope_bytes,
+        draining: draining.clone(),
+    };
+    let provider_admin = ProviderAdminServiceImpl {
+        engine: engine.clone(),
     };
+    let health = HealthServiceImpl {
+        engine: engine.clone(),
+    };
+    
+    // Require signed tokens on the auction and admin services when keys are configured
+    let auth = load_authenticator(&args.authorized_keys)?;
+    if auth.is_some() {
+        info!("Auction requests require tokens from {} authorized key(s)", args.authorized_keys.len());
+    }
 
-    // Parse server address
-    let addr = GCAM_SERVER_ADDR.parse()
-        .context("Invalid server address")?;
+    let addr = config.addr;
     
     info!("Starting gRPC server on {}", addr);
     
+    let mut server = tonic::transport::Server::builder();
+    if let (Some(cert), Some(key)) = (&args.tls_cert, &args.tls_key) {
+        info!("Serving gRPC over TLS");
+        server = server
+            .tls_config(server_tls_config(cert, key)?)
+            .context("Invalid TLS configuration")?;
+    }
+    
     // Create server with graceful shutdown
-    let server = tonic::transport::Server::builder()
-        .add_service(AuctionServiceServer::new(service))
-        .serve_with_shutdown(addr, shutdown_signal(engine.clone()));
+    let server = server
+        .add_service(service.into_server(OptionalAuth(auth.clone())))
+        .add_service(ProviderAdminServiceServer::with_interceptor(provider_admin, OptionalAuth(auth)))
+        .add_service(HealthServiceServer::new(health))
+        .add_service(reflection_service().context("Failed to build reflection service")?)
+        .serve_with_shutdown(addr, shutdown_signal(draining, Duration::from_secs(args.drain_grace_secs)));
     
     // Run server
     server.await.context("Server error")?;
+    if let Some(periodic_flush) = periodic_flush {
+        periodic_flush.stop().await;
+    }
+    
+    // Flush once in-flight auctions and the periodic flush have finished
+    if let Err(e) = engine.flush().await {
+        eprintln!("Error flushing database: {}", e);
+    } else {
+        info!("Database flushed successfully");
+    }
     
     info!("GCAM Node Service stopped");
     Ok(())
 }
 
-/// Wait for shutdown signal and flush database
-async fn shutdown_signal(engine: Arc<AuctionEngine>) {
+/// Wait for shutdown signal and start draining
+///
+/// New auctions are rejected from the signal on, for `grace` before this
+/// future resolves; in-flight ones then complete before `serve_with_shutdown`
+/// returns, after which `main` flushes.
+async fn shutdown_signal(draining: Arc<AtomicBool>, grace: Duration) {
     // Wait for CTRL+C
     signal::ctrl_c()
         .await
         .expect("Failed to install CTRL+C signal handler");
+    draining.store(true, Ordering::SeqCst);
     
-    info!("Shutdown signal received, flushing database...");
-    
-    // Flush database to ensure all data is persisted
-    if let Err(e) = engine.flush().await {
-        eprintln!("Error flushing database: {}", e);
-    } else {
-        info!("Database flushed successfully");
+    info!("Shutdown signal received, draining for {:?}...", grace);
+    tokio::time::sleep(grace).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gix_proto::{AuctionServiceClient, HealthServiceClient};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+
+    #[test]
+    fn test_receipt_key_loads_either_wallet_format() -> Result<()> {
+        use gix_crypto::wallet::{encode_wallet, WalletFormat};
+
+        let keypair = KeyPair::generate();
+        for (name, format) in [("json", WalletFormat::Json), ("gwl", WalletFormat::Binary)] {
+            let path = std::env::temp_dir().join(format!("gcam_receipt_key_test.{}", name));
+            std::fs::write(&path, encode_wallet(&keypair, format)?)?;
+            let loaded = load_receipt_key(&path)?;
+            std::fs::remove_file(&path).ok();
+            assert_eq!(loaded.public, keypair.public);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_service_config_from_environment() -> Result<()> {
+        let config = ServiceConfig::load(|_| None)?;
+        assert_eq!(config.addr, GCAM_SERVER_ADDR.parse()?);
+        assert_eq!(config.metrics_addr, METRICS_ADDR.parse()?);
+        assert_eq!(config.db_path, PathBuf::from(DB_PATH));
+        
+        let config = ServiceConfig::load(|name| match name {
+            "GIX_GCAM_ADDR" => Some("127.0.0.1:6052".to_string()),
+            "GIX_GCAM_DB" => Some("/tmp/gcam-b".to_string()),
+            _ => None,
+        })?;
+        assert_eq!(config.addr, "127.0.0.1:6052".parse()?);
+        assert_eq!(config.db_path, PathBuf::from("/tmp/gcam-b"));
+        
+        // A malformed address is a startup error naming the variable, not a panic
+        let err = ServiceConfig::load(|name| {
+            (name == "GIX_GCAM_METRICS_ADDR").then(|| "localhost".to_string())
+        })
+        .err()
+        .expect("malformed address rejected");
+        let message = err.to_string();
+        assert!(message.contains("GIX_GCAM_METRICS_ADDR"), "{}", message);
+        assert!(message.contains("\"localhost\""), "{}", message);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_serving() -> Result<()> {
+        let engine = Arc::new(AuctionEngine::temporary()?);
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = format!("http://{}", listener.local_addr()?);
+        
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(HealthServiceServer::new(HealthServiceImpl { engine }))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        
+        let mut client = HealthServiceClient::connect(addr).await?;
+        
+        for service in ["", SERVICE_NAME] {
+            let response = client
+                .check(Request::new(HealthCheckRequest { service: service.to_string() }))
+                .await?
+                .into_inner();
+            assert_eq!(response.status, ServingStatus::Serving as i32);
+        }
+        
+        let response = client
+            .check(Request::new(HealthCheckRequest { service: "gix.v1.Unknown".to_string() }))
+            .await?
+            .into_inner();
+        assert_eq!(response.status, ServingStatus::ServiceUnknown as i32);
+        
+        server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_auction_stats_pushes_snapshots() -> Result<()> {
+        let engine = Arc::new(AuctionEngine::temporary()?);
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = format!("http://{}", listener.local_addr()?);
+        
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(AuctionServiceServer::new(AuctionServiceImpl {
+                    engine: engine.clone(),
+                    rate_limiter: None,
+                    max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES,
+                    draining: Arc::new(AtomicBool::new(false)),
+                }))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        
+        let mut client = AuctionServiceClient::connect(addr).await?;
+        
+        let status = client
+            .stream_auction_stats(Request::new(StreamStatsRequest { interval_ms: 0 }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        
+        let mut stream = client
+            .stream_auction_stats(Request::new(StreamStatsRequest { interval_ms: 20 }))
+            .await?
+            .into_inner();
+        
+        let first = stream.message().await?.expect("first snapshot");
+        let job = GxfJob::new(JobId([3; 16]), gix_gxf::PrecisionLevel::BF16, 1024);
+        engine.run_auction(&job, 200).await?;
+        
+        // A snapshot may already be buffered from before the auction
+        let mut latest = stream.message().await?.expect("second snapshot");
+        assert!(latest.total_auctions >= first.total_auctions);
+        while latest.total_auctions == first.total_auctions {
+            let next = stream.message().await?.expect("further snapshot");
+            assert!(next.total_auctions >= latest.total_auctions);
+            latest = next;
+        }
+        assert_eq!(latest.total_auctions, first.total_auctions + 1);
+        
+        drop(stream);
+        server.abort();
+        Ok(())
+    }
+
+    #[cfg(feature = "vdf")]
+    #[tokio::test]
+    async fn test_bare_job_rejected_when_vdf_required() -> Result<()> {
+        let engine = Arc::new(AuctionEngine::temporary()?.with_vdf_min_iterations(100));
+        let service = AuctionServiceImpl {
+            engine,
+            rate_limiter: None,
+            max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES,
+            draining: Arc::new(AtomicBool::new(false)),
+        };
+        let job = GxfJob::new(JobId([1; 16]), gix_gxf::PrecisionLevel::INT8, 256);
+        let status = service
+            .run_auction(Request::new(RunAuctionRequest {
+                job: serde_json::to_vec(&job)?,
+                priority: 50,
+                source_slp: String::new(),
+                envelope: Vec::new(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(service.engine.get_stats().await.total_auctions, 0);
+        
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_auction_rate_limits_each_client() -> Result<()> {
+        let engine = Arc::new(AuctionEngine::temporary()?);
+        let service = AuctionServiceImpl {
+            engine,
+            rate_limiter: Some(RateLimiter::new(RateLimit { requests_per_sec: 0.01, burst: 3 })),
+            max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES,
+            draining: Arc::new(AtomicBool::new(false)),
+        };
+        let request = |i: u8, caller: &str| {
+            let job = GxfJob::new(JobId([i; 16]), gix_gxf::PrecisionLevel::BF16, 1024);
+            let mut request = Request::new(RunAuctionRequest {
+                job: serde_json::to_vec(&job).unwrap(),
+                priority: 50,
+                // Rotating the claimed source does not buy a fresh bucket
+                source_slp: format!("slp-{}", i),
+                envelope: Vec::new(),
+            });
+            request.extensions_mut().insert(AuthenticatedKey(caller.to_string()));
+            request
+        };
+        
+        // The burst is admitted, the excess is throttled
+        for i in 0..3 {
+            service.run_auction(request(i, "noisy")).await?;
+        }
+        for i in 3..5 {
+            let status = service.run_auction(request(i, "noisy")).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        }
+        
+        // Other callers keep their own budget
+        service.run_auction(request(5, "quiet")).await?;
+        assert_eq!(service.engine.get_stats().await.total_auctions, 4);
+        
+        Ok(())
+    }
+
+    /// Service over a throwaway engine with no rate limit
+    fn test_service() -> Result<AuctionServiceImpl> {
+        Ok(AuctionServiceImpl {
+            engine: Arc::new(AuctionEngine::temporary()?),
+            rate_limiter: None,
+            max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES,
+            draining: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Auction request for an envelope, claiming top priority outside it
+    fn envelope_request(envelope: &GxfEnvelope) -> Result<Request<RunAuctionRequest>> {
+        Ok(Request::new(RunAuctionRequest {
+            job: Vec::new(),
+            priority: 255,
+            source_slp: String::new(),
+            envelope: envelope.to_json()?,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_envelope_priority_comes_from_its_metadata() -> Result<()> {
+        let service = test_service()?;
+        let job = GxfJob::new(JobId([1; 16]), gix_gxf::PrecisionLevel::BF16, 1024);
+        let envelope = GxfEnvelope::from_job(job, 50)?;
+        
+        // A low-priority envelope stays on the deep lane whatever the request says
+        let response = service.run_auction(envelope_request(&envelope)?).await?.into_inner();
+        assert_eq!(response.lane_id.map(|lane| lane.id), Some(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry_ignores_request_priority() -> Result<()> {
+        let service = test_service()?;
+        let job = GxfJob::new(JobId([2; 16]), gix_gxf::PrecisionLevel::BF16, 1024);
+        let mut envelope = GxfEnvelope::from_job(job, 50)?;
+        envelope.meta.idempotency_key = Some("submit-1".to_string());
+        
+        let first = service.run_auction(envelope_request(&envelope)?).await?.into_inner();
+        let retry = service.run_auction(envelope_request(&envelope)?).await?.into_inner();
+        assert_eq!(first.lane_id.as_ref().map(|lane| lane.id), Some(1));
+        assert_eq!(retry.lane_id, first.lane_id);
+        assert_eq!(service.engine.get_stats().await.total_auctions, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bare_job_is_limited_and_validated() -> Result<()> {
+        let service = test_service()?;
+        let request = |job: &GxfJob| {
+            Request::new(RunAuctionRequest {
+                job: serde_json::to_vec(job).unwrap(),
+                priority: 50,
+                source_slp: String::new(),
+                envelope: Vec::new(),
+            })
+        };
+        
+        let mut bloated = GxfJob::new(JobId([1; 16]), gix_gxf::PrecisionLevel::BF16, 1024);
+        for i in 0..10_000 {
+            bloated.parameters.insert(format!("p{}", i), "x".to_string());
+        }
+        let status = service.run_auction(request(&bloated)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        
+        let invalid = GxfJob::new(JobId([2; 16]), gix_gxf::PrecisionLevel::BF16, 0);
+        let status = service.run_auction(request(&invalid)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(service.engine.get_stats().await.total_auctions, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_limiter_bounds_tracked_clients() {
+        let limiter = RateLimiter::new(RateLimit { requests_per_sec: 1000.0, burst: 2 }).with_max_clients(3);
+        for i in 0..100 {
+            assert!(limiter.try_acquire(&format!("client-{}", i)));
+        }
+        assert!(limiter.tracked_clients() <= 3);
+        
+        // Buckets that refilled are dropped before any client with debt
+        let slow = RateLimiter::new(RateLimit { requests_per_sec: 0.01, burst: 2 }).with_max_clients(2);
+        assert!(slow.try_acquire("a"));
+        assert!(slow.try_acquire("a"));
+        assert!(slow.try_acquire("b"));
+        assert!(slow.try_acquire("c"));
+        assert_eq!(slow.tracked_clients(), 2);
+        // "a" was the idlest and evicted, so it starts over with a full bucket
+        assert!(slow.try_acquire("a"));
     }
 }
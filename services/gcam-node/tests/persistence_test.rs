@@ -3,18 +3,29 @@
 //! These tests verify that the auction engine state survives restarts.
 
 use anyhow::Result;
-use gcam_node::AuctionEngine;
-use gix_common::JobId;
+use gcam_node::{open_db, AuctionEngine, PROVIDER_SCHEMA_VERSION, PersistenceMode, ComputeProvider, ProviderStatus, Route};
+use gix_common::{GixError, JobId, LaneId, SlpId};
 use gix_gxf::{GxfJob, PrecisionLevel};
 use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Write providers into a fresh database before the engine opens it
+fn seed_providers(db_path: &Path, providers: &[ComputeProvider]) -> Result<()> {
+    let db = open_db(db_path)?;
+    let tree = db.open_tree("providers")?;
+    for provider in providers {
+        tree.insert(provider.slp_id.0.as_bytes(), provider.to_record()?)?;
+    }
+    db.flush()?;
+    Ok(())
+}
 
 #[tokio::test]
 async fn test_persistence_survives_restart() -> Result<()> {
-    let test_db_path = "./test_data/gcam_persistence_test";
-    
-    // Clean up any existing test database
-    let _ = fs::remove_dir_all(test_db_path);
-    fs::create_dir_all(test_db_path)?;
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
     
     // Phase 1: Create engine, run auction, and close
     {
@@ -75,19 +86,13 @@ async fn test_persistence_survives_restart() -> Result<()> {
         engine.flush().await?;
     }
     
-    // Clean up test database
-    fs::remove_dir_all(test_db_path)?;
-    
     Ok(())
 }
 
 #[tokio::test]
 async fn test_provider_utilization_persists() -> Result<()> {
-    let test_db_path = "./test_data/gcam_provider_test";
-    
-    // Clean up any existing test database
-    let _ = fs::remove_dir_all(test_db_path);
-    fs::create_dir_all(test_db_path)?;
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
     
     let initial_volume;
     
@@ -133,19 +138,13 @@ async fn test_provider_utilization_persists() -> Result<()> {
         engine.flush().await?;
     }
     
-    // Clean up test database
-    fs::remove_dir_all(test_db_path)?;
-    
     Ok(())
 }
 
 #[tokio::test]
 async fn test_crash_recovery() -> Result<()> {
-    let test_db_path = "./test_data/gcam_crash_test";
-    
-    // Clean up any existing test database
-    let _ = fs::remove_dir_all(test_db_path);
-    fs::create_dir_all(test_db_path)?;
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
     
     // Phase 1: Normal operation
     {
@@ -184,10 +183,823 @@ async fn test_crash_recovery() -> Result<()> {
         engine.flush().await?;
     }
     
-    // Clean up test database
-    fs::remove_dir_all(test_db_path)?;
+    Ok(())
+}
+
+
+
+#[tokio::test]
+async fn test_deferred_history_waits_for_flush() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    seed_providers(test_db_path, &[bulk_provider()])?;
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?
+            .with_persistence_mode(PersistenceMode::Deferred)
+            .with_history_capacity(3);
+        for i in 0..5 {
+            engine.run_auction(&indexed_job(i), 50).await?;
+        }
+        
+        // Queued records are served before they are written
+        let history = engine.get_history(10);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].job_id, indexed_job(4).job_id);
+        assert_eq!(history[2].job_id, indexed_job(2).job_id);
+        engine.flush().await?;
+    }
+    
+    // The trimmed count carries over a restart
+    {
+        let engine = AuctionEngine::new(test_db_path)?.with_history_capacity(3);
+        assert_eq!(engine.get_history(10).len(), 3);
+        engine.run_auction(&indexed_job(5), 50).await?;
+        let history = engine.get_history(10);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].job_id, indexed_job(5).job_id);
+        assert_eq!(history[2].job_id, indexed_job(3).job_id);
+    }
     
     Ok(())
 }
 
+#[tokio::test]
+async fn test_disabled_provider_is_not_matched() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    let provider = |id: &str, base_price: u64| ComputeProvider {
+        slp_id: SlpId(id.to_string()),
+        supported_precisions: vec![PrecisionLevel::INT8],
+        base_price,
+        capacity: 10,
+        utilization: 0,
+        region: "US".to_string(),
+        max_seq_len: 32768,
+        status: ProviderStatus::Active,
+    };
+    let cheap = SlpId("slp-cheap".to_string());
+    seed_providers(test_db_path, &[provider("slp-cheap", 1000), provider("slp-pricey", 2000)])?;
+    let job = |i: u8| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 128);
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert_eq!(engine.run_auction(&job(1), 50).await?.slp_id, cheap);
+        
+        // Draining keeps the job it holds but is offered no new ones
+        engine.set_provider_status(&cheap, ProviderStatus::Draining).await?;
+        assert_eq!(engine.run_auction(&job(2), 50).await?.slp_id, SlpId("slp-pricey".to_string()));
+        engine.release_capacity(&cheap).await?;
+        assert_eq!(engine.utilization(&cheap).await, Some((0, 10)));
+        
+        engine.set_provider_status(&cheap, ProviderStatus::Disabled).await?;
+        let quote = engine.quote(&job(3), 50).await?;
+        assert!(quote.candidates.iter().all(|(id, _)| id != &cheap));
+        
+        // Still listed, with its status
+        let listed = engine.get_providers().await;
+        let disabled = listed.iter().find(|p| p.slp_id == cheap).expect("provider still listed");
+        assert_eq!(disabled.status, ProviderStatus::Disabled);
+        
+        assert!(engine.set_provider_status(&SlpId("slp-unknown".to_string()), ProviderStatus::Disabled).await.is_err());
+    }
+    
+    // The status survives a restart, and reactivating restores matching
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert_eq!(engine.get_provider(&cheap).await.map(|p| p.status), Some(ProviderStatus::Disabled));
+        
+        engine.set_provider_status(&cheap, ProviderStatus::Active).await?;
+        assert_eq!(engine.run_auction(&job(4), 50).await?.slp_id, cheap);
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_old_stats_record_migrates_to_current_schema() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    seed_providers(test_db_path, &[bulk_provider()])?;
+    {
+        // Stats as written before clearing prices were tracked
+        let by_lane: std::collections::HashMap<LaneId, u64> = [(LaneId(0), 4)].into_iter().collect();
+        let by_precision: std::collections::HashMap<PrecisionLevel, u64> =
+            [(PrecisionLevel::INT8, 4)].into_iter().collect();
+        let old = bincode::serialize(&(5u64, 4u64, 1u64, 10_000u64, by_precision, by_lane))?;
+        let db = open_db(test_db_path)?;
+        db.open_tree("stats")?.insert("stats", old)?;
+        db.flush()?;
+    }
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        let stats = engine.get_stats().await;
+        assert_eq!((stats.total_auctions, stats.total_matches, stats.total_volume), (5, 4, 10_000));
+        assert_eq!(stats.matches_by_lane.get(&LaneId(0)), Some(&4));
+        assert!((stats.avg_price - 2500.0).abs() < f64::EPSILON);
+        assert_eq!((stats.min_price, stats.max_price), (0, 0));
+        
+        // The next match fills in the unknown extremes
+        let result = engine.run_auction(&indexed_job(0), 50).await?;
+        let stats = engine.get_stats().await;
+        assert_eq!((stats.min_price, stats.max_price), (result.price, result.price));
+        assert_eq!(stats.total_matches, 5);
+    }
+    
+    // Saved back in the current layout
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert_eq!(engine.get_stats().await.total_matches, 5);
+    }
+    
+    Ok(())
+}
 
+#[tokio::test]
+async fn test_route_latency_updates_selection() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    let job = |i: u8| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 64);
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        
+        // A second flash-lane route, slower than the default one
+        engine.add_route(Route {
+            id: "route-flash-2".to_string(),
+            lane_id: LaneId(0),
+            path: vec!["node-6".to_string(), "node-7".to_string()],
+            latency_ms: 80.0,
+            cost: 100,
+        }).await?;
+        assert_eq!(engine.get_routes().await.len(), 3);
+        
+        let result = engine.run_auction(&job(1), 200).await?;
+        assert_eq!(result.route, vec!["node-1".to_string(), "node-2".to_string()]);
+        
+        // Measured congestion on the default route shifts traffic to the new one
+        engine.update_route_latency("route-flash-1", 500).await?;
+        let result = engine.run_auction(&job(2), 200).await?;
+        assert_eq!(result.route, vec!["node-6".to_string(), "node-7".to_string()]);
+        
+        let err = engine.update_route_latency("route-missing", 10).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<GixError>(), Some(GixError::NoMatch(_))));
+        
+        engine.flush().await?;
+    }
+    
+    // Route changes survive a restart
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        let routes = engine.get_routes().await;
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes.iter().find(|r| r.id == "route-flash-1").unwrap().latency_ms, 500.0);
+        
+        let removed = engine.remove_route("route-flash-2").await?;
+        assert_eq!(removed.latency_ms, 80.0);
+        
+        let result = engine.run_auction(&job(3), 200).await?;
+        assert_eq!(result.route, vec!["node-1".to_string(), "node-2".to_string()]);
+        assert!(engine.remove_route("route-flash-2").await.is_err());
+    }
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert_eq!(engine.get_routes().await.len(), 2);
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_corrupt_records_are_quarantined() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    // Custom routes and a provider followed by a garbage record
+    seed_providers(test_db_path, &[ComputeProvider {
+        slp_id: SlpId("slp-custom".to_string()),
+        supported_precisions: vec![PrecisionLevel::INT8],
+        base_price: 1,
+        capacity: 1,
+        utilization: 0,
+        region: "US".to_string(),
+        max_seq_len: 32768,
+        status: ProviderStatus::Active,
+    }])?;
+    {
+        let db = open_db(test_db_path)?;
+        db.open_tree("providers")?.insert("slp-zz-garbage", vec![0xff; 3])?;
+        db.open_tree("stats")?.insert("stats", vec![0xff; 5])?;
+        let route = Route {
+            id: "route-custom".to_string(),
+            lane_id: LaneId(0),
+            path: vec!["node-9".to_string()],
+            latency_ms: 10.0,
+            cost: 1,
+        };
+        db.open_tree("routes")?.insert("route-custom", bincode::serialize(&route)?)?;
+        db.flush()?;
+    }
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        
+        // Only the corrupt record is dropped; its intact neighbours are kept
+        let providers = engine.get_providers().await;
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].slp_id, SlpId("slp-custom".to_string()));
+        let job = GxfJob::new(JobId([1; 16]), PrecisionLevel::INT8, 100);
+        let result = engine.run_auction(&job, 50).await?;
+        assert_eq!(result.slp_id, SlpId("slp-custom".to_string()));
+        let routes = engine.get_routes().await;
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].id, "route-custom");
+        assert_eq!(engine.get_stats().await.total_auctions, 1);
+        
+        engine.flush().await?;
+    }
+    
+    // The repaired database reopens cleanly
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert_eq!(engine.get_stats().await.total_auctions, 1);
+        assert_eq!(engine.get_providers().await.len(), 1);
+    }
+    
+    // The corrupt record was moved aside rather than deleted
+    {
+        let db = open_db(test_db_path)?;
+        assert!(db.open_tree("providers")?.get("slp-zz-garbage")?.is_none());
+        let quarantined = db.open_tree("providers_quarantine")?.get("slp-zz-garbage")?;
+        assert_eq!(quarantined.as_deref(), Some(&[0xff; 3][..]));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_locked_database_reports_storage_locked() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    {
+        let _holder = open_db(test_db_path)?;
+        let err = AuctionEngine::new(test_db_path).err().expect("database should be locked");
+        assert!(matches!(err.downcast_ref::<GixError>(), Some(GixError::StorageLocked(_))));
+    }
+    
+    // Released locks are picked up again
+    AuctionEngine::new(test_db_path)?;
+    
+    // `open` waits for a handle that is shutting down
+    let holder = open_db(test_db_path)?;
+    let release = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        drop(holder);
+    });
+    AuctionEngine::open(test_db_path).await?;
+    release.await?;
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reset_stats_persists() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        for i in 0..3 {
+            let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::BF16, 512);
+            engine.run_auction(&job, 200).await?;
+        }
+        
+        let snapshot = engine.reset_stats().await?;
+        assert_eq!(snapshot.total_auctions, 3);
+        assert_eq!(snapshot.total_matches, 3);
+        assert!(snapshot.total_volume > 0);
+        assert_eq!(snapshot.matches_by_precision.get(&PrecisionLevel::BF16), Some(&3));
+        assert_eq!(snapshot.matches_by_lane.values().sum::<u64>(), 3);
+        
+        let stats = engine.snapshot_stats().await;
+        assert_eq!((stats.total_auctions, stats.total_matches, stats.total_volume), (0, 0, 0));
+        assert_eq!((stats.min_price, stats.max_price), (0, 0));
+        assert!(stats.matches_by_precision.is_empty());
+        assert!(stats.matches_by_lane.is_empty());
+    }
+    
+    // The reset survives a restart
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert_eq!(engine.get_stats().await.total_auctions, 0);
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_providers_without_max_seq_len_are_upgraded() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    // A record in the layout used before max_seq_len was added
+    {
+        let db = open_db(test_db_path)?;
+        let tree = db.open_tree("providers")?;
+        let legacy = (
+            SlpId("slp-legacy".to_string()),
+            vec![PrecisionLevel::INT8],
+            1000u64,
+            10u32,
+            0u32,
+            "US".to_string(),
+        );
+        tree.insert("slp-legacy", bincode::serialize(&legacy)?)?;
+        db.flush()?;
+    }
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        
+        let job = GxfJob::new(JobId([4; 16]), PrecisionLevel::INT8, 65536);
+        let result = engine.run_auction(&job, 50).await?;
+        assert_eq!(result.slp_id, SlpId("slp-legacy".to_string()));
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_old_provider_records_migrate_to_current_schema() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    // A current record, marked so that a needless rewrite would show
+    let mut current_marked = bulk_provider().to_record()?;
+    current_marked.push(0xA5);
+    
+    // Schema 1 layout: no max_seq_len, written before records were versioned
+    {
+        let db = open_db(test_db_path)?;
+        let tree = db.open_tree("providers")?;
+        let old = (
+            SlpId("slp-old".to_string()),
+            vec![PrecisionLevel::BF16],
+            1500u64,
+            20u32,
+            3u32,
+            "EU".to_string(),
+        );
+        tree.insert("slp-old", bincode::serialize(&old)?)?;
+        tree.insert("slp-bulk", current_marked.clone())?;
+        db.flush()?;
+    }
+    
+    {
+        let _engine = AuctionEngine::new(test_db_path)?;
+    }
+    
+    // Loading rewrote the record in the current layout with defaults for new fields
+    {
+        let db = open_db(test_db_path)?;
+        let tree = db.open_tree("providers")?;
+        let record = tree.get("slp-old")?.expect("provider record kept");
+        let (provider, version) = ComputeProvider::from_record(&record)?;
+        assert_eq!(version, PROVIDER_SCHEMA_VERSION);
+        assert_eq!(provider.base_price, 1500);
+        assert_eq!((provider.capacity, provider.utilization), (20, 3));
+        assert_eq!(provider.region, "EU");
+        assert_eq!(provider.max_seq_len, u32::MAX);
+        
+        // Only the outdated record was rewritten
+        assert_eq!(tree.get("slp-bulk")?.expect("current record kept").to_vec(), current_marked);
+        
+        // Records from a newer schema are not guessed at
+        let mut future = record.to_vec();
+        future[4..6].copy_from_slice(&(PROVIDER_SCHEMA_VERSION + 1).to_le_bytes());
+        assert!(ComputeProvider::from_record(&future).is_err());
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reported_latency_shifts_route_selection() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    let job = |i: u8| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 64);
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?.with_latency_alpha(0.5);
+        engine.add_route(Route {
+            id: "route-flash-2".to_string(),
+            lane_id: LaneId(0),
+            path: vec!["node-6".to_string(), "node-7".to_string()],
+            latency_ms: 80.0,
+            cost: 100,
+        }).await?;
+        
+        let result = engine.run_auction(&job(0), 200).await?;
+        assert_eq!(result.route, vec!["node-1".to_string(), "node-2".to_string()]);
+        
+        // A single slow sample only moves the estimate halfway
+        assert_eq!(engine.report_latency("route-flash-1", 90).await?, 70.0);
+        let result = engine.run_auction(&job(1), 200).await?;
+        assert_eq!(result.route, vec!["node-1".to_string(), "node-2".to_string()]);
+        
+        // Consistently slow measurements eventually favour the alternative
+        for _ in 0..4 {
+            engine.report_latency("route-flash-1", 300).await?;
+        }
+        let result = engine.run_auction(&job(2), 200).await?;
+        assert_eq!(result.route, vec!["node-6".to_string(), "node-7".to_string()]);
+        
+        assert!(engine.report_latency("route-missing", 10).await.is_err());
+        engine.flush().await?;
+    }
+    
+    // The tuned estimate is persisted
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        let routes = engine.get_routes().await;
+        let latency = routes.iter().find(|r| r.id == "route-flash-1").unwrap().latency_ms;
+        assert!(latency > 80.0, "latency {} not persisted", latency);
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_small_latency_reports_accumulate() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    // A route in the layout used when latency was whole milliseconds
+    {
+        let db = open_db(test_db_path)?;
+        let tree = db.open_tree("routes")?;
+        let legacy = (
+            "route-flash-1".to_string(),
+            LaneId(0),
+            vec!["node-1".to_string(), "node-2".to_string()],
+            50u64,
+            100u64,
+        );
+        tree.insert("route-flash-1", bincode::serialize(&legacy)?)?;
+        db.flush()?;
+    }
+    
+    // Each report moves the estimate by less than a millisecond
+    {
+        let engine = AuctionEngine::new(test_db_path)?.with_latency_alpha(0.01);
+        assert_eq!(engine.get_routes().await[0].latency_ms, 50.0);
+        for _ in 0..10 {
+            engine.report_latency("route-flash-1", 60).await?;
+        }
+        let latency = engine.get_routes().await[0].latency_ms;
+        assert!(latency > 50.9 && latency < 51.0, "latency {} did not accumulate", latency);
+        engine.flush().await?;
+    }
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        let latency = engine.get_routes().await[0].latency_ms;
+        assert!(latency > 50.9 && latency < 51.0, "latency {} not persisted", latency);
+    }
+    
+    Ok(())
+}
+
+/// Copy a database directory as it is on disk, like the state left by a crash
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_periodic_flush_persists_without_explicit_flush() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    let snapshot = TempDir::new()?;
+    let snapshot_path = snapshot.path();
+    
+    {
+        // Deferred persistence leaves every write to the periodic flush
+        let engine = AuctionEngine::new(test_db_path)?.with_persistence_mode(PersistenceMode::Deferred);
+        let flusher = engine.start_periodic_flush(Duration::from_millis(20));
+        
+        let job = GxfJob::new(JobId([11; 16]), PrecisionLevel::INT8, 1024);
+        engine.run_auction(&job, 50).await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        
+        // Snapshot the files while the engine is still running, without flushing
+        copy_dir(test_db_path, snapshot_path)?;
+        flusher.stop().await;
+    }
+    
+    {
+        let restored = AuctionEngine::new(snapshot_path)?;
+        assert_eq!(restored.get_stats().await.total_auctions, 1);
+        assert_eq!(restored.get_history(10).len(), 1);
+    }
+    
+    Ok(())
+}
+
+/// Provider with enough capacity that benchmark auctions never release it
+fn bulk_provider() -> ComputeProvider {
+    ComputeProvider {
+        slp_id: SlpId("slp-bulk".to_string()),
+        supported_precisions: vec![PrecisionLevel::INT8],
+        base_price: 1000,
+        capacity: 100_000,
+        utilization: 0,
+        region: "US".to_string(),
+        max_seq_len: 32768,
+        status: ProviderStatus::Active,
+    }
+}
+
+/// Job with a unique ID per auction index
+fn indexed_job(i: u32) -> GxfJob {
+    let mut id = [0u8; 16];
+    id[..4].copy_from_slice(&i.to_be_bytes());
+    GxfJob::new(JobId(id), PrecisionLevel::INT8, 128)
+}
+
+#[tokio::test]
+async fn test_persistence_mode_throughput() -> Result<()> {
+    const AUCTIONS: u32 = 500;
+    
+    for mode in [PersistenceMode::Immediate, PersistenceMode::Deferred] {
+        let dir = TempDir::new()?;
+        seed_providers(dir.path(), &[bulk_provider()])?;
+        
+        {
+            let engine = AuctionEngine::new(dir.path())?.with_persistence_mode(mode);
+            let start = std::time::Instant::now();
+            for i in 0..AUCTIONS {
+                engine.run_auction(&indexed_job(i), 50).await?;
+            }
+            engine.flush().await?;
+            let elapsed = start.elapsed();
+            println!(
+                "{:?}: {} auctions in {:?} ({:.0}/s)",
+                mode,
+                AUCTIONS,
+                elapsed,
+                AUCTIONS as f64 / elapsed.as_secs_f64()
+            );
+            
+            assert_eq!(engine.get_stats().await.total_auctions, AUCTIONS as u64);
+            assert!(!engine.is_dirty());
+        }
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deferred_persistence_survives_restart_after_flush() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    seed_providers(test_db_path, &[bulk_provider()])?;
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?.with_persistence_mode(PersistenceMode::Deferred);
+        assert!(!engine.is_dirty());
+        for i in 0..5 {
+            engine.run_auction(&indexed_job(i), 50).await?;
+        }
+        assert!(engine.is_dirty());
+        
+        engine.flush().await?;
+        assert!(!engine.is_dirty());
+    }
+    
+    {
+        let restored = AuctionEngine::new(test_db_path)?;
+        let stats = restored.get_stats().await;
+        assert_eq!(stats.total_auctions, 5);
+        assert_eq!(stats.total_matches, 5);
+        assert_eq!(restored.get_history(10).len(), 5);
+        
+        let providers = restored.get_providers().await;
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].utilization, 5);
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auction_rewrites_only_matched_provider() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    let matched = bulk_provider();
+    let untouched = ComputeProvider {
+        slp_id: SlpId("slp-bf16-only".to_string()),
+        supported_precisions: vec![PrecisionLevel::BF16],
+        ..bulk_provider()
+    };
+    // Store the unmatched provider as a current record with a trailing marker
+    // byte: the engine decodes it as is, but any rewrite would drop the marker
+    let mut untouched_bytes = untouched.to_record()?;
+    untouched_bytes.push(0xA5);
+    {
+        let db = open_db(test_db_path)?;
+        let tree = db.open_tree("providers")?;
+        tree.insert(matched.slp_id.0.as_bytes(), matched.to_record()?)?;
+        tree.insert(untouched.slp_id.0.as_bytes(), untouched_bytes.clone())?;
+        db.flush()?;
+    }
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        let result = engine.run_auction(&indexed_job(1), 50).await?;
+        assert_eq!(result.slp_id, matched.slp_id);
+    }
+    
+    {
+        let db = open_db(test_db_path)?;
+        let tree = db.open_tree("providers")?;
+        
+        let stored = tree.get(matched.slp_id.0.as_bytes())?.expect("matched provider stored");
+        let (stored, _) = ComputeProvider::from_record(&stored)?;
+        assert_eq!(stored.utilization, 1);
+        
+        let stored = tree.get(untouched.slp_id.0.as_bytes())?.expect("unmatched provider stored");
+        assert_eq!(stored.to_vec(), untouched_bytes);
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_capacity_clamps_utilization() -> Result<()> {
+    let dir = TempDir::new()?;
+    let test_db_path = dir.path();
+    
+    let slp_id = SlpId("slp-elastic".to_string());
+    seed_providers(test_db_path, &[ComputeProvider {
+        slp_id: slp_id.clone(),
+        supported_precisions: vec![PrecisionLevel::INT8],
+        base_price: 1000,
+        capacity: 3,
+        utilization: 0,
+        region: "US".to_string(),
+        max_seq_len: 32768,
+        status: ProviderStatus::Active,
+    }])?;
+    let job = |i: u8| GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 128);
+    
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        for i in 0..3 {
+            engine.run_auction(&job(i), 50).await?;
+        }
+        
+        // Shrinking below utilization clamps it to the new capacity
+        engine.set_capacity(&slp_id, 1).await?;
+        assert!(matches!(engine.run_auction(&job(3), 50).await, Err(GixError::NoMatch(_))));
+        
+        // One release frees the single remaining slot
+        engine.release_capacity(&slp_id).await?;
+        engine.run_auction(&job(4), 50).await?;
+        assert!(engine.run_auction(&job(5), 50).await.is_err());
+        
+        // Scaling to zero takes the provider out of matching entirely
+        engine.set_capacity(&slp_id, 0).await?;
+        assert!(engine.quote(&job(6), 50).await?.candidates.is_empty());
+        
+        assert!(engine.set_capacity(&SlpId("slp-unknown".to_string()), 4).await.is_err());
+        engine.flush().await?;
+    }
+    
+    // The resized capacity survives a restart
+    {
+        let engine = AuctionEngine::new(test_db_path)?;
+        assert!(engine.quote(&job(7), 50).await?.candidates.is_empty());
+        
+        engine.set_capacity(&slp_id, 2).await?;
+        engine.run_auction(&job(8), 50).await?;
+        engine.run_auction(&job(9), 50).await?;
+        assert!(engine.run_auction(&job(10), 50).await.is_err());
+    }
+    
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trips_into_fresh_engine() -> Result<()> {
+    let source_dir = TempDir::new()?;
+    let target_dir = TempDir::new()?;
+    let (source_path, target_path) = (source_dir.path(), target_dir.path());
+
+    let snapshot = {
+        let engine = AuctionEngine::new(source_path)?;
+        engine.add_provider(bulk_provider()).await?;
+        engine.set_provider_status(&SlpId("slp-eu-west-1".to_string()), ProviderStatus::Draining).await?;
+        for i in 0..3 {
+            engine.run_auction(&GxfJob::new(JobId([i; 16]), PrecisionLevel::BF16, 512), 200).await?;
+        }
+        engine.export_snapshot().await?
+    };
+
+    {
+        let engine = AuctionEngine::new(target_path)?;
+        engine.run_auction(&GxfJob::new(JobId([9; 16]), PrecisionLevel::INT8, 512), 0).await?;
+
+        // A corrupt snapshot is rejected before anything is overwritten
+        let mut corrupt = snapshot.clone();
+        corrupt.truncate(corrupt.len() / 2);
+        assert!(engine.import_snapshot(&corrupt).await.is_err());
+        assert_eq!(engine.get_stats().await.total_auctions, 1);
+        assert_eq!(engine.get_providers().await.len(), 2);
+
+        engine.import_snapshot(&snapshot).await?;
+    }
+
+    // The imported state replaces the old one and survives a restart
+    let source = AuctionEngine::new(source_path)?;
+    let target = AuctionEngine::new(target_path)?;
+
+    let mut expected = source.get_providers().await;
+    let mut imported = target.get_providers().await;
+    expected.sort_by(|a, b| a.slp_id.0.cmp(&b.slp_id.0));
+    imported.sort_by(|a, b| a.slp_id.0.cmp(&b.slp_id.0));
+    assert_eq!(imported.len(), 3);
+    for (imported, expected) in imported.iter().zip(&expected) {
+        assert_eq!(imported.slp_id, expected.slp_id);
+        assert_eq!(imported.utilization, expected.utilization);
+        assert_eq!(imported.capacity, expected.capacity);
+        assert_eq!(imported.status, expected.status);
+    }
+
+    let (stats, expected_stats) = (target.get_stats().await, source.get_stats().await);
+    assert_eq!(stats.total_auctions, 3);
+    assert_eq!(
+        (stats.total_matches, stats.total_volume, stats.min_price, stats.max_price),
+        (expected_stats.total_matches, expected_stats.total_volume, expected_stats.min_price, expected_stats.max_price)
+    );
+    assert_eq!(stats.matches_by_precision, expected_stats.matches_by_precision);
+
+    assert_eq!(target.get_routes().await.len(), source.get_routes().await.len());
+    assert_eq!(target.get_history(10), source.get_history(10));
+    assert!(target.get_record(&JobId([9; 16])).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_skips_unreadable_history() -> Result<()> {
+    let source_dir = TempDir::new()?;
+    let source_path = source_dir.path();
+
+    let snapshot = {
+        let engine = AuctionEngine::new(source_path)?;
+        for i in 0..2 {
+            engine.run_auction(&GxfJob::new(JobId([i; 16]), PrecisionLevel::BF16, 512), 200).await?;
+        }
+        engine.flush().await?;
+        drop(engine);
+
+        // A garbage record between the two readable ones
+        {
+            let db = open_db(source_path)?;
+            let history = db.open_tree("history")?;
+            let mut key = history.first()?.expect("history has records").0.to_vec();
+            key.push(0);
+            history.insert(key, vec![0xff; 3])?;
+            db.flush()?;
+        }
+
+        AuctionEngine::new(source_path)?.export_snapshot().await?
+    };
+
+    let target = AuctionEngine::temporary()?;
+    target.import_snapshot(&snapshot).await?;
+    let history = target.get_history(10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].job_id, JobId([0; 16]));
+    assert_eq!(history[0].job_id, JobId([1; 16]));
+
+    Ok(())
+}
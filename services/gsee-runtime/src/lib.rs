@@ -2,13 +2,18 @@
 //!
 //! Provides runtime state and envelope processing functionality.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use gix_common::JobId;
-use gix_crypto::hash_blake3;
-use gix_gxf::{GxfEnvelope, GxfJob, PrecisionLevel};
+
+pub use gix_common::ComplianceError;
+use gix_gxf::{GxfEnvelope, GxfJob, GxfMetadata, PrecisionLevel};
+use metrics::{gauge, histogram, increment_counter};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex, Notify, RwLock};
+use tracing::debug;
 
 /// Execution result
 #[derive(Debug, Clone)]
@@ -19,7 +24,7 @@ pub struct ExecutionResult {
     pub status: ExecutionStatus,
     /// Execution duration in milliseconds
     pub duration_ms: u64,
-    /// Output data hash (simulated)
+    /// Output data hash, see [`GxfJob::output_hash`]
     pub output_hash: [u8; 32],
 }
 
@@ -32,6 +37,77 @@ pub enum ExecutionStatus {
     Failed(String),
     /// Job was rejected due to compliance violation
     Rejected(String),
+    /// Job exceeded the runtime's execution timeout
+    Timeout,
+    /// Job was cancelled before a worker started it
+    Cancelled,
+}
+
+/// Job parameter holding the batch size
+pub const BATCH_SIZE_PARAM: &str = "batch_size";
+
+/// Simulated execution time for a job
+///
+/// Duration is `base_ms + ceil(seq_len / seq_len_per_ms) * precision * batch`,
+/// where the batch factor is `1 + (batch_size - 1) * batch_scaling`. The
+/// defaults reproduce the original fixed formula for unbatched INT8 jobs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionCostModel {
+    /// Fixed overhead per job (ms)
+    pub base_ms: u64,
+    /// Sequence positions processed per millisecond
+    pub seq_len_per_ms: u32,
+    /// Compute multiplier for INT8 jobs
+    pub int8_multiplier: f64,
+    /// Compute multiplier for E5M2 jobs
+    pub e5m2_multiplier: f64,
+    /// Compute multiplier for FP8 jobs
+    pub fp8_multiplier: f64,
+    /// Compute multiplier for BF16 jobs
+    pub bf16_multiplier: f64,
+    /// Extra compute per additional batch element (1.0 = linear)
+    pub batch_scaling: f64,
+}
+
+impl Default for ExecutionCostModel {
+    fn default() -> Self {
+        ExecutionCostModel {
+            base_ms: 10,
+            seq_len_per_ms: 1000,
+            int8_multiplier: 1.0,
+            e5m2_multiplier: 1.2,
+            fp8_multiplier: 1.5,
+            bf16_multiplier: 2.0,
+            batch_scaling: 0.25,
+        }
+    }
+}
+
+impl ExecutionCostModel {
+    /// Multiplier applied for a precision level
+    pub fn precision_multiplier(&self, precision: PrecisionLevel) -> f64 {
+        match precision {
+            PrecisionLevel::INT8 => self.int8_multiplier,
+            PrecisionLevel::E5M2 => self.e5m2_multiplier,
+            PrecisionLevel::FP8 => self.fp8_multiplier,
+            PrecisionLevel::BF16 => self.bf16_multiplier,
+        }
+    }
+
+    /// Simulated duration of `job` in milliseconds
+    pub fn duration_ms(&self, job: &GxfJob) -> u64 {
+        let compute = (job.kv_cache_seq_len as f64 / self.seq_len_per_ms.max(1) as f64).ceil();
+        let batch_size = job
+            .parameters
+            .get(BATCH_SIZE_PARAM)
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(1)
+            .max(1);
+        let batch_factor = 1.0 + (batch_size - 1) as f64 * self.batch_scaling;
+
+        self.base_ms + (compute * self.precision_multiplier(job.precision) * batch_factor).ceil() as u64
+    }
 }
 
 /// Shape validation requirements
@@ -41,20 +117,22 @@ pub struct ShapeRequirements {
     pub max_sequence_length: u32,
     /// Maximum batch size
     pub max_batch_size: u32,
-    /// Required dimensions
+    /// Required dimensions, matched against the job's `dimensions` parameter (empty = any)
     pub required_dimensions: Vec<u32>,
 }
 
-impl ShapeRequirements {
+impl Default for ShapeRequirements {
     /// Create default shape requirements
-    pub fn default() -> Self {
+    fn default() -> Self {
         ShapeRequirements {
             max_sequence_length: 8192,
             max_batch_size: 32,
             required_dimensions: vec![],
         }
     }
+}
 
+impl ShapeRequirements {
     /// Validate shape against requirements
     pub fn validate(&self, job: &GxfJob) -> Result<(), ComplianceError> {
         if job.kv_cache_seq_len > self.max_sequence_length {
@@ -63,7 +141,7 @@ impl ShapeRequirements {
                 job.kv_cache_seq_len, self.max_sequence_length
             )));
         }
-        if let Some(batch_size_str) = job.parameters.get("batch_size") {
+        if let Some(batch_size_str) = job.parameters.get(BATCH_SIZE_PARAM) {
             if let Ok(batch_size) = batch_size_str.parse::<u32>() {
                 if batch_size > self.max_batch_size {
                     return Err(ComplianceError::ShapeViolation(format!(
@@ -73,10 +151,34 @@ impl ShapeRequirements {
                 }
             }
         }
+        if !self.required_dimensions.is_empty() {
+            let Some(dimensions_str) = job.parameters.get("dimensions") else {
+                return Err(ComplianceError::ShapeViolation(format!(
+                    "Required dimensions {:?} not specified",
+                    self.required_dimensions
+                )));
+            };
+            let dimensions = dimensions_str
+                .split(',')
+                .map(|d| d.trim().parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| {
+                    ComplianceError::ShapeViolation(format!("Invalid dimensions '{}'", dimensions_str))
+                })?;
+            if dimensions != self.required_dimensions {
+                return Err(ComplianceError::ShapeViolation(format!(
+                    "Dimensions {:?} do not match required {:?}",
+                    dimensions, self.required_dimensions
+                )));
+            }
+        }
         Ok(())
     }
 }
 
+/// Metadata `additional_fields` key holding the job's region
+pub const META_REGION_FIELD: &str = "region";
+
 /// Residency requirements
 #[derive(Debug, Clone)]
 pub struct ResidencyRequirements {
@@ -86,24 +188,31 @@ pub struct ResidencyRequirements {
     pub required_residency: Option<String>,
 }
 
-impl ResidencyRequirements {
+impl Default for ResidencyRequirements {
     /// Create default residency requirements
-    pub fn default() -> Self {
+    fn default() -> Self {
         ResidencyRequirements {
             allowed_regions: vec!["US".to_string(), "EU".to_string()],
             required_residency: None,
         }
     }
+}
+
+impl ResidencyRequirements {
+    fn check_region(&self, region: &str) -> Result<(), ComplianceError> {
+        if !self.allowed_regions.iter().any(|r| r == region) {
+            return Err(ComplianceError::ResidencyViolation(format!(
+                "Region '{}' not in allowed regions: {:?}",
+                region, self.allowed_regions
+            )));
+        }
+        Ok(())
+    }
 
     /// Validate residency requirements
     pub fn validate(&self, job: &GxfJob) -> Result<(), ComplianceError> {
         if let Some(job_region) = job.parameters.get("region") {
-            if !self.allowed_regions.contains(job_region) {
-                return Err(ComplianceError::ResidencyViolation(format!(
-                    "Region '{}' not in allowed regions: {:?}",
-                    job_region, self.allowed_regions
-                )));
-            }
+            self.check_region(job_region)?;
         }
         if let Some(required) = &self.required_residency {
             if let Some(job_residency) = job.parameters.get("residency") {
@@ -122,17 +231,91 @@ impl ResidencyRequirements {
         }
         Ok(())
     }
+
+    /// Validate residency using both job parameters and envelope metadata
+    ///
+    /// A `region` in `meta.additional_fields` is checked like the job parameter
+    /// and must agree with it when both are set.
+    pub fn validate_with_meta(&self, job: &GxfJob, meta: &GxfMetadata) -> Result<(), ComplianceError> {
+        self.validate(job)?;
+
+        let param_region = job.parameters.get("region");
+        let meta_region = meta.additional_fields.get(META_REGION_FIELD);
+        if let (Some(param_region), Some(meta_region)) = (param_region, meta_region) {
+            if param_region != meta_region {
+                return Err(ComplianceError::ResidencyViolation(format!(
+                    "Job region '{}' conflicts with metadata region '{}'",
+                    param_region, meta_region
+                )));
+            }
+        }
+        if let Some(meta_region) = meta_region {
+            self.check_region(meta_region)?;
+        }
+        Ok(())
+    }
+
+    /// Validate the region of the provider the auction awarded the job to
+    ///
+    /// The region comes from the auction match, not from the envelope, so a
+    /// submitter cannot claim one. It must be allowed and match the job's
+    /// region, from its parameters or metadata, when the job names one.
+    pub fn validate_provider_region(
+        &self,
+        job: &GxfJob,
+        meta: Option<&GxfMetadata>,
+        provider_region: &str,
+    ) -> Result<(), ComplianceError> {
+        self.check_region(provider_region)?;
+        let job_region = job
+            .parameters
+            .get("region")
+            .or_else(|| meta.and_then(|meta| meta.additional_fields.get(META_REGION_FIELD)));
+        if let Some(job_region) = job_region {
+            if job_region != provider_region {
+                return Err(ComplianceError::ResidencyViolation(format!(
+                    "Provider region '{}' does not match job region '{}'",
+                    provider_region, job_region
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
-/// Compliance error types
-#[derive(Debug, Clone, thiserror::Error)]
-pub enum ComplianceError {
-    #[error("Precision violation: {0}")]
-    PrecisionViolation(String),
-    #[error("Shape violation: {0}")]
-    ShapeViolation(String),
-    #[error("Residency violation: {0}")]
-    ResidencyViolation(String),
+/// Outcome delivered to the submitter of a queued job
+pub type QueuedOutcome = Result<ExecutionResult, ComplianceError>;
+
+/// Job waiting for an execution worker
+struct QueuedJob {
+    job: GxfJob,
+    meta: Option<GxfMetadata>,
+    /// Region of the provider the job was awarded to, if known
+    provider_region: Option<String>,
+    /// Submission order, used to keep equal priorities first-in first-out
+    seq: u64,
+    done: oneshot::Sender<QueuedOutcome>,
+}
+
+impl QueuedJob {
+    fn priority(&self) -> u8 {
+        self.meta.as_ref().map_or(0, |meta| meta.priority)
+    }
+}
+
+/// Jobs waiting for an execution worker
+#[derive(Default)]
+struct ExecutionQueue {
+    jobs: Vec<QueuedJob>,
+    next_seq: u64,
+    /// Jobs taken by a worker that have not finished yet
+    executing: usize,
+}
+
+impl ExecutionQueue {
+    fn is_idle(&self) -> bool {
+        self.jobs.is_empty() && self.executing == 0
+    }
 }
 
 /// GSEE Runtime state
@@ -146,6 +329,21 @@ pub struct RuntimeState {
     residency_requirements: ResidencyRequirements,
     /// Execution statistics
     stats: Arc<RwLock<ExecutionStats>>,
+    /// Simulated execution time model
+    cost_model: ExecutionCostModel,
+    /// Jobs waiting for a worker
+    queue: Arc<Mutex<ExecutionQueue>>,
+    /// Wakes idle workers when a job is queued
+    queue_ready: Arc<Notify>,
+    /// Wakes `drain` when the queue empties and no job is executing
+    queue_idle: Arc<Notify>,
+    /// Maximum execution time per job (None waits indefinitely)
+    execution_timeout: Option<Duration>,
+    /// Grace window for envelope expiry, in seconds
+    clock_skew_secs: u64,
+    /// Minimum VDF iterations required on envelopes (None disables the gate)
+    #[cfg(feature = "vdf")]
+    vdf_min_iterations: Option<u64>,
 }
 
 /// Execution statistics
@@ -159,6 +357,10 @@ pub struct ExecutionStats {
     pub total_failed: u64,
     /// Total jobs rejected
     pub total_rejected: u64,
+    /// Total jobs that exceeded the execution timeout
+    pub total_timeout: u64,
+    /// Total jobs cancelled while queued
+    pub total_cancelled: u64,
     /// Jobs by precision level
     pub jobs_by_precision: HashMap<PrecisionLevel, u64>,
 }
@@ -176,6 +378,48 @@ impl RuntimeState {
             shape_requirements: ShapeRequirements::default(),
             residency_requirements: ResidencyRequirements::default(),
             stats: Arc::new(RwLock::new(ExecutionStats::default())),
+            cost_model: ExecutionCostModel::default(),
+            queue: Arc::new(Mutex::new(ExecutionQueue::default())),
+            queue_ready: Arc::new(Notify::new()),
+            queue_idle: Arc::new(Notify::new()),
+            execution_timeout: None,
+            clock_skew_secs: 0,
+            #[cfg(feature = "vdf")]
+            vdf_min_iterations: None,
+        }
+    }
+
+    /// Replace the simulated execution time model
+    pub fn with_cost_model(mut self, model: ExecutionCostModel) -> Self {
+        self.cost_model = model;
+        self
+    }
+
+    /// Abort jobs that run longer than `timeout`
+    pub fn with_execution_timeout(mut self, timeout: Duration) -> Self {
+        self.execution_timeout = Some(timeout);
+        self
+    }
+
+    /// Accept envelopes that expired up to `secs` seconds ago
+    pub fn with_clock_skew(mut self, secs: u64) -> Self {
+        self.clock_skew_secs = secs;
+        self
+    }
+
+    /// Require envelopes to carry a VDF proof of at least `iterations`
+    #[cfg(feature = "vdf")]
+    pub fn with_vdf_min_iterations(mut self, iterations: u64) -> Self {
+        self.vdf_min_iterations = Some(iterations);
+        self
+    }
+
+    /// Reject envelopes whose VDF proof is missing, invalid, or below the difficulty floor
+    #[cfg(feature = "vdf")]
+    pub fn check_vdf(&self, envelope: &GxfEnvelope) -> Result<(), gix_gxf::GxfError> {
+        match self.vdf_min_iterations {
+            Some(min_iterations) => envelope.verify_vdf_proof(min_iterations),
+            None => Ok(()),
         }
     }
 
@@ -199,74 +443,643 @@ impl RuntimeState {
         self.shape_requirements.validate(job)
     }
 
-    fn check_residency(&self, job: &GxfJob) -> Result<(), ComplianceError> {
-        self.residency_requirements.validate(job)
+    fn check_residency(
+        &self,
+        job: &GxfJob,
+        meta: Option<&GxfMetadata>,
+        provider_region: Option<&str>,
+    ) -> Result<(), ComplianceError> {
+        match meta {
+            Some(meta) => self.residency_requirements.validate_with_meta(job, meta)?,
+            None => self.residency_requirements.validate(job)?,
+        }
+        if let Some(provider_region) = provider_region {
+            self.residency_requirements.validate_provider_region(job, meta, provider_region)?;
+        }
+        Ok(())
     }
 
-    fn check_compliance(&self, job: &GxfJob) -> Result<(), ComplianceError> {
+    fn check_compliance(
+        &self,
+        job: &GxfJob,
+        meta: Option<&GxfMetadata>,
+        provider_region: Option<&str>,
+    ) -> Result<(), ComplianceError> {
         self.check_precision(job)?;
         self.check_shape(job)?;
-        self.check_residency(job)?;
+        self.check_residency(job, meta, provider_region)?;
         Ok(())
     }
 
     async fn simulate_execution(&self, job: &GxfJob) -> ExecutionResult {
         let start_time = std::time::Instant::now();
-        let duration_ms = (job.kv_cache_seq_len as f64 / 1000.0).ceil() as u64 + 10;
+        let duration_ms = self.cost_model.duration_ms(job);
         tokio::time::sleep(tokio::time::Duration::from_millis(duration_ms)).await;
-        let output_hash = hash_blake3(&job.job_id.0);
+        let (status, output_hash) = match job.output_hash() {
+            Ok(hash) => (ExecutionStatus::Completed, hash),
+            Err(e) => (ExecutionStatus::Failed(e.to_string()), [0u8; 32]),
+        };
         let elapsed = start_time.elapsed().as_millis() as u64;
         ExecutionResult {
             job_id: job.job_id,
-            status: ExecutionStatus::Completed,
+            status,
             duration_ms: elapsed,
             output_hash,
         }
     }
 
-    async fn execute_job(&self, job: GxfJob) -> Result<ExecutionResult, ComplianceError> {
-        self.check_compliance(&job)?;
+    async fn execute_job(
+        &self,
+        job: GxfJob,
+        meta: Option<&GxfMetadata>,
+        provider_region: Option<&str>,
+    ) -> Result<ExecutionResult, ComplianceError> {
+        if let Err(e) = self.check_compliance(&job, meta, provider_region) {
+            increment_counter!("gix_jobs_rejected_total");
+            let mut stats = self.stats.write().await;
+            stats.total_rejected += 1;
+            *stats.jobs_by_precision.entry(job.precision).or_insert(0) += 1;
+            return Err(e);
+        }
+
+        increment_counter!("gix_jobs_executed_total");
+        increment_counter!("gix_jobs_by_precision", "precision" => job.precision.metric_label());
         {
             let mut stats = self.stats.write().await;
             stats.total_executed += 1;
             *stats.jobs_by_precision.entry(job.precision).or_insert(0) += 1;
+
+            gauge!("gix_runtime_total_executed", stats.total_executed as f64);
         }
-        let result = self.simulate_execution(&job).await;
+        let result = match self.execution_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.simulate_execution(&job))
+                .await
+                .unwrap_or(ExecutionResult {
+                    job_id: job.job_id,
+                    status: ExecutionStatus::Timeout,
+                    duration_ms: timeout.as_millis() as u64,
+                    output_hash: [0u8; 32],
+                }),
+            None => self.simulate_execution(&job).await,
+        };
+        histogram!("gix_execution_duration_ms", result.duration_ms as f64);
         {
             let mut stats = self.stats.write().await;
             match result.status {
-                ExecutionStatus::Completed => stats.total_completed += 1,
-                ExecutionStatus::Failed(_) => stats.total_failed += 1,
-                ExecutionStatus::Rejected(_) => stats.total_rejected += 1,
+                ExecutionStatus::Completed => {
+                    stats.total_completed += 1;
+                    increment_counter!("gix_jobs_completed_total");
+                }
+                ExecutionStatus::Failed(_) => {
+                    stats.total_failed += 1;
+                    increment_counter!("gix_jobs_failed_total");
+                }
+                ExecutionStatus::Rejected(_) => {
+                    stats.total_rejected += 1;
+                    increment_counter!("gix_jobs_rejected_total");
+                }
+                ExecutionStatus::Timeout => {
+                    stats.total_timeout += 1;
+                    increment_counter!("gix_jobs_timeout_total");
+                }
+                // Cancelled jobs never reach a worker
+                ExecutionStatus::Cancelled => {
+                    stats.total_cancelled += 1;
+                    increment_counter!("gix_jobs_cancelled_total");
+                }
             }
         }
         Ok(result)
     }
 
+    /// Queue a job for the execution workers
+    ///
+    /// Higher-priority jobs run first; equal priorities run in submission
+    /// order. The receiver yields the outcome; a job cancelled before a
+    /// worker picks it up yields a `Cancelled` result. `provider_region` is
+    /// the region of the provider the auction awarded the job to, if known.
+    pub async fn enqueue(
+        &self,
+        job: GxfJob,
+        meta: Option<GxfMetadata>,
+        provider_region: Option<String>,
+    ) -> oneshot::Receiver<QueuedOutcome> {
+        let (done, outcome) = oneshot::channel();
+        {
+            let mut queue = self.queue.lock().await;
+            let seq = queue.next_seq;
+            queue.next_seq += 1;
+            queue.jobs.push(QueuedJob { job, meta, provider_region, seq, done });
+            gauge!("gix_runtime_queue_depth", queue.jobs.len() as f64);
+        }
+        self.queue_ready.notify_one();
+        outcome
+    }
+
+    /// Number of jobs waiting for a worker
+    pub async fn queue_len(&self) -> usize {
+        self.queue.lock().await.jobs.len()
+    }
+
+    /// Remove a job that is still waiting in the queue
+    ///
+    /// Returns false if the job is already executing, has finished, or is unknown.
+    pub async fn cancel(&self, job_id: &JobId) -> bool {
+        let queued = {
+            let mut queue = self.queue.lock().await;
+            let Some(index) = queue.jobs.iter().position(|queued| queued.job.job_id == *job_id) else {
+                return false;
+            };
+            let queued = queue.jobs.remove(index);
+            gauge!("gix_runtime_queue_depth", queue.jobs.len() as f64);
+            if queue.is_idle() {
+                self.queue_idle.notify_waiters();
+            }
+            queued
+        };
+
+        self.stats.write().await.total_cancelled += 1;
+        increment_counter!("gix_jobs_cancelled_total");
+        // The submitter may have stopped waiting
+        let _ = queued.done.send(Ok(ExecutionResult {
+            job_id: *job_id,
+            status: ExecutionStatus::Cancelled,
+            duration_ms: 0,
+            output_hash: [0u8; 32],
+        }));
+        true
+    }
+
+    /// Execute the highest-priority queued job
+    ///
+    /// Returns false if the queue was empty.
+    pub async fn run_next(&self) -> bool {
+        let next = {
+            let mut queue = self.queue.lock().await;
+            let index = queue
+                .jobs
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, queued)| (queued.priority(), std::cmp::Reverse(queued.seq)))
+                .map(|(index, _)| index);
+            let next = index.map(|index| queue.jobs.remove(index));
+            if next.is_some() {
+                queue.executing += 1;
+            }
+            gauge!("gix_runtime_queue_depth", queue.jobs.len() as f64);
+            next
+        };
+        let Some(queued) = next else {
+            return false;
+        };
+
+        let outcome = self
+            .execute_job(queued.job, queued.meta.as_ref(), queued.provider_region.as_deref())
+            .await;
+        // The submitter may have stopped waiting; the job still ran
+        let _ = queued.done.send(outcome);
+
+        let mut queue = self.queue.lock().await;
+        queue.executing -= 1;
+        if queue.is_idle() {
+            self.queue_idle.notify_waiters();
+        }
+        true
+    }
+
+    /// Wait until every queued job has run and no job is executing
+    ///
+    /// Jobs queued while waiting are waited for too. Needs running workers
+    /// to make progress.
+    pub async fn drain(&self) {
+        loop {
+            // Register before checking so a job finishing in between is not missed
+            let idle = self.queue_idle.notified();
+            tokio::pin!(idle);
+            idle.as_mut().enable();
+
+            if self.queue.lock().await.is_idle() {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Spawn `count` background workers that drain the queue
+    pub fn spawn_workers(&self, count: usize) {
+        for _ in 0..count {
+            let runtime = self.clone();
+            tokio::spawn(async move {
+                loop {
+                    if !runtime.run_next().await {
+                        runtime.queue_ready.notified().await;
+                    }
+                }
+            });
+        }
+    }
+
     /// Get execution statistics
     pub async fn get_stats(&self) -> ExecutionStats {
         self.stats.read().await.clone()
     }
+
+    /// Snapshot the current execution statistics
+    pub async fn snapshot_stats(&self) -> ExecutionStats {
+        self.get_stats().await
+    }
+
+    /// Zero the execution counters, returning their values before the reset
+    pub async fn reset_stats(&self) -> ExecutionStats {
+        let snapshot = std::mem::take(&mut *self.stats.write().await);
+        gauge!("gix_runtime_total_executed", 0.0);
+        snapshot
+    }
+}
+
+impl Default for RuntimeState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Process a GXF envelope through the runtime
+///
+/// `provider_region` is the region of the provider the auction awarded the
+/// job to, taken from the auction match; it is checked against residency.
+#[tracing::instrument(
+    skip_all,
+    fields(job_id = tracing::field::Empty, precision = tracing::field::Empty, priority = envelope.meta.priority, fingerprint = %envelope.fingerprint())
+)]
 pub async fn process_envelope(
     runtime: &RuntimeState,
     envelope: GxfEnvelope,
+    provider_region: Option<&str>,
 ) -> Result<ExecutionResult> {
-    envelope.validate().map_err(|e| anyhow::anyhow!("Envelope validation failed: {}", e))?;
-    if envelope.meta.is_expired() {
-        return Err(anyhow::anyhow!("Envelope expired"));
-    }
+    let job = admit_envelope(runtime, &envelope)?;
+
+    let result = runtime
+        .execute_job(job, Some(&envelope.meta), provider_region)
+        .await
+        .map_err(|e| anyhow::Error::new(e).context("Compliance check failed"))?;
+    debug!(duration_ms = result.duration_ms, "Job executed");
+    Ok(result)
+}
+
+/// Process a GXF envelope through the execution queue
+///
+/// Waits for a worker to run the job. A job cancelled before it started
+/// returns a `Cancelled` result. `provider_region` is checked as in
+/// [`process_envelope`].
+#[tracing::instrument(
+    skip_all,
+    fields(job_id = tracing::field::Empty, precision = tracing::field::Empty, priority = envelope.meta.priority, fingerprint = %envelope.fingerprint())
+)]
+pub async fn process_envelope_queued(
+    runtime: &RuntimeState,
+    envelope: GxfEnvelope,
+    provider_region: Option<String>,
+) -> Result<ExecutionResult> {
+    let job = admit_envelope(runtime, &envelope)?;
+
+    let outcome = runtime
+        .enqueue(job, Some(envelope.meta), provider_region)
+        .await
+        .await
+        .context("Execution queue dropped the job")?;
+    // Keep the violation in the chain so callers can report why the job was rejected
+    let result = outcome.map_err(|e| anyhow::Error::new(e).context("Compliance check failed"))?;
+    debug!(duration_ms = result.duration_ms, status = ?result.status, "Job finished");
+    Ok(result)
+}
+
+/// Validate an envelope and extract its job, recording the job on the current span
+fn admit_envelope(runtime: &RuntimeState, envelope: &GxfEnvelope) -> Result<GxfJob> {
+    envelope
+        .validate_with_skew(runtime.clock_skew_secs)
+        .context("Envelope validation failed")?;
+    envelope
+        .meta
+        .check_expiry_with_skew(runtime.clock_skew_secs)
+        .with_context(|| format!("Envelope expired ({}s old)", envelope.age_secs()))?;
+    #[cfg(feature = "vdf")]
+    runtime
+        .check_vdf(envelope)
+        .context("VDF check failed")?;
     let job = envelope
         .deserialize_job()
-        .map_err(|e| anyhow::anyhow!("Failed to deserialize job: {}", e))?;
+        .context("Failed to deserialize job")?;
+    let span = tracing::Span::current();
+    span.record("job_id", tracing::field::display(hex::encode(job.job_id.0)));
+    span.record("precision", tracing::field::debug(job.precision));
     job.validate()
-        .map_err(|e| anyhow::anyhow!("Job validation failed: {}", e))?;
-
-    runtime
-        .execute_job(job)
-        .await
-        .map_err(|e| anyhow::anyhow!("Compliance check failed: {}", e))
+        .context("Job validation failed")?;
+    Ok(job)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gix_common::GixError;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+    use metrics_util::MetricKind;
+
+    fn job_with_dimensions(dimensions: Option<&str>) -> GxfJob {
+        let mut job = GxfJob::new(JobId([5; 16]), PrecisionLevel::BF16, 1024);
+        if let Some(dimensions) = dimensions {
+            job.parameters.insert("dimensions".to_string(), dimensions.to_string());
+        }
+        job
+    }
+
+    #[test]
+    fn test_required_dimensions_match() {
+        let shape = ShapeRequirements {
+            required_dimensions: vec![4096, 4096],
+            ..Default::default()
+        };
+        assert!(shape.validate(&job_with_dimensions(Some("4096,4096"))).is_ok());
+        assert!(shape.validate(&job_with_dimensions(Some("4096, 4096"))).is_ok());
+    }
+
+    #[test]
+    fn test_required_dimensions_mismatch() {
+        let shape = ShapeRequirements {
+            required_dimensions: vec![4096, 4096],
+            ..Default::default()
+        };
+        for dimensions in [Some("4096,2048"), Some("4096"), Some("4096,x"), None] {
+            assert!(
+                matches!(shape.validate(&job_with_dimensions(dimensions)), Err(ComplianceError::ShapeViolation(_))),
+                "dimensions {:?} should be rejected",
+                dimensions
+            );
+        }
+    }
+
+    #[test]
+    fn test_required_dimensions_unconstrained_by_default() {
+        let shape = ShapeRequirements::default();
+        assert!(shape.required_dimensions.is_empty());
+        assert!(shape.validate(&job_with_dimensions(None)).is_ok());
+        assert!(shape.validate(&job_with_dimensions(Some("1,2,3"))).is_ok());
+    }
+
+    fn meta_with(fields: &[(&str, &str)]) -> GxfMetadata {
+        let mut meta = GxfMetadata::new(64).unwrap();
+        for (key, value) in fields {
+            meta.additional_fields.insert(key.to_string(), value.to_string());
+        }
+        meta
+    }
+
+    fn job_in_region(region: Option<&str>) -> GxfJob {
+        let mut job = GxfJob::new(JobId([6; 16]), PrecisionLevel::BF16, 1024);
+        if let Some(region) = region {
+            job.parameters.insert("region".to_string(), region.to_string());
+        }
+        job
+    }
+
+    #[test]
+    fn test_residency_region_in_parameters() {
+        let residency = ResidencyRequirements::default();
+        let meta = meta_with(&[]);
+        assert!(residency.validate_with_meta(&job_in_region(Some("EU")), &meta).is_ok());
+        assert!(residency.validate_with_meta(&job_in_region(Some("APAC")), &meta).is_err());
+    }
+
+    #[test]
+    fn test_residency_region_in_metadata() {
+        let residency = ResidencyRequirements::default();
+        let job = job_in_region(None);
+
+        // Plain validate never sees the metadata region
+        assert!(residency.validate(&job).is_ok());
+        assert!(residency.validate_with_meta(&job, &meta_with(&[("region", "US")])).is_ok());
+        assert!(matches!(
+            residency.validate_with_meta(&job, &meta_with(&[("region", "APAC")])),
+            Err(ComplianceError::ResidencyViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_residency_conflicting_regions() {
+        let residency = ResidencyRequirements::default();
+
+        let conflict = residency.validate_with_meta(&job_in_region(Some("US")), &meta_with(&[("region", "EU")]));
+        assert!(matches!(conflict, Err(ComplianceError::ResidencyViolation(_))));
+
+        // Provider region must agree with the job's region, wherever it came from
+        let job = job_in_region(Some("US"));
+        assert!(residency.validate_provider_region(&job, None, "US").is_ok());
+        assert!(residency.validate_provider_region(&job, None, "EU").is_err());
+        let meta = meta_with(&[("region", "EU")]);
+        assert!(residency.validate_provider_region(&job_in_region(None), Some(&meta), "US").is_err());
+        assert!(residency.validate_provider_region(&job_in_region(None), None, "APAC").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_provider_region_comes_from_the_auction_not_the_envelope() {
+        let runtime = RuntimeState::new();
+        let mut envelope = GxfEnvelope::from_job(job_in_region(Some("US")), 64).unwrap();
+        // A submitter-supplied field is not a provider region
+        envelope.meta.additional_fields.insert("provider_region".to_string(), "US".to_string());
+
+        assert!(process_envelope(&runtime, envelope.clone(), Some("EU")).await.is_err());
+        assert!(process_envelope(&runtime, envelope, Some("US")).await.is_ok());
+        assert_eq!(runtime.get_stats().await.total_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_output_hash_verifies_against_the_job() {
+        let runtime = RuntimeState::new();
+        let job = GxfJob::new(JobId([6; 16]), PrecisionLevel::BF16, 1024);
+        let envelope = GxfEnvelope::from_job(job.clone(), 128).unwrap();
+
+        let result = process_envelope(&runtime, envelope, None).await.unwrap();
+        assert_eq!(result.status, ExecutionStatus::Completed);
+        assert!(job.verify_output(&result.output_hash));
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_queued_and_executing_jobs() {
+        let cost_model = ExecutionCostModel { base_ms: 50, ..Default::default() };
+        let runtime = RuntimeState::new().with_cost_model(cost_model);
+        let first = runtime.enqueue(job_in_region(None), None, None).await;
+        let second = runtime.enqueue(job_with_dimensions(None), None, None).await;
+        runtime.spawn_workers(1);
+
+        tokio::time::timeout(Duration::from_secs(2), runtime.drain())
+            .await
+            .expect("queue did not drain");
+        assert_eq!(runtime.queue_len().await, 0);
+        assert_eq!(runtime.get_stats().await.total_executed, 2);
+        assert!(first.await.unwrap().is_ok());
+        assert!(second.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_envelope_maps_to_failed_precondition() {
+        let runtime = RuntimeState::new();
+        let envelope = GxfEnvelope::from_job(job_in_region(Some("APAC")), 64).unwrap();
+
+        let err = process_envelope(&runtime, envelope.clone(), None).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ComplianceError>(), Some(ComplianceError::ResidencyViolation(_))));
+        let status = gix_proto::errors::chain_to_status(err.as_ref());
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert!(status.message().contains("APAC"));
+
+        runtime.spawn_workers(1);
+        let err = process_envelope_queued(&runtime, envelope, None).await.unwrap_err();
+        let status = gix_proto::errors::chain_to_status(err.as_ref());
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[test]
+    fn test_compliance_error_converts_to_gix_error() {
+        let err: GixError = ComplianceError::ResidencyViolation("region 'EU' not allowed".to_string()).into();
+        match &err {
+            GixError::Compliance(detail) => assert!(detail.contains("region 'EU' not allowed")),
+            other => panic!("expected a compliance error, got {other:?}"),
+        }
+        assert_eq!(err.code(), 1006);
+    }
+
+    #[tokio::test]
+    async fn test_process_envelope_checks_metadata_region() {
+        let runtime = RuntimeState::new();
+        let mut envelope = GxfEnvelope::from_job(job_in_region(None), 64).unwrap();
+        envelope.meta.additional_fields.insert("region".to_string(), "APAC".to_string());
+
+        assert!(process_envelope(&runtime, envelope, None).await.is_err());
+        assert_eq!(runtime.get_stats().await.total_executed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_compliance_rejection_is_counted() {
+        let runtime = RuntimeState::new();
+        assert_eq!(runtime.get_stats().await.total_rejected, 0);
+
+        let job = job_in_region(Some("APAC"));
+        assert!(runtime.execute_job(job, None, None).await.is_err());
+
+        let stats = runtime.get_stats().await;
+        assert_eq!(stats.total_rejected, 1);
+        assert_eq!(stats.total_executed, 0);
+        assert_eq!(stats.jobs_by_precision.get(&PrecisionLevel::BF16), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_reset_stats_returns_snapshot() {
+        let runtime = RuntimeState::new();
+        runtime.execute_job(job_in_region(None), None, None).await.unwrap();
+        runtime.execute_job(job_with_dimensions(None), None, None).await.unwrap();
+
+        let snapshot = runtime.reset_stats().await;
+        assert_eq!(snapshot.total_executed, 2);
+        assert_eq!(snapshot.total_completed, 2);
+        assert_eq!(snapshot.jobs_by_precision.get(&PrecisionLevel::BF16), Some(&2));
+
+        let stats = runtime.snapshot_stats().await;
+        assert_eq!(stats.total_executed, 0);
+        assert_eq!(stats.total_completed, 0);
+        assert!(stats.jobs_by_precision.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execution_emits_metrics() {
+        DebuggingRecorder::per_thread().install().unwrap();
+
+        let runtime = RuntimeState::new();
+        let job = GxfJob::new(JobId([3; 16]), PrecisionLevel::FP8, 1024);
+        runtime.execute_job(job, None, None).await.unwrap();
+
+        let mut rejected = GxfJob::new(JobId([4; 16]), PrecisionLevel::INT8, 1024);
+        rejected.parameters.insert("region".to_string(), "APAC".to_string());
+        assert!(runtime.execute_job(rejected, None, None).await.is_err());
+
+        let snapshot = Snapshotter::current_thread_snapshot().unwrap().into_vec();
+        let counter = |name: &str| {
+            snapshot
+                .iter()
+                .find(|(key, _, _, _)| key.kind() == MetricKind::Counter && key.key().name() == name)
+                .map(|(_, _, _, value)| match value {
+                    DebugValue::Counter(count) => *count,
+                    other => panic!("unexpected value for {}: {:?}", name, other),
+                })
+        };
+
+        assert_eq!(counter("gix_jobs_executed_total"), Some(1));
+        assert_eq!(counter("gix_jobs_completed_total"), Some(1));
+        assert_eq!(counter("gix_jobs_rejected_total"), Some(1));
+        assert_eq!(counter("gix_jobs_by_precision"), Some(1));
+        assert!(snapshot.iter().any(|(key, _, _, value)| {
+            key.key().name() == "gix_execution_duration_ms" && matches!(value, DebugValue::Histogram(v) if v.len() == 1)
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job_never_executes() {
+        let runtime = RuntimeState::new();
+        let job = GxfJob::new(JobId([8; 16]), PrecisionLevel::BF16, 1024);
+        let outcome = runtime.enqueue(job.clone(), None, None).await;
+        assert_eq!(runtime.queue_len().await, 1);
+
+        assert!(runtime.cancel(&job.job_id).await);
+        assert!(!runtime.cancel(&job.job_id).await);
+        assert_eq!(outcome.await.unwrap().unwrap().status, ExecutionStatus::Cancelled);
+
+        // No worker finds anything to run
+        assert!(!runtime.run_next().await);
+        let stats = runtime.get_stats().await;
+        assert_eq!(stats.total_executed, 0);
+        assert_eq!(stats.total_cancelled, 1);
+    }
+
+    #[tokio::test]
+    async fn test_queue_runs_highest_priority_first() {
+        let runtime = RuntimeState::new();
+        let mut outcomes = Vec::new();
+        for (id, priority) in [(1u8, 50u8), (2, 200), (3, 50)] {
+            let job = GxfJob::new(JobId([id; 16]), PrecisionLevel::BF16, 64);
+            outcomes.push(runtime.enqueue(job, Some(GxfMetadata::new(priority).unwrap()), None).await);
+        }
+
+        // Highest priority first, then submission order
+        for expected in [1, 0, 2] {
+            assert!(runtime.run_next().await);
+            let result = outcomes[expected].try_recv().unwrap().unwrap();
+            assert_eq!(result.job_id, JobId([expected as u8 + 1; 16]));
+        }
+        assert_eq!(runtime.get_stats().await.total_executed, 3);
+
+        // A job that has already run can no longer be cancelled
+        assert!(!runtime.cancel(&JobId([2; 16])).await);
+    }
+
+    #[tokio::test]
+    async fn test_cost_model_scales_with_precision_and_batch() {
+        let model = ExecutionCostModel::default();
+        let int8 = GxfJob::new(JobId([1; 16]), PrecisionLevel::INT8, 8192);
+        let bf16 = GxfJob::new(JobId([2; 16]), PrecisionLevel::BF16, 8192);
+
+        // INT8 keeps the original ceil(seq_len / 1000) + 10 formula
+        assert_eq!(model.duration_ms(&int8), 19);
+        assert_eq!(model.duration_ms(&bf16), 28);
+
+        // A batch of 32 runs 1 + 31 * 0.25 = 8.75 times the compute
+        let batched = |mut job: GxfJob| {
+            job.parameters.insert(BATCH_SIZE_PARAM.to_string(), "32".to_string());
+            job
+        };
+        let (int8, bf16) = (batched(int8), batched(bf16));
+        assert_eq!(model.duration_ms(&int8), 10 + 79);
+        assert_eq!(model.duration_ms(&bf16), 10 + 158);
+
+        let runtime = RuntimeState::new();
+        let int8_result = runtime.execute_job(int8, None, None).await.unwrap();
+        let bf16_result = runtime.execute_job(bf16, None, None).await.unwrap();
+        assert!(bf16_result.duration_ms > int8_result.duration_ms);
+    }
+}
@@ -3,20 +3,89 @@
 //! Enclave execution runtime that securely executes jobs within encrypted
 //! envelopes. Supports both simulation mode and production enclave mode.
 
-use gsee_runtime::RuntimeState;
+use gsee_runtime::{ComplianceError, ExecutionCostModel, RuntimeState};
 use anyhow::{Context, Result};
-use gix_gxf::GxfEnvelope;
-use gix_proto::v1::{ExecuteJobRequest, ExecuteJobResponse, ExecutionStatus as ProtoExecutionStatus, GetRuntimeStatsRequest, GetRuntimeStatsResponse, JobId as ProtoJobId};
-use gix_proto::{ExecutionService, ExecutionServiceServer};
+use clap::Parser;
+use gix_gxf::{GxfEnvelope, DEFAULT_MAX_ENVELOPE_BYTES};
+use gix_common::config::{env_lookup, setting};
+use gix_common::JobId;
+use gix_proto::v1::{CancelJobRequest, CancelJobResponse, HealthCheckRequest, HealthCheckResponse, ServingStatus, ExecuteJobRequest, ExecuteJobResponse, ExecutionStatus as ProtoExecutionStatus, GetRuntimeStatsRequest, GetRuntimeStatsResponse, JobId as ProtoJobId, RejectionReason};
+use gix_proto::errors::{chain_to_status, decoding_limit, draining, oversized_envelope, to_status};
+use gix_proto::reflection::reflection_service;
+use gix_proto::tls::server_tls_config;
+use gix_proto::trace::trace_id_or_new;
+use gix_proto::{ExecutionService, ExecutionServiceServer, HealthService, HealthServiceServer};
+use metrics_exporter_prometheus::PrometheusBuilder;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::signal;
 use tonic::{Request, Response, Status};
-use tracing::info;
+use tracing::{info, info_span, Instrument};
 
 const GSEE_SERVER_ADDR: &str = "0.0.0.0:50053";
+const METRICS_ADDR: &str = "0.0.0.0:9003";
+const SERVICE_NAME: &str = "gix.v1.ExecutionService";
+
+/// Bind addresses and limits, overridable through the environment
+struct ServiceConfig {
+    /// gRPC listen address
+    addr: SocketAddr,
+    /// Prometheus metrics listen address
+    metrics_addr: SocketAddr,
+    /// Largest encoded envelope accepted, also sizing the gRPC decode limit
+    ///
+    /// Decompressed payloads are bounded separately by `MAX_PAYLOAD_BYTES`.
+    max_envelope_bytes: usize,
+}
+
+impl ServiceConfig {
+    /// Read `GIX_GSEE_ADDR`, `GIX_GSEE_METRICS_ADDR` and `GIX_GSEE_MAX_ENVELOPE_BYTES`, defaulting unset ones
+    fn load(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        Ok(ServiceConfig {
+            addr: setting(&lookup, "GIX_GSEE_ADDR", GSEE_SERVER_ADDR)?,
+            metrics_addr: setting(&lookup, "GIX_GSEE_METRICS_ADDR", METRICS_ADDR)?,
+            max_envelope_bytes: setting(&lookup, "GIX_GSEE_MAX_ENVELOPE_BYTES", &DEFAULT_MAX_ENVELOPE_BYTES.to_string())?,
+        })
+    }
+}
 
 /// Runtime service implementation
 struct ExecutionServiceImpl {
     runtime: Arc<RuntimeState>,
+    /// Largest encoded envelope accepted
+    max_envelope_bytes: usize,
+    /// Set on shutdown to turn away new jobs
+    draining: Arc<AtomicBool>,
+}
+
+/// Response for a job that failed compliance checks
+fn rejection_response(violation: &ComplianceError) -> ExecuteJobResponse {
+    let (reason, detail) = match violation {
+        ComplianceError::PrecisionViolation(detail) => (RejectionReason::Precision, detail),
+        ComplianceError::ShapeViolation(detail) => (RejectionReason::Shape, detail),
+        ComplianceError::ResidencyViolation(detail) => (RejectionReason::Residency, detail),
+    };
+    
+    ExecuteJobResponse {
+        status: ProtoExecutionStatus::Rejected as i32,
+        success: false,
+        error: violation.to_string(),
+        rejection_reason: reason as i32,
+        rejection_detail: detail.clone(),
+        ..Default::default()
+    }
+}
+
+impl ExecutionServiceImpl {
+    /// Serve over gRPC, decoding requests up to the envelope limit
+    fn into_server(self) -> ExecutionServiceServer<Self> {
+        let limit = decoding_limit(self.max_envelope_bytes);
+        ExecutionServiceServer::new(self).max_decoding_message_size(limit)
+    }
 }
 
 #[tonic::async_trait]
@@ -25,22 +94,40 @@ impl ExecutionService for ExecutionServiceImpl {
         &self,
         request: Request<ExecuteJobRequest>,
     ) -> Result<Response<ExecuteJobResponse>, Status> {
+        if let Some(status) = draining(&self.draining) {
+            return Err(status);
+        }
+        let span = info_span!("execute_job", trace_id = %trace_id_or_new(&request));
         let req = request.into_inner();
+        if let Some(status) = oversized_envelope(req.envelope.len(), self.max_envelope_bytes) {
+            return Err(status);
+        }
         
         // Deserialize GXF envelope from bytes
         let envelope = GxfEnvelope::from_json(&req.envelope)
-            .map_err(|e| Status::invalid_argument(format!("Invalid envelope: {}", e)))?;
+            .map_err(|e| to_status(&e))?;
         
-        // Execute job
-        let result = gsee_runtime::process_envelope(&self.runtime, envelope)
+        // Queue the job and wait for a worker to execute it
+        let provider_region = Some(req.provider_region).filter(|region| !region.is_empty());
+        let result = match gsee_runtime::process_envelope_queued(&self.runtime, envelope, provider_region)
+            .instrument(span)
             .await
-            .map_err(|e| Status::internal(format!("Execution failed: {}", e)))?;
+        {
+            Ok(result) => result,
+            // Compliance rejections are an answer, not a failure of the call
+            Err(e) => match e.downcast_ref::<ComplianceError>() {
+                Some(violation) => return Ok(Response::new(rejection_response(violation))),
+                None => return Err(chain_to_status(e.as_ref())),
+            },
+        };
         
         // Convert execution status
         let status = match result.status {
             gsee_runtime::ExecutionStatus::Completed => ProtoExecutionStatus::Completed,
             gsee_runtime::ExecutionStatus::Failed(_) => ProtoExecutionStatus::Failed,
             gsee_runtime::ExecutionStatus::Rejected(_) => ProtoExecutionStatus::Rejected,
+            gsee_runtime::ExecutionStatus::Timeout => ProtoExecutionStatus::Timeout,
+            gsee_runtime::ExecutionStatus::Cancelled => ProtoExecutionStatus::Cancelled,
         };
         
         Ok(Response::new(ExecuteJobResponse {
@@ -50,6 +137,8 @@ impl ExecutionService for ExecutionServiceImpl {
             output_hash: result.output_hash.to_vec(),
             success: matches!(result.status, gsee_runtime::ExecutionStatus::Completed),
             error: String::new(),
+            rejection_reason: RejectionReason::None as i32,
+            rejection_detail: String::new(),
         }))
     }
 
@@ -70,12 +159,126 @@ impl ExecutionService for ExecutionServiceImpl {
             total_failed: stats.total_failed,
             total_rejected: stats.total_rejected,
             jobs_by_precision,
+            total_timeout: stats.total_timeout,
+            total_cancelled: stats.total_cancelled,
+        }))
+    }
+
+    async fn cancel_job(
+        &self,
+        request: Request<CancelJobRequest>,
+    ) -> Result<Response<CancelJobResponse>, Status> {
+        let job_id = request
+            .into_inner()
+            .job_id
+            .ok_or_else(|| Status::invalid_argument("Missing job ID"))?;
+        let job_id: [u8; 16] = job_id.id.as_slice().try_into()
+            .map_err(|_| Status::invalid_argument("Job ID must be 16 bytes"))?;
+        
+        let cancelled = self.runtime.cancel(&JobId(job_id)).await;
+        Ok(Response::new(CancelJobResponse { cancelled }))
+    }
+}
+
+/// Health service implementation
+///
+/// The server only starts once the runtime is initialized, so a reachable
+/// daemon is always serving.
+struct HealthServiceImpl;
+
+#[tonic::async_trait]
+impl HealthService for HealthServiceImpl {
+    async fn check(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        let status = match request.into_inner().service.as_str() {
+            "" | SERVICE_NAME => ServingStatus::Serving,
+            _ => ServingStatus::ServiceUnknown,
+        };
+        
+        Ok(Response::new(HealthCheckResponse {
+            status: status as i32,
         }))
     }
 }
 
+/// Command-line arguments
+#[derive(Parser)]
+#[command(about = "GSEE runtime service")]
+struct Args {
+    /// PEM certificate chain for serving gRPC over TLS
+    #[arg(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key for serving gRPC over TLS
+    #[arg(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// Accept envelopes that expired up to this many seconds ago (clock skew)
+    #[arg(long, default_value_t = 0)]
+    clock_skew_secs: u64,
+
+    /// JSON file overriding the default execution cost model
+    #[arg(long)]
+    cost_model: Option<PathBuf>,
+
+    /// Number of workers executing queued jobs
+    #[arg(long, default_value_t = 4)]
+    workers: usize,
+
+    /// Abort jobs that run longer than this many milliseconds
+    #[arg(long)]
+    execution_timeout_ms: Option<u64>,
+
+    /// Keep serving for this many seconds after a shutdown signal, rejecting
+    /// new requests as draining, before waiting out in-flight ones
+    #[arg(long, default_value_t = 5)]
+    drain_grace_secs: u64,
+
+    /// Require envelopes to carry a VDF proof of at least this many iterations
+    #[cfg(feature = "vdf")]
+    #[arg(long)]
+    vdf_min_iterations: Option<u64>,
+}
+
+/// Build the runtime the arguments describe, without starting its workers
+fn build_runtime(args: &Args) -> Result<RuntimeState> {
+    let cost_model = load_cost_model(args.cost_model.as_ref())?;
+    info!("Execution cost model: {:?}", cost_model);
+    
+    let mut runtime = RuntimeState::new()
+        .with_cost_model(cost_model)
+        .with_clock_skew(args.clock_skew_secs);
+    if let Some(timeout_ms) = args.execution_timeout_ms {
+        info!("Execution timeout: {}ms", timeout_ms);
+        runtime = runtime.with_execution_timeout(std::time::Duration::from_millis(timeout_ms));
+    }
+    #[cfg(feature = "vdf")]
+    if let Some(iterations) = args.vdf_min_iterations {
+        info!("Envelopes require VDF proofs of {} iterations", iterations);
+        runtime = runtime.with_vdf_min_iterations(iterations);
+    }
+    Ok(runtime)
+}
+
+/// Load cost model coefficients from a JSON file (missing fields keep their defaults)
+fn load_cost_model(path: Option<&PathBuf>) -> Result<ExecutionCostModel> {
+    let Some(path) = path else {
+        return Ok(ExecutionCostModel::default());
+    };
+    
+    let contents = std::fs::read_to_string(path)
+        .context(format!("Failed to read cost model config: {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .context(format!("Invalid cost model config: {}", path.display()))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = ServiceConfig::load(env_lookup)?;
+
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -85,25 +288,215 @@ async fn main() -> Result<()> {
 
     info!("GSEE Runtime Service starting...");
 
-    let runtime = Arc::new(RuntimeState::new());
-    info!("Runtime initialized");
+    // Initialize Prometheus metrics exporter
+    let metrics_addr = config.metrics_addr;
+    
+    info!("Starting Prometheus metrics endpoint on {}", metrics_addr);
+    
+    PrometheusBuilder::new()
+        .with_http_listener(metrics_addr)
+        .install()
+        .context("Failed to install Prometheus recorder")?;
+
+    let runtime = Arc::new(build_runtime(&args)?);
+    runtime.spawn_workers(args.workers.max(1));
+    info!("Runtime initialized with {} execution worker(s)", args.workers.max(1));
 
     // Create service implementation
+    let draining = Arc::new(AtomicBool::new(false));
     let service = ExecutionServiceImpl {
         runtime: runtime.clone(),
+        max_envelope_bytes: config.max_envelope_bytes,
+        draining: draining.clone(),
     };
 
     // Start gRPC server
-    let addr = GSEE_SERVER_ADDR.parse()
-        .context("Invalid server address")?;
+    let addr = config.addr;
     
     info!("Starting gRPC server on {}", addr);
     
-    tonic::transport::Server::builder()
-        .add_service(ExecutionServiceServer::new(service))
-        .serve(addr)
+    let mut server = tonic::transport::Server::builder();
+    if let (Some(cert), Some(key)) = (&args.tls_cert, &args.tls_key) {
+        info!("Serving gRPC over TLS");
+        server = server
+            .tls_config(server_tls_config(cert, key)?)
+            .context("Invalid TLS configuration")?;
+    }
+    
+    server
+        .add_service(service.into_server())
+        .add_service(HealthServiceServer::new(HealthServiceImpl))
+        .add_service(reflection_service().context("Failed to build reflection service")?)
+        .serve_with_shutdown(
+            addr,
+            shutdown_signal(draining, Duration::from_secs(args.drain_grace_secs), ctrl_c()),
+        )
         .await
         .context("Server error")?;
 
+    // Run jobs still queued by requests whose callers went away
+    info!("Draining the execution queue ({} job(s) waiting)...", runtime.queue_len().await);
+    runtime.drain().await;
+    let stats = runtime.get_stats().await;
+    info!(
+        "Runtime drained: {} executed ({} completed, {} failed, {} rejected, {} timed out, {} cancelled)",
+        stats.total_executed, stats.total_completed, stats.total_failed, stats.total_rejected,
+        stats.total_timeout, stats.total_cancelled
+    );
+
+    info!("GSEE Runtime Service stopped");
     Ok(())
 }
+
+/// Wait for CTRL+C
+async fn ctrl_c() {
+    signal::ctrl_c()
+        .await
+        .expect("Failed to install CTRL+C signal handler");
+}
+
+/// Wait for shutdown signal and drain in-flight executions
+///
+/// New jobs are rejected from the signal on, for `grace` before this future
+/// resolves. `serve_with_shutdown` then stops accepting connections and
+/// waits for running executions to complete, after which `main` drains the
+/// execution queue.
+async fn shutdown_signal<S: Future<Output = ()>>(draining: Arc<AtomicBool>, grace: Duration, signal: S) {
+    signal.await;
+    draining.store(true, Ordering::SeqCst);
+
+    info!("Shutdown signal received, draining for {:?}...", grace);
+    tokio::time::sleep(grace).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_signal_returns_on_signal() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+        let draining = Arc::new(AtomicBool::new(false));
+        let shutdown = tokio::spawn(shutdown_signal(draining.clone(), Duration::ZERO, async {
+            rx.await.ok();
+        }));
+        tx.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown)
+            .await
+            .expect("shutdown future did not return")
+            .unwrap();
+        assert!(draining.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_draining_rejects_new_jobs_and_finishes_in_flight() {
+        use gix_gxf::{GxfJob, PrecisionLevel};
+
+        let cost_model = ExecutionCostModel { base_ms: 200, ..Default::default() };
+        let runtime = Arc::new(RuntimeState::new().with_cost_model(cost_model));
+        runtime.spawn_workers(1);
+        let draining = Arc::new(AtomicBool::new(false));
+        let service = Arc::new(ExecutionServiceImpl {
+            runtime,
+            max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES,
+            draining: draining.clone(),
+        });
+        let request = |i: u8| {
+            let job = GxfJob::new(JobId([i; 16]), PrecisionLevel::INT8, 1024);
+            let envelope = GxfEnvelope::from_job(job, 64).unwrap();
+            Request::new(ExecuteJobRequest { envelope: envelope.to_json().unwrap(), provider_region: String::new() })
+        };
+
+        let in_flight = tokio::spawn({
+            let service = service.clone();
+            let request = request(11);
+            async move { service.execute_job(request).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        draining.store(true, Ordering::SeqCst);
+        let status = service.execute_job(request(12)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(status.message(), "draining");
+
+        let response = in_flight.await.unwrap().expect("in-flight job completes").into_inner();
+        assert!(response.success);
+        assert_eq!(response.status, ProtoExecutionStatus::Completed as i32);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_job_reports_timeout_status() {
+        use gix_gxf::{GxfJob, PrecisionLevel};
+
+        let runtime = Arc::new(RuntimeState::new().with_execution_timeout(Duration::from_millis(1)));
+        runtime.spawn_workers(1);
+        let service = ExecutionServiceImpl { runtime: runtime.clone(), max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES, draining: Arc::new(AtomicBool::new(false)) };
+
+        // A long BF16 job takes far longer than the 1ms timeout
+        let job = GxfJob::new(JobId([9; 16]), PrecisionLevel::BF16, 8192);
+        let envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        let response = service
+            .execute_job(Request::new(ExecuteJobRequest { envelope: envelope.to_json().unwrap(), provider_region: String::new() }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.status, ProtoExecutionStatus::Timeout as i32);
+        assert!(!response.success);
+
+        let stats = service
+            .get_runtime_stats(Request::new(GetRuntimeStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(stats.total_executed, 1);
+        assert_eq!(stats.total_timeout, 1);
+        assert_eq!(stats.total_completed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_residency_violation_reports_rejection_reason() {
+        use gix_gxf::{GxfJob, PrecisionLevel};
+
+        let runtime = Arc::new(RuntimeState::new());
+        runtime.spawn_workers(1);
+        let service = ExecutionServiceImpl { runtime, max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES, draining: Arc::new(AtomicBool::new(false)) };
+
+        // Only US and EU are allowed by default
+        let mut job = GxfJob::new(JobId([10; 16]), PrecisionLevel::BF16, 1024);
+        job.parameters.insert("region".to_string(), "APAC".to_string());
+        let envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        let response = service
+            .execute_job(Request::new(ExecuteJobRequest { envelope: envelope.to_json().unwrap(), provider_region: String::new() }))
+            .await
+            .expect("a compliance rejection is not an RPC error")
+            .into_inner();
+
+        assert!(!response.success);
+        assert_eq!(response.status, ProtoExecutionStatus::Rejected as i32);
+        assert_eq!(response.rejection_reason, RejectionReason::Residency as i32);
+        assert!(response.rejection_detail.contains("Region 'APAC' not in allowed regions"));
+    }
+
+    #[cfg(feature = "vdf")]
+    #[tokio::test]
+    async fn test_vdf_flag_rejects_unproven_envelopes() {
+        use gix_gxf::{GxfJob, PrecisionLevel};
+
+        let args = Args::parse_from(["gsee-runtime", "--vdf-min-iterations", "100"]);
+        let runtime = Arc::new(build_runtime(&args).unwrap());
+        runtime.spawn_workers(1);
+        let service = ExecutionServiceImpl { runtime, max_envelope_bytes: DEFAULT_MAX_ENVELOPE_BYTES, draining: Arc::new(AtomicBool::new(false)) };
+
+        let job = GxfJob::new(JobId([12; 16]), PrecisionLevel::BF16, 1024);
+        let envelope = GxfEnvelope::from_job(job, 64).unwrap();
+        let status = service
+            .execute_job(Request::new(ExecuteJobRequest { envelope: envelope.to_json().unwrap(), provider_region: String::new() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        assert_eq!(service.runtime.get_stats().await.total_executed, 0);
+    }
+}
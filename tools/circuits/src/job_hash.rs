@@ -0,0 +1,129 @@
+//! Job hash commitment circuit
+//!
+//! Statement: "I know a `GxfJob` whose canonical bytes hash (Blake3) to the
+//! public commitment `C`." The commitment lets the auction and runtime refer to
+//! a job without handling its contents.
+//!
+//! The `mock-prover` backend checks the same statement but its proof carries
+//! the witness in the clear, so it provides soundness without privacy.
+
+use gix_gxf::GxfJob;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Circuit errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    #[error("Failed to encode witness: {0}")]
+    Witness(String),
+}
+
+/// Public commitment to a job: Blake3 of its canonical bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JobCommitment(pub [u8; 32]);
+
+impl JobCommitment {
+    /// Commit to a job
+    pub fn of(job: &GxfJob) -> Result<Self, CircuitError> {
+        Ok(JobCommitment(gix_crypto::hash_blake3(&witness_bytes(job)?)))
+    }
+}
+
+/// Proving backend that produced a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofSystem {
+    /// Transparent mock backend (not zero-knowledge)
+    Mock,
+}
+
+/// Proof of knowledge of a job preimage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proof {
+    /// Backend that produced the proof
+    pub system: ProofSystem,
+    /// Commitment the proof was produced for
+    pub commitment: JobCommitment,
+    /// Backend-specific proof bytes
+    pub bytes: Vec<u8>,
+}
+
+/// Encode the witness exactly as the commitment hashes it
+fn witness_bytes(job: &GxfJob) -> Result<Vec<u8>, CircuitError> {
+    job.canonical_bytes().map_err(|e| CircuitError::Witness(e.to_string()))
+}
+
+/// Prove knowledge of `job` for its commitment
+#[cfg(feature = "mock-prover")]
+pub fn prove(job: &GxfJob) -> Result<Proof, CircuitError> {
+    let witness = witness_bytes(job)?;
+
+    Ok(Proof {
+        system: ProofSystem::Mock,
+        commitment: JobCommitment(gix_crypto::hash_blake3(&witness)),
+        bytes: witness,
+    })
+}
+
+/// Verify that `proof` shows knowledge of a job committed to by `commitment`
+#[cfg(feature = "mock-prover")]
+pub fn verify(commitment: &JobCommitment, proof: &Proof) -> bool {
+    match proof.system {
+        ProofSystem::Mock => {
+            // The witness must be a well-formed job in canonical form
+            let Ok(job) = serde_json::from_slice::<GxfJob>(&proof.bytes) else {
+                return false;
+            };
+            if witness_bytes(&job).ok().as_deref() != Some(proof.bytes.as_slice()) {
+                return false;
+            }
+
+            proof.commitment == *commitment
+                && gix_crypto::hash_blake3(&proof.bytes) == commitment.0
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock-prover"))]
+mod tests {
+    use super::*;
+    use gix_common::JobId;
+    use gix_gxf::PrecisionLevel;
+
+    fn job(id: u8) -> GxfJob {
+        let mut job = GxfJob::new(JobId([id; 16]), PrecisionLevel::BF16, 2048);
+        job.parameters.insert("model".to_string(), "llama-70b".to_string());
+        job
+    }
+
+    #[test]
+    fn test_valid_proof_verifies() {
+        let job = job(1);
+        let commitment = JobCommitment::of(&job).unwrap();
+
+        let proof = prove(&job).unwrap();
+        assert_eq!(proof.commitment, commitment);
+        assert!(verify(&commitment, &proof));
+
+        // Proofs survive serialization
+        let decoded: Proof = serde_json::from_slice(&serde_json::to_vec(&proof).unwrap()).unwrap();
+        assert!(verify(&commitment, &decoded));
+    }
+
+    #[test]
+    fn test_wrong_commitment_rejected() {
+        let proof = prove(&job(1)).unwrap();
+        let other = JobCommitment::of(&job(2)).unwrap();
+        assert!(!verify(&other, &proof));
+
+        // Relabelling the proof does not help without a matching witness
+        let mut forged = proof.clone();
+        forged.commitment = other;
+        assert!(!verify(&other, &forged));
+
+        // Nor does a tampered witness
+        let mut tampered = proof.clone();
+        let last = tampered.bytes.len() - 2;
+        tampered.bytes[last] ^= 0x01;
+        assert!(!verify(&proof.commitment, &tampered));
+    }
+}
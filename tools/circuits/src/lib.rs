@@ -6,20 +6,14 @@
 //! - Nova circuits
 //!
 //! Used for privacy-preserving verification of job execution and routing.
+//!
+//! The [`job_hash`] circuit proves knowledge of a `GxfJob` whose Blake3 hash
+//! equals a public commitment. It currently runs on a transparent mock
+//! backend (`mock-prover` feature) until a real proving system is wired in.
+
+pub mod job_hash;
 
 // TODO: Implement ZK circuits
 // - Job execution proofs
 // - Routing correctness proofs
 // - Auction integrity proofs
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        // Placeholder test
-    }
-}
-
-
-
-
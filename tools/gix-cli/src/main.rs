@@ -7,14 +7,19 @@ mod wallet;
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use gcam_node::{AuctionReceipt, ComputeProvider};
 use gix_common::JobId;
 use gix_crypto::pqc::dilithium;
-use gix_gxf::{GxfEnvelope, GxfJob, GxfMetadata, PrecisionLevel};
-use gix_proto::v1::{GetAuctionStatsRequest, RunAuctionRequest};
-use gix_proto::AuctionServiceClient;
-use rand::Rng;
+use gix_gxf::{GxfEnvelope, GxfJob, JobPriority, PrecisionLevel};
+use gix_proto::v1::{AddProviderRequest, ExecuteJobRequest, ExecutionStatus, GetAuctionStatsRequest, ListProvidersRequest, RejectionReason, RouteEnvelopeRequest, RunAuctionRequest};
+use gix_proto::auth::AuthToken;
+use gix_proto::tls::connect_channel;
+use gix_proto::{AuctionServiceClient, ExecutionServiceClient, ProviderAdminServiceClient, RouterServiceClient};
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// GIX Command Line Interface
 #[derive(Parser)]
@@ -23,6 +28,14 @@ use std::time::{SystemTime, UNIX_EPOCH};
 #[command(version = "0.1.0")]
 #[command(about = "Global Intelligence Exchange CLI", long_about = None)]
 struct Cli {
+    /// Print machine-readable JSON instead of formatted text
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// PEM CA certificate for connecting to services over TLS (use https:// addresses)
+    #[arg(long, global = true)]
+    tls_ca: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,6 +47,14 @@ enum Commands {
         /// Output path for wallet file (default: ~/.gix/wallet.json)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Dilithium security level (2, 3 or 5)
+        #[arg(short, long, default_value = "3")]
+        level: u8,
+
+        /// Save the wallet in the compact binary format (implied by a .gwl output path)
+        #[arg(long)]
+        binary: bool,
     },
     
     /// Submit a job to the GIX network
@@ -49,11 +70,43 @@ enum Commands {
         #[arg(short, long)]
         node: Option<String>,
         
-        /// Job priority (0-255)
-        #[arg(short, long, default_value = "128")]
+        /// Job priority (0-255, or low/normal/high/critical)
+        #[arg(short, long, default_value = "128", value_parser = parse_priority)]
+        priority: u8,
+
+        /// Attach a VDF proof of this many iterations (for nodes requiring proof of work)
+        #[arg(long)]
+        pow_iterations: Option<u64>,
+
+        /// Save the node's signed auction receipt to this path
+        #[arg(long)]
+        receipt: Option<String>,
+    },
+    
+    /// Route a job through the AJR router
+    Route {
+        /// Path to job YAML file
+        job_file: String,
+        
+        /// AJR router address (default: http://127.0.0.1:50051)
+        #[arg(short, long)]
+        node: Option<String>,
+        
+        /// Job priority (0-255, or low/normal/high/critical)
+        #[arg(short, long, default_value = "128", value_parser = parse_priority)]
         priority: u8,
     },
     
+    /// Execute a job directly on a GSEE runtime
+    Execute {
+        /// Path to job YAML file
+        job_file: String,
+        
+        /// GSEE runtime address (default: http://127.0.0.1:50053)
+        #[arg(short, long)]
+        node: Option<String>,
+    },
+    
     /// Query auction statistics
     Status {
         /// GCAM node address (default: http://127.0.0.1:50052)
@@ -66,6 +119,57 @@ enum Commands {
         /// Wallet file path (default: ~/.gix/wallet.json)
         #[arg(short = 'f', long)]
         wallet: Option<String>,
+
+        /// Export the public key as an armored block to this path
+        #[arg(long)]
+        export_pubkey: Option<String>,
+    },
+
+    /// Import or export the provider set of a GCAM node
+    Providers {
+        #[command(subcommand)]
+        action: ProvidersCommand,
+    },
+
+    /// Verify a signed auction receipt against the node's public key
+    VerifyReceipt {
+        /// Receipt file written by `gix submit --receipt`
+        receipt_file: String,
+
+        /// Armored Dilithium public key of the node that ran the auction
+        #[arg(long)]
+        node_key: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProvidersCommand {
+    /// Add every provider in a JSON array file, replacing existing ones with the same SLP ID
+    Import {
+        /// JSON file holding an array of providers
+        file: String,
+
+        /// GCAM node address (default: http://127.0.0.1:50052)
+        #[arg(short, long)]
+        node: Option<String>,
+
+        /// Wallet used to authenticate with nodes that require it
+        #[arg(short, long)]
+        wallet: Option<String>,
+    },
+
+    /// Write the node's providers to a JSON array file
+    Export {
+        /// Output JSON file
+        file: String,
+
+        /// GCAM node address (default: http://127.0.0.1:50052)
+        #[arg(short, long)]
+        node: Option<String>,
+
+        /// Wallet used to authenticate with nodes that require it
+        #[arg(short, long)]
+        wallet: Option<String>,
     },
 }
 
@@ -89,22 +193,155 @@ struct JobSpec {
 fn default_token_count() -> u32 { 128 }
 fn default_batch_size() -> u32 { 1 }
 
+/// Options of the submit command beyond its inputs
+struct SubmitOptions {
+    /// Job priority (0-255)
+    priority: u8,
+    /// VDF iterations to attach, if any
+    pow_iterations: Option<u64>,
+    /// Where to save the signed auction receipt, if anywhere
+    receipt: Option<String>,
+}
+
+/// JSON output of the submit command
+#[derive(Debug, Serialize)]
+struct SubmitOutput {
+    /// Whether the auction matched the job
+    success: bool,
+    /// Job ID (hex)
+    job_id: String,
+    /// Matched SLP ID
+    slp_id: String,
+    /// Selected lane ID
+    lane_id: u32,
+    /// Clearing price (micro-tokens)
+    price: u64,
+    /// Route path
+    route: Vec<String>,
+    /// Time spent computing the VDF proof, if one was attached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vdf_elapsed_ms: Option<u64>,
+    /// Path the signed receipt was saved to, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt_file: Option<String>,
+    /// Error message when unsuccessful
+    error: String,
+}
+
+/// JSON output of the route command
+#[derive(Debug, Serialize)]
+struct RouteOutput {
+    /// Whether the router accepted the envelope
+    success: bool,
+    /// Job ID (hex)
+    job_id: String,
+    /// Selected lane ID
+    lane_id: u32,
+    /// Error message when unsuccessful
+    error: String,
+}
+
+/// JSON output of the execute command
+#[derive(Debug, Serialize)]
+struct ExecuteOutput {
+    /// Whether execution completed
+    success: bool,
+    /// Job ID (hex)
+    job_id: String,
+    /// Execution status name
+    status: String,
+    /// Execution duration in milliseconds
+    duration_ms: u64,
+    /// Output hash (hex)
+    output_hash: String,
+    /// Error message when unsuccessful
+    error: String,
+    /// Why the job was rejected, if it was
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rejection_reason: Option<String>,
+}
+
+/// JSON output of the status command
+#[derive(Debug, Serialize)]
+struct StatusOutput {
+    /// Total auctions processed
+    total_auctions: u64,
+    /// Total matches found
+    total_matches: u64,
+    /// Total volume (micro-tokens)
+    total_volume: u64,
+    /// Lowest clearing price (micro-tokens)
+    min_price: u64,
+    /// Highest clearing price (micro-tokens)
+    max_price: u64,
+    /// Mean clearing price (micro-tokens)
+    avg_price: f64,
+    /// Matches by precision
+    matches_by_precision: BTreeMap<String, u64>,
+    /// Matches by lane
+    matches_by_lane: BTreeMap<u32, u64>,
+}
+
+/// A provider entry rejected during import
+#[derive(Debug, Serialize)]
+struct ImportFailure {
+    /// Position of the entry in the import file
+    index: usize,
+    /// SLP ID of the entry, if it could be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slp_id: Option<String>,
+    /// Why the entry was rejected
+    error: String,
+}
+
+/// JSON output of the providers import command
+#[derive(Debug, Serialize)]
+struct ImportOutput {
+    /// Number of providers added
+    imported: usize,
+    /// Entries that were rejected
+    failed: Vec<ImportFailure>,
+}
+
+/// Print a progress line unless JSON output is requested
+fn progress(json: bool, message: &str) {
+    if !json {
+        println!("{}", message.cyan());
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Keygen { output } => {
-            handle_keygen(output).await?;
+        Commands::Keygen { output, level, binary } => {
+            handle_keygen(output, level, binary).await?;
+        }
+        Commands::Submit { job_file, wallet, node, priority, pow_iterations, receipt } => {
+            let options = SubmitOptions { priority, pow_iterations, receipt };
+            handle_submit(job_file, wallet, node, options, cli.json, cli.tls_ca.as_deref()).await?;
+        }
+        Commands::Route { job_file, node, priority } => {
+            handle_route(job_file, node, priority, cli.json, cli.tls_ca.as_deref()).await?;
         }
-        Commands::Submit { job_file, wallet, node, priority } => {
-            handle_submit(job_file, wallet, node, priority).await?;
+        Commands::Execute { job_file, node } => {
+            handle_execute(job_file, node, cli.json, cli.tls_ca.as_deref()).await?;
         }
         Commands::Status { node } => {
-            handle_status(node).await?;
+            handle_status(node, cli.json, cli.tls_ca.as_deref()).await?;
+        }
+        Commands::Wallet { wallet, export_pubkey } => {
+            handle_wallet_info(wallet, export_pubkey).await?;
+        }
+        Commands::Providers { action: ProvidersCommand::Import { file, node, wallet } } => {
+            handle_providers_import(file, node, wallet, cli.json, cli.tls_ca.as_deref()).await?;
+        }
+        Commands::Providers { action: ProvidersCommand::Export { file, node, wallet } } => {
+            handle_providers_export(file, node, wallet, cli.json, cli.tls_ca.as_deref()).await?;
         }
-        Commands::Wallet { wallet } => {
-            handle_wallet_info(wallet).await?;
+        Commands::VerifyReceipt { receipt_file, node_key } => {
+            handle_verify_receipt(receipt_file, node_key, cli.json)?;
         }
     }
     
@@ -112,16 +349,21 @@ async fn main() -> Result<()> {
 }
 
 /// Handle keygen command
-async fn handle_keygen(output: Option<String>) -> Result<()> {
-    println!("{}", "Generating new Dilithium3 keypair...".cyan());
+async fn handle_keygen(output: Option<String>, level: u8, binary: bool) -> Result<()> {
+    let level = dilithium::DilithiumLevel::try_from(level)?;
+    println!("{}", format!("Generating new {} keypair...", level).cyan());
     
-    let keypair = dilithium::KeyPair::generate();
+    let keypair = dilithium::KeyPair::generate_with_level(level);
     
     let wallet_path = output.unwrap_or_else(|| {
         wallet::get_default_wallet_path().to_string_lossy().to_string()
     });
     
-    wallet::save_wallet(&keypair, &wallet_path)?;
+    if binary {
+        wallet::save_wallet_as(&keypair, &wallet_path, wallet::WalletFormat::Binary)?;
+    } else {
+        wallet::save_wallet(&keypair, &wallet_path)?;
+    }
     
     println!("{}", "✓ Keypair generated successfully!".green());
     println!("Wallet saved to: {}", wallet_path.bright_white());
@@ -137,10 +379,14 @@ async fn handle_submit(
     job_file: String,
     wallet_path: Option<String>,
     node_addr: Option<String>,
-    priority: u8,
+    options: SubmitOptions,
+    json: bool,
+    tls_ca: Option<&Path>,
 ) -> Result<()> {
+    let SubmitOptions { priority, pow_iterations, receipt: receipt_path } = options;
+    
     // Load job spec from YAML
-    println!("{}", format!("Loading job from {}...", job_file).cyan());
+    progress(json, &format!("Loading job from {}...", job_file));
     let job_spec = load_job_spec(&job_file)?;
     
     // Load wallet
@@ -148,73 +394,287 @@ async fn handle_submit(
         wallet::get_default_wallet_path().to_string_lossy().to_string()
     });
     
-    println!("{}", "Loading wallet...".cyan());
+    progress(json, "Loading wallet...");
     let keypair = wallet::load_wallet(&wallet_path)?;
     
     // Create GXF job
-    let job_id = JobId(rand::random());
-    let precision = parse_precision(&job_spec.precision)?;
-    
-    let job = GxfJob::new(job_id, precision, job_spec.kv_cache_seq_len);
+    let job = build_job(&job_spec)?;
     
     // Create envelope from job
-    println!("{}", "Creating envelope...".cyan());
-    let envelope = GxfEnvelope::from_job(job.clone(), priority)?;
-    
-    // Sign the payload
-    println!("{}", "Signing payload...".cyan());
-    let signature = dilithium::sign_detached(&envelope.payload, &keypair.secret)?;
+    progress(json, "Creating envelope...");
+    let mut envelope = GxfEnvelope::from_job(job.clone(), priority)?;
+    envelope.validate().context("Envelope failed validation")?;
+    
+    // Compute the proof of work before signing so the signature covers it
+    let vdf_elapsed = match pow_iterations {
+        Some(iterations) => {
+            let (proved, elapsed) = compute_vdf_proof(envelope, iterations, json).await?;
+            envelope = proved;
+            if !json {
+                println!("{}", format!("✓ VDF proof computed in {:.1}s", elapsed.as_secs_f64()).green());
+            }
+            Some(elapsed)
+        }
+        None => None,
+    };
+    
+    // Sign the canonical envelope bytes and check them against the wallet's public key
+    progress(json, "Signing envelope...");
+    let signed_bytes = envelope.canonical_bytes()?;
+    let signature = dilithium::sign_detached(&signed_bytes, &keypair.secret)?;
+    dilithium::verify_detached(&signed_bytes, &signature, &keypair.public)
+        .context("Wallet keypair does not verify its own signature")?;
+    if !json {
+        println!("Envelope signed ({}, {} bytes)", keypair.level(), signature.bytes.len());
+        println!("Envelope created at {}", envelope.meta.created_at_datetime()?);
+    }
     
     // Connect to GCAM node
     let node_addr = node_addr.unwrap_or_else(|| "http://127.0.0.1:50052".to_string());
-    println!("{}", format!("Connecting to {}...", node_addr).cyan());
+    progress(json, &format!("Connecting to {}...", node_addr));
     
-    let mut client = AuctionServiceClient::connect(node_addr.clone())
+    let channel = connect_channel(&node_addr, tls_ca)
         .await
         .context("Failed to connect to GCAM node")?;
+    let mut client = AuctionServiceClient::new(channel);
     
     // Submit job
-    println!("{}", "Submitting job to auction...".cyan());
-    let request = tonic::Request::new(RunAuctionRequest {
+    progress(json, "Submitting job to auction...");
+    let mut request = tonic::Request::new(RunAuctionRequest {
         job: serde_json::to_vec(&job)?,
         priority: priority as u32,
+        source_slp: String::new(),
+        // Only send the envelope when it carries a proof the node may require
+        envelope: if vdf_elapsed.is_some() { envelope.to_json()? } else { Vec::new() },
     });
     
+    // Authenticate with the wallet key; ignored by nodes without authentication
+    AuthToken::now(&keypair)?.insert_into(request.metadata_mut());
+    
     let response = client.run_auction(request)
         .await
         .context("Failed to run auction")?
         .into_inner();
     
+    if let Some(path) = &receipt_path {
+        anyhow::ensure!(
+            !response.receipt.is_empty(),
+            "Node did not return a receipt (it must be started with --receipt-key)"
+        );
+        std::fs::write(path, &response.receipt)
+            .context(format!("Failed to write receipt: {}", path))?;
+    }
+    
+    let output = SubmitOutput {
+        success: response.success,
+        job_id: hex::encode(response.job_id.map(|id| id.id).unwrap_or_default()),
+        slp_id: response.slp_id.map(|id| id.id).unwrap_or_default(),
+        lane_id: response.lane_id.map(|id| id.id).unwrap_or_default(),
+        price: response.price,
+        route: response.route,
+        error: response.error,
+        vdf_elapsed_ms: vdf_elapsed.map(|elapsed| elapsed.as_millis() as u64),
+        receipt_file: receipt_path,
+    };
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    
     // Display results
     println!();
-    if response.success {
+    if output.success {
         println!("{}", "✓ Job submitted successfully!".green().bold());
         println!();
         println!("{}", "Auction Results:".yellow().bold());
-        println!("  Job ID:     {}", hex::encode(&response.job_id.unwrap().id));
-        println!("  SLP ID:     {}", response.slp_id.unwrap().id);
-        println!("  Lane ID:    {}", response.lane_id.unwrap().id);
-        println!("  Price:      {} μGIX", response.price.to_string().bright_white());
-        println!("  Route:      {}", response.route.join(" → "));
+        println!("  Job ID:     {}", output.job_id);
+        println!("  SLP ID:     {}", output.slp_id);
+        println!("  Lane ID:    {}", output.lane_id);
+        println!("  Price:      {} μGIX", output.price.to_string().bright_white());
+        println!("  Route:      {}", output.route.join(" → "));
+        if let Some(path) = &output.receipt_file {
+            println!("  Receipt:    {}", path);
+        }
     } else {
         println!("{}", "✗ Job submission failed!".red().bold());
-        println!("Error: {}", response.error);
+        println!("Error: {}", output.error);
+    }
+    
+    Ok(())
+}
+
+/// Attach a VDF proof to `envelope` off the async runtime, spinning while it runs
+async fn compute_vdf_proof(
+    mut envelope: GxfEnvelope,
+    iterations: u64,
+    json: bool,
+) -> Result<(GxfEnvelope, Duration)> {
+    const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+    
+    let started = Instant::now();
+    let mut task = tokio::task::spawn_blocking(move || {
+        envelope.attach_vdf_proof(iterations).map(|()| envelope)
+    });
+    
+    let mut ticker = tokio::time::interval(Duration::from_millis(100));
+    let mut frame = 0;
+    let envelope = loop {
+        tokio::select! {
+            result = &mut task => break result.context("VDF task panicked")??,
+            _ = ticker.tick() => {
+                if !json {
+                    print!("\r{} Computing VDF proof ({} iterations)... {:.1}s",
+                        SPINNER[frame % SPINNER.len()], iterations, started.elapsed().as_secs_f64());
+                    std::io::stdout().flush().ok();
+                }
+                frame += 1;
+            }
+        }
+    };
+    if !json {
+        println!();
+    }
+    
+    Ok((envelope, started.elapsed()))
+}
+
+/// Handle route command
+async fn handle_route(
+    job_file: String,
+    node_addr: Option<String>,
+    priority: u8,
+    json: bool,
+    tls_ca: Option<&Path>,
+) -> Result<()> {
+    progress(json, &format!("Loading job from {}...", job_file));
+    let job = build_job(&load_job_spec(&job_file)?)?;
+    let envelope = GxfEnvelope::from_job(job.clone(), priority)?;
+    
+    let node_addr = node_addr.unwrap_or_else(|| "http://127.0.0.1:50051".to_string());
+    progress(json, &format!("Connecting to {}...", node_addr));
+    
+    let channel = connect_channel(&node_addr, tls_ca)
+        .await
+        .context("Failed to connect to AJR router")?;
+    let mut client = RouterServiceClient::new(channel);
+    
+    progress(json, "Routing envelope...");
+    let request = tonic::Request::new(RouteEnvelopeRequest {
+        envelope: envelope.to_json()?,
+    });
+    
+    let response = client.route_envelope(request)
+        .await
+        .context("Failed to route envelope")?
+        .into_inner();
+    
+    let output = RouteOutput {
+        success: response.success,
+        job_id: hex::encode(job.job_id.0),
+        lane_id: response.lane_id.map(|id| id.id).unwrap_or_default(),
+        error: response.error,
+    };
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    
+    println!();
+    if output.success {
+        println!("{}", "✓ Envelope routed successfully!".green().bold());
+        println!("  Job ID:     {}", output.job_id);
+        println!("  Lane ID:    {}", output.lane_id.to_string().bright_white());
+    } else {
+        println!("{}", "✗ Routing failed!".red().bold());
+        println!("Error: {}", output.error);
+    }
+    
+    Ok(())
+}
+
+/// Handle execute command
+async fn handle_execute(job_file: String, node_addr: Option<String>, json: bool, tls_ca: Option<&Path>) -> Result<()> {
+    progress(json, &format!("Loading job from {}...", job_file));
+    let job = build_job(&load_job_spec(&job_file)?)?;
+    let envelope = GxfEnvelope::from_job(job, 128)?;
+    
+    let node_addr = node_addr.unwrap_or_else(|| "http://127.0.0.1:50053".to_string());
+    progress(json, &format!("Connecting to {}...", node_addr));
+    
+    let channel = connect_channel(&node_addr, tls_ca)
+        .await
+        .context("Failed to connect to GSEE runtime")?;
+    let mut client = ExecutionServiceClient::new(channel);
+    
+    progress(json, "Executing job...");
+    let request = tonic::Request::new(ExecuteJobRequest {
+        envelope: envelope.to_json()?,
+        provider_region: String::new(),
+    });
+    
+    let response = client.execute_job(request)
+        .await
+        .context("Failed to execute job")?
+        .into_inner();
+    
+    let status = ExecutionStatus::try_from(response.status)
+        .map(|status| status.as_str_name().trim_start_matches("EXECUTION_STATUS_").to_string())
+        .unwrap_or_else(|_| format!("UNKNOWN({})", response.status));
+    let rejection_reason = match RejectionReason::try_from(response.rejection_reason) {
+        Ok(RejectionReason::None) => None,
+        Ok(reason) => Some(reason.as_str_name().trim_start_matches("REJECTION_REASON_").to_string()),
+        Err(_) => Some(format!("UNKNOWN({})", response.rejection_reason)),
+    };
+    
+    let output = ExecuteOutput {
+        success: response.success,
+        job_id: hex::encode(response.job_id.map(|id| id.id).unwrap_or_default()),
+        status,
+        duration_ms: response.duration_ms,
+        output_hash: hex::encode(&response.output_hash),
+        error: response.error,
+        rejection_reason,
+    };
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    
+    println!();
+    if output.success {
+        println!("{}", "✓ Job executed successfully!".green().bold());
+    } else {
+        println!("{}", "✗ Job execution failed!".red().bold());
+    }
+    println!("  Job ID:       {}", output.job_id);
+    println!("  Status:       {}", output.status);
+    println!("  Duration:     {} ms", output.duration_ms);
+    println!("  Output hash:  {}", output.output_hash);
+    if let Some(reason) = &output.rejection_reason {
+        println!("  Rejected for: {}", reason);
+    }
+    if !output.error.is_empty() {
+        println!("Error: {}", output.error);
     }
     
     Ok(())
 }
 
 /// Handle status command
-async fn handle_status(node_addr: Option<String>) -> Result<()> {
+async fn handle_status(node_addr: Option<String>, json: bool, tls_ca: Option<&Path>) -> Result<()> {
     let node_addr = node_addr.unwrap_or_else(|| "http://127.0.0.1:50052".to_string());
     
-    println!("{}", format!("Connecting to {}...", node_addr).cyan());
+    progress(json, &format!("Connecting to {}...", node_addr));
     
-    let mut client = AuctionServiceClient::connect(node_addr)
+    let channel = connect_channel(&node_addr, tls_ca)
         .await
         .context("Failed to connect to GCAM node")?;
+    let mut client = AuctionServiceClient::new(channel);
     
-    println!("{}", "Fetching auction statistics...".cyan());
+    progress(json, "Fetching auction statistics...");
     
     let request = tonic::Request::new(GetAuctionStatsRequest {});
     let response = client.get_auction_stats(request)
@@ -222,26 +682,48 @@ async fn handle_status(node_addr: Option<String>) -> Result<()> {
         .context("Failed to get stats")?
         .into_inner();
     
+    let output = StatusOutput {
+        total_auctions: response.total_auctions,
+        total_matches: response.total_matches,
+        total_volume: response.total_volume,
+        min_price: response.min_price,
+        max_price: response.max_price,
+        avg_price: response.avg_price,
+        matches_by_precision: response.matches_by_precision.into_iter().collect(),
+        matches_by_lane: response.matches_by_lane.into_iter().collect(),
+    };
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    
     // Display stats
     println!();
     println!("{}", "=== GCAM Auction Statistics ===".yellow().bold());
     println!();
-    println!("Total Auctions:  {}", response.total_auctions.to_string().bright_white());
-    println!("Total Matches:   {}", response.total_matches.to_string().bright_white());
-    println!("Total Volume:    {} μGIX", response.total_volume.to_string().bright_white());
+    println!("Total Auctions:  {}", output.total_auctions.to_string().bright_white());
+    println!("Total Matches:   {}", output.total_matches.to_string().bright_white());
+    println!("Total Volume:    {} μGIX", output.total_volume.to_string().bright_white());
+    if output.total_matches > 0 {
+        println!(
+            "Clearing Price:  min {} / avg {:.1} / max {} μGIX",
+            output.min_price, output.avg_price, output.max_price
+        );
+    }
     
-    if !response.matches_by_precision.is_empty() {
+    if !output.matches_by_precision.is_empty() {
         println!();
         println!("{}", "Matches by Precision:".cyan());
-        for (precision, count) in &response.matches_by_precision {
+        for (precision, count) in &output.matches_by_precision {
             println!("  {:<10} {}", precision, count);
         }
     }
     
-    if !response.matches_by_lane.is_empty() {
+    if !output.matches_by_lane.is_empty() {
         println!();
         println!("{}", "Matches by Lane:".cyan());
-        for (lane_id, count) in &response.matches_by_lane {
+        for (lane_id, count) in &output.matches_by_lane {
             println!("  Lane {:<5} {}", lane_id, count);
         }
     }
@@ -250,7 +732,7 @@ async fn handle_status(node_addr: Option<String>) -> Result<()> {
 }
 
 /// Handle wallet info command
-async fn handle_wallet_info(wallet_path: Option<String>) -> Result<()> {
+async fn handle_wallet_info(wallet_path: Option<String>, export_pubkey: Option<String>) -> Result<()> {
     let wallet_path = wallet_path.unwrap_or_else(|| {
         wallet::get_default_wallet_path().to_string_lossy().to_string()
     });
@@ -266,22 +748,213 @@ async fn handle_wallet_info(wallet_path: Option<String>) -> Result<()> {
     println!();
     println!("Public Key Size:  {} bytes", keypair.public.bytes.len());
     println!("Secret Key Size:  {} bytes", keypair.secret.bytes.len());
-    println!("Algorithm:        Dilithium3 (NIST Level 3 PQC)");
+    println!("Algorithm:        {} (NIST Level {} PQC)", keypair.level(), u8::from(keypair.level()));
+    
+    if let Some(export_path) = export_pubkey {
+        wallet::export_public_key(&keypair, &export_path)?;
+        println!();
+        println!("{}", format!("✓ Public key exported to {}", export_path).green());
+    }
+    
+    Ok(())
+}
+
+/// Connect to a GCAM node's provider admin service
+async fn connect_provider_admin(
+    node_addr: Option<String>,
+    json: bool,
+    tls_ca: Option<&Path>,
+) -> Result<ProviderAdminServiceClient<tonic::transport::Channel>> {
+    let node_addr = node_addr.unwrap_or_else(|| "http://127.0.0.1:50052".to_string());
+    progress(json, &format!("Connecting to {}...", node_addr));
+    
+    let channel = connect_channel(&node_addr, tls_ca)
+        .await
+        .context("Failed to connect to GCAM node")?;
+    Ok(ProviderAdminServiceClient::new(channel))
+}
+
+/// Wrap `message` in a request, authenticated with the wallet key if one is given
+fn admin_request<T>(message: T, keypair: Option<&dilithium::KeyPair>) -> Result<tonic::Request<T>> {
+    let mut request = tonic::Request::new(message);
+    if let Some(keypair) = keypair {
+        AuthToken::now(keypair)?.insert_into(request.metadata_mut());
+    }
+    Ok(request)
+}
+
+/// Handle providers import command
+///
+/// Each entry is checked and submitted on its own; a bad entry is reported
+/// and skipped rather than aborting the rest of the file.
+async fn handle_providers_import(
+    file: String,
+    node_addr: Option<String>,
+    wallet_path: Option<String>,
+    json: bool,
+    tls_ca: Option<&Path>,
+) -> Result<()> {
+    progress(json, &format!("Loading providers from {}...", file));
+    let contents = std::fs::read_to_string(&file)
+        .context(format!("Failed to read provider file: {}", file))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)
+        .context("Provider file must hold a JSON array")?;
+    let keypair = wallet_path.map(|path| wallet::load_wallet(&path)).transpose()?;
+    let mut client = connect_provider_admin(node_addr, json, tls_ca).await?;
+    
+    let mut output = ImportOutput { imported: 0, failed: Vec::new() };
+    for (index, entry) in entries.into_iter().enumerate() {
+        let slp_id = entry.get("slp_id").and_then(|id| id.as_str()).map(str::to_string);
+        let result = async {
+            let provider: ComputeProvider = serde_json::from_value(entry)?;
+            provider.validate()?;
+            let request = admin_request(
+                AddProviderRequest { provider: serde_json::to_vec(&provider)? },
+                keypair.as_ref(),
+            )?;
+            client.add_provider(request).await.map_err(|status| anyhow::anyhow!(status.message().to_string()))?;
+            anyhow::Ok(())
+        }
+        .await;
+        
+        match result {
+            Ok(()) => output.imported += 1,
+            Err(e) => output.failed.push(ImportFailure { index, slp_id, error: e.to_string() }),
+        }
+    }
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", format!("✓ Imported {} provider(s)", output.imported).green());
+        for failure in &output.failed {
+            let slp_id = failure.slp_id.as_deref().unwrap_or("?");
+            println!("{}", format!("✗ Entry {} ({}): {}", failure.index, slp_id, failure.error).red());
+        }
+    }
+    
+    anyhow::ensure!(
+        output.failed.is_empty(),
+        "{} of {} provider entries were rejected",
+        output.failed.len(),
+        output.failed.len() + output.imported
+    );
+    Ok(())
+}
+
+/// Handle providers export command
+async fn handle_providers_export(
+    file: String,
+    node_addr: Option<String>,
+    wallet_path: Option<String>,
+    json: bool,
+    tls_ca: Option<&Path>,
+) -> Result<()> {
+    let keypair = wallet_path.map(|path| wallet::load_wallet(&path)).transpose()?;
+    let mut client = connect_provider_admin(node_addr, json, tls_ca).await?;
+    
+    progress(json, "Fetching providers...");
+    let response = client.list_providers(admin_request(ListProvidersRequest {}, keypair.as_ref())?)
+        .await
+        .context("Failed to list providers")?
+        .into_inner();
+    let providers = response
+        .providers
+        .iter()
+        .map(|bytes| serde_json::from_slice(bytes))
+        .collect::<Result<Vec<ComputeProvider>, _>>()
+        .context("Node returned a malformed provider")?;
+    
+    std::fs::write(&file, serde_json::to_string_pretty(&providers)?)
+        .context(format!("Failed to write provider file: {}", file))?;
+    
+    if json {
+        let output = serde_json::json!({ "exported": providers.len(), "file": file });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", format!("✓ Exported {} provider(s) to {}", providers.len(), file).green());
+    }
+    
+    Ok(())
+}
+
+/// Handle verify-receipt command
+fn handle_verify_receipt(receipt_file: String, node_key_file: String, json: bool) -> Result<()> {
+    let receipt_bytes = std::fs::read(&receipt_file)
+        .context(format!("Failed to read receipt: {}", receipt_file))?;
+    let receipt = AuctionReceipt::from_json(&receipt_bytes)?;
+    let armored = std::fs::read_to_string(&node_key_file)
+        .context(format!("Failed to read node key: {}", node_key_file))?;
+    let node_key = dilithium::PublicKey::from_armored(&armored)
+        .context(format!("Invalid node key: {}", node_key_file))?;
+    
+    receipt
+        .verify(&node_key)
+        .context(format!("Receipt {} is not signed by {}", receipt_file, node_key_file))?;
+    
+    let matched = &receipt.auction_match;
+    if json {
+        let output = serde_json::json!({
+            "valid": true,
+            "job_id": hex::encode(matched.job_id.0),
+            "slp_id": matched.slp_id.as_str(),
+            "lane_id": matched.lane_id.as_u32(),
+            "price": matched.price,
+            "route": matched.route,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    
+    println!("{}", "✓ Receipt signature is valid".green().bold());
+    println!();
+    println!("  Job ID:     {}", hex::encode(matched.job_id.0));
+    println!("  SLP ID:     {}", matched.slp_id);
+    println!("  Lane ID:    {}", matched.lane_id);
+    println!("  Price:      {} μGIX", matched.price.to_string().bright_white());
+    println!("  Route:      {}", matched.route.join(" → "));
     
     Ok(())
 }
 
 /// Load job specification from YAML file
+///
+/// Parse errors name the offending field and its line and column.
 fn load_job_spec(path: &str) -> Result<JobSpec> {
     let content = std::fs::read_to_string(path)
         .context(format!("Failed to read job file: {}", path))?;
     
     let spec: JobSpec = serde_yaml::from_str(&content)
-        .context("Failed to parse job YAML")?;
+        .map_err(|e| anyhow::anyhow!("Failed to parse job YAML {}: {}", path, e))?;
+    parse_precision(&spec.precision)
+        .context(format!("Invalid job file: {}", path))?;
     
     Ok(spec)
 }
 
+/// Build a GXF job from a job specification
+fn build_job(spec: &JobSpec) -> Result<GxfJob> {
+    let precision = parse_precision(&spec.precision)?;
+    let mut job = GxfJob::new(JobId(rand::random()), precision, spec.kv_cache_seq_len);
+    
+    job.parameters.insert("model".to_string(), spec.model.clone());
+    job.parameters.insert("token_count".to_string(), spec.token_count.to_string());
+    job.parameters.insert("batch_size".to_string(), spec.batch_size.to_string());
+    
+    job.validate().context("Job failed validation")?;
+    Ok(job)
+}
+
+/// Parse a priority given as a number or a `JobPriority` name
+fn parse_priority(s: &str) -> Result<u8, String> {
+    if let Ok(value) = s.parse::<u8>() {
+        return Ok(value);
+    }
+    s.parse::<JobPriority>()
+        .map(|priority| priority.as_u8())
+        .map_err(|_| format!("expected 0-255 or low/normal/high/critical, got {}", s))
+}
+
 /// Parse precision level from string
 fn parse_precision(s: &str) -> Result<PrecisionLevel> {
     match s.to_uppercase().as_str() {
@@ -289,6 +962,54 @@ fn parse_precision(s: &str) -> Result<PrecisionLevel> {
         "FP8" => Ok(PrecisionLevel::FP8),
         "E5M2" => Ok(PrecisionLevel::E5M2),
         "INT8" => Ok(PrecisionLevel::INT8),
-        _ => Err(anyhow::anyhow!("Invalid precision level: {}", s)),
+        _ => Err(anyhow::anyhow!("unknown precision '{}', expected one of BF16/FP8/E5M2/INT8", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write `content` to a temporary job file and try to load it
+    fn load_spec_from(name: &str, content: &str) -> Result<JobSpec> {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, content)?;
+        let result = load_job_spec(path.to_str().unwrap());
+        std::fs::remove_file(path).ok();
+        result
+    }
+
+    #[test]
+    fn test_load_job_spec_reports_bad_field() {
+        let err = load_spec_from(
+            "test_job_bad_field.yaml",
+            "model: llama\nprecision: BF16\nkv_cache_seq_len: lots\n",
+        )
+        .unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("kv_cache_seq_len"), "{}", message);
+        assert!(message.contains("line 3"), "{}", message);
+    }
+
+    #[test]
+    fn test_load_job_spec_rejects_unknown_precision() {
+        let err = load_spec_from(
+            "test_job_bad_precision.yaml",
+            "model: llama\nprecision: fp4\nkv_cache_seq_len: 1024\n",
+        )
+        .unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(
+            message.contains("unknown precision 'fp4', expected one of BF16/FP8/E5M2/INT8"),
+            "{}",
+            message
+        );
+
+        let spec = load_spec_from(
+            "test_job_good_precision.yaml",
+            "model: llama\nprecision: fp8\nkv_cache_seq_len: 1024\n",
+        )
+        .unwrap();
+        assert_eq!(spec.precision, "fp8");
     }
 }
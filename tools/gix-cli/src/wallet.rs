@@ -1,24 +1,19 @@
 //! Wallet management for GIX CLI
 //!
-//! Handles secure storage and loading of Dilithium keypairs.
+//! Handles secure storage and loading of Dilithium keypairs. The file
+//! encoding lives in `gix_crypto::wallet`, shared with the services that
+//! load wallet keys.
 
 use anyhow::{Context, Result};
 use gix_crypto::pqc::dilithium::KeyPair;
-use serde::{Deserialize, Serialize};
+use gix_crypto::wallet::{decode_wallet, encode_wallet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
-/// Wallet structure stored in JSON
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Wallet {
-    /// Version for future compatibility
-    pub version: u32,
-    /// Dilithium keypair
-    pub keypair: KeyPair,
-}
+pub use gix_crypto::wallet::WalletFormat;
 
 /// Get the default wallet directory (~/.gix)
 pub fn get_default_wallet_dir() -> PathBuf {
@@ -31,14 +26,14 @@ pub fn get_default_wallet_path() -> PathBuf {
     get_default_wallet_dir().join("wallet.json")
 }
 
-/// Save a wallet to a file with secure permissions
+/// Save a wallet to a file with secure permissions, in the format implied by its extension
 pub fn save_wallet(keypair: &KeyPair, path: &str) -> Result<()> {
-    let wallet = Wallet {
-        version: 1,
-        keypair: keypair.clone(),
-    };
-    
-    let wallet_json = serde_json::to_string_pretty(&wallet)
+    save_wallet_as(keypair, path, WalletFormat::for_path(Path::new(path)))
+}
+
+/// Save a wallet to a file with secure permissions in the given format
+pub fn save_wallet_as(keypair: &KeyPair, path: &str, format: WalletFormat) -> Result<()> {
+    let wallet_bytes = encode_wallet(keypair, format)
         .context("Failed to serialize wallet")?;
     
     // Ensure parent directory exists
@@ -49,7 +44,7 @@ pub fn save_wallet(keypair: &KeyPair, path: &str) -> Result<()> {
     }
     
     // Write wallet file
-    fs::write(path, wallet_json)
+    fs::write(path, wallet_bytes)
         .context(format!("Failed to write wallet to: {}", path))?;
     
     // Set restrictive permissions (600 - owner read/write only) on Unix
@@ -65,7 +60,7 @@ pub fn save_wallet(keypair: &KeyPair, path: &str) -> Result<()> {
     Ok(())
 }
 
-/// Load a wallet from a file
+/// Load a wallet from a file, detecting its format from the magic prefix
 pub fn load_wallet(path: &str) -> Result<KeyPair> {
     // Check if file exists
     if !Path::new(path).exists() {
@@ -90,27 +85,26 @@ pub fn load_wallet(path: &str) -> Result<KeyPair> {
     }
     
     // Read and parse wallet
-    let wallet_json = fs::read_to_string(path)
+    let wallet_bytes = fs::read(path)
         .context(format!("Failed to read wallet from: {}", path))?;
     
-    let wallet: Wallet = serde_json::from_str(&wallet_json)
-        .context("Failed to parse wallet JSON")?;
-    
-    // Check version
-    if wallet.version != 1 {
-        return Err(anyhow::anyhow!(
-            "Unsupported wallet version: {}. Expected version 1.",
-            wallet.version
-        ));
-    }
+    decode_wallet(&wallet_bytes)
+        .context(format!("Failed to parse wallet: {}", path))
+}
+
+/// Export the wallet's public key as an armored block
+pub fn export_public_key(keypair: &KeyPair, path: &str) -> Result<()> {
+    fs::write(path, keypair.public.to_armored())
+        .context(format!("Failed to write public key to: {}", path))?;
     
-    Ok(wallet.keypair)
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use gix_crypto::pqc::dilithium;
+    use gix_crypto::wallet::BINARY_WALLET_MAGIC;
     
     #[test]
     fn test_wallet_save_load_roundtrip() {
@@ -135,6 +129,75 @@ mod tests {
         std::fs::remove_file(wallet_path).ok();
     }
     
+    #[test]
+    fn test_binary_wallet_roundtrip() {
+        let wallet_path = std::env::temp_dir().join("test_wallet_binary.gwl");
+        let wallet_path_str = wallet_path.to_str().unwrap();
+        
+        let original_keypair = dilithium::KeyPair::generate_with_level(dilithium::DilithiumLevel::Level5);
+        save_wallet(&original_keypair, wallet_path_str).unwrap();
+        
+        let bytes = std::fs::read(&wallet_path).unwrap();
+        assert!(bytes.starts_with(BINARY_WALLET_MAGIC));
+        
+        let loaded_keypair = load_wallet(wallet_path_str).unwrap();
+        assert_eq!(original_keypair.public, loaded_keypair.public);
+        assert_eq!(original_keypair.secret, loaded_keypair.secret);
+        
+        std::fs::remove_file(wallet_path).ok();
+    }
+    
+    #[test]
+    fn test_load_wallet_detects_format() {
+        let keypair = dilithium::KeyPair::generate();
+        let json_path = std::env::temp_dir().join("test_wallet_detect_json.gwl");
+        let binary_path = std::env::temp_dir().join("test_wallet_detect_binary.json");
+        
+        // The explicit format wins over the extension; loading goes by content
+        save_wallet_as(&keypair, json_path.to_str().unwrap(), WalletFormat::Json).unwrap();
+        save_wallet_as(&keypair, binary_path.to_str().unwrap(), WalletFormat::Binary).unwrap();
+        
+        let json_len = std::fs::metadata(&json_path).unwrap().len();
+        let binary_len = std::fs::metadata(&binary_path).unwrap().len();
+        assert!(binary_len < json_len, "{} >= {}", binary_len, json_len);
+        
+        for path in [&json_path, &binary_path] {
+            let loaded = load_wallet(path.to_str().unwrap()).unwrap();
+            assert_eq!(loaded.public, keypair.public);
+            assert_eq!(loaded.secret, keypair.secret);
+            std::fs::remove_file(path).ok();
+        }
+    }
+    
+    #[test]
+    fn test_wallet_preserves_dilithium_level() {
+        let wallet_path = std::env::temp_dir().join("test_wallet_level5.json");
+        let wallet_path_str = wallet_path.to_str().unwrap();
+        
+        let original_keypair = dilithium::KeyPair::generate_with_level(dilithium::DilithiumLevel::Level5);
+        save_wallet(&original_keypair, wallet_path_str).unwrap();
+        
+        let loaded_keypair = load_wallet(wallet_path_str).unwrap();
+        assert_eq!(loaded_keypair.level(), dilithium::DilithiumLevel::Level5);
+        
+        std::fs::remove_file(wallet_path).ok();
+    }
+    
+    #[test]
+    fn test_export_public_key_roundtrip() {
+        let export_path = std::env::temp_dir().join("test_wallet_pubkey.pem");
+        let export_path_str = export_path.to_str().unwrap();
+        
+        let keypair = dilithium::KeyPair::generate();
+        export_public_key(&keypair, export_path_str).unwrap();
+        
+        let armored = std::fs::read_to_string(&export_path).unwrap();
+        let public = dilithium::PublicKey::from_armored(&armored).unwrap();
+        assert_eq!(public, keypair.public);
+        
+        std::fs::remove_file(export_path).ok();
+    }
+    
     #[test]
     fn test_load_nonexistent_wallet() {
         let result = load_wallet("/nonexistent/path/wallet.json");
@@ -0,0 +1,45 @@
+//! CLI tests against running daemons
+//!
+//! These tests are ignored by default. Start the services on their default
+//! ports and run them with `cargo test -p gix-cli -- --ignored`.
+
+use anyhow::Result;
+use std::process::Command;
+
+/// Path to the sample job specification
+fn sample_job() -> String {
+    format!("{}/../../examples/job_sample.yaml", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Run the `gix` binary with JSON output and parse the result
+fn run_gix_json(args: &[&str]) -> Result<serde_json::Value> {
+    let output = Command::new(env!("CARGO_BIN_EXE_gix")).arg("--json").args(args).output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "gix {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+#[test]
+#[ignore = "requires a running AJR router on 127.0.0.1:50051"]
+fn test_route_against_running_router() -> Result<()> {
+    let output = run_gix_json(&["route", &sample_job(), "--priority", "200"])?;
+
+    assert_eq!(output["success"], true);
+    assert_eq!(output["lane_id"], 0, "high priority jobs use the Flash lane");
+    Ok(())
+}
+
+#[test]
+#[ignore = "requires a running GSEE runtime on 127.0.0.1:50053"]
+fn test_execute_against_running_runtime() -> Result<()> {
+    let output = run_gix_json(&["execute", &sample_job()])?;
+
+    assert_eq!(output["success"], true);
+    assert_eq!(output["status"], "COMPLETED");
+    assert_eq!(output["output_hash"].as_str().unwrap().len(), 64);
+    Ok(())
+}
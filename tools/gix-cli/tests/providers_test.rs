@@ -0,0 +1,131 @@
+//! CLI provider import/export tests
+//!
+//! These tests run the `gix` binary against an in-process GCAM provider
+//! admin service.
+
+use anyhow::Result;
+use gcam_node::{AuctionEngine, ComputeProvider, ProviderStatus};
+use gix_common::SlpId;
+use gix_gxf::PrecisionLevel;
+use gix_proto::v1::{AddProviderRequest, AddProviderResponse, ListProvidersRequest, ListProvidersResponse};
+use gix_proto::{ProviderAdminService, ProviderAdminServiceServer};
+use tokio::net::TcpListener;
+use tokio::process::Command;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+/// Provider admin service backed by a real engine
+struct EngineAdmin {
+    engine: AuctionEngine,
+}
+
+#[tonic::async_trait]
+impl ProviderAdminService for EngineAdmin {
+    async fn add_provider(
+        &self,
+        request: Request<AddProviderRequest>,
+    ) -> Result<Response<AddProviderResponse>, Status> {
+        let provider: ComputeProvider = serde_json::from_slice(&request.into_inner().provider)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        self.engine
+            .add_provider(provider)
+            .await
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(AddProviderResponse { success: true, error: String::new() }))
+    }
+
+    async fn list_providers(
+        &self,
+        _request: Request<ListProvidersRequest>,
+    ) -> Result<Response<ListProvidersResponse>, Status> {
+        let providers = self.engine
+            .get_providers()
+            .await
+            .iter()
+            .map(|provider| serde_json::to_vec(provider).unwrap())
+            .collect();
+
+        Ok(Response::new(ListProvidersResponse { providers }))
+    }
+}
+
+/// Run the `gix` binary, returning stdout or, on a non-zero exit, stderr as the error
+async fn run_gix(args: &[&str]) -> Result<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_gix")).args(args).output().await?;
+    anyhow::ensure!(
+        output.status.success(),
+        "gix {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn provider(slp_id: &str, base_price: u64) -> ComputeProvider {
+    ComputeProvider {
+        slp_id: SlpId(slp_id.to_string()),
+        supported_precisions: vec![PrecisionLevel::BF16, PrecisionLevel::INT8],
+        base_price,
+        capacity: 8,
+        utilization: 0,
+        region: "eu-west".to_string(),
+        max_seq_len: 32_768,
+        status: ProviderStatus::Active,
+    }
+}
+
+#[tokio::test]
+async fn test_import_then_export_providers() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("gix_cli_providers_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let engine = AuctionEngine::new(dir.join("gcam_db"))?;
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = format!("http://{}", listener.local_addr()?);
+    let server = tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(ProviderAdminServiceServer::new(EngineAdmin { engine: engine.clone() }))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let imported = vec![provider("slp-import-a", 90), provider("slp-import-b", 110)];
+    let import_path = dir.join("providers.json").to_string_lossy().into_owned();
+    std::fs::write(&import_path, serde_json::to_vec(&imported)?)?;
+
+    let stdout = run_gix(&["--json", "providers", "import", &import_path, "--node", &addr]).await?;
+    let output: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(output["imported"], 2);
+    assert_eq!(output["failed"].as_array().unwrap().len(), 0);
+
+    // The export lists both imported providers
+    let export_path = dir.join("export.json").to_string_lossy().into_owned();
+    run_gix(&["providers", "export", &export_path, "--node", &addr]).await?;
+    let exported: Vec<ComputeProvider> = serde_json::from_slice(&std::fs::read(&export_path)?)?;
+    for expected in &imported {
+        let found = exported
+            .iter()
+            .find(|p| p.slp_id == expected.slp_id)
+            .expect("imported provider is listed");
+        assert_eq!(found.base_price, expected.base_price);
+        assert_eq!(found.max_seq_len, expected.max_seq_len);
+    }
+
+    // A bad entry is reported without stopping the rest of the batch
+    let mut overloaded = provider("slp-overloaded", 100);
+    overloaded.utilization = overloaded.capacity + 1;
+    let batch = serde_json::json!([overloaded, {"slp_id": "slp-malformed"}, provider("slp-import-c", 95)]);
+    std::fs::write(&import_path, serde_json::to_vec(&batch)?)?;
+
+    let err = run_gix(&["providers", "import", &import_path, "--node", &addr]).await.unwrap_err();
+    assert!(err.to_string().contains("2 of 3 provider entries were rejected"), "unexpected error: {}", err);
+    let providers = engine.get_providers().await;
+    assert!(providers.iter().any(|p| p.slp_id.0 == "slp-import-c"));
+    assert!(!providers.iter().any(|p| p.slp_id.0 == "slp-overloaded"));
+
+    server.abort();
+    drop(engine);
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
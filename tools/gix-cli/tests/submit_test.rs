@@ -0,0 +1,290 @@
+//! CLI submission tests
+//!
+//! These tests run the `gix` binary against an in-process GCAM auction engine.
+
+use anyhow::Result;
+use gcam_node::AuctionEngine;
+use gix_crypto::pqc::dilithium::KeyPair;
+use gix_gxf::{GxfEnvelope, GxfJob};
+use gix_proto::v1::{
+    GetAuctionHistoryRequest, GetAuctionHistoryResponse, GetAuctionStatsRequest,
+    GetAuctionStatsResponse, JobId, LaneId, ReleaseCapacityRequest, ReleaseCapacityResponse,
+    RunAuctionRequest, RunAuctionResponse, SlpId, StreamStatsRequest,
+};
+use gix_proto::{AuctionService, AuctionServiceServer};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::process::Command;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+/// Auction service backed by a real engine
+struct EngineAuction {
+    engine: AuctionEngine,
+    /// Envelope attached to the most recent auction request, if any
+    last_envelope: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+#[tonic::async_trait]
+impl AuctionService for EngineAuction {
+    async fn run_auction(
+        &self,
+        request: Request<RunAuctionRequest>,
+    ) -> Result<Response<RunAuctionResponse>, Status> {
+        let req = request.into_inner();
+        if !req.envelope.is_empty() {
+            *self.last_envelope.lock().unwrap() = Some(req.envelope.clone());
+        }
+        let job: GxfJob = serde_json::from_slice(&req.job)
+            .map_err(|e| Status::invalid_argument(format!("Invalid job: {}", e)))?;
+        job.validate()
+            .map_err(|e| Status::invalid_argument(format!("Invalid job: {}", e)))?;
+
+        let result = self.engine
+            .run_auction(&job, req.priority as u8)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let receipt = match self.engine.receipt_for(&result) {
+            Ok(Some(receipt)) => receipt.to_json().map_err(|e| Status::internal(e.to_string()))?,
+            Ok(None) => Vec::new(),
+            Err(e) => return Err(Status::internal(e.to_string())),
+        };
+
+        Ok(Response::new(RunAuctionResponse {
+            job_id: Some(JobId { id: result.job_id.0.to_vec() }),
+            slp_id: Some(SlpId { id: result.slp_id.0 }),
+            lane_id: Some(LaneId { id: result.lane_id.as_u32() }),
+            price: result.price,
+            route: result.route,
+            success: true,
+            error: String::new(),
+            receipt,
+            provider_region: result.provider_region,
+        }))
+    }
+
+    async fn get_auction_stats(
+        &self,
+        _request: Request<GetAuctionStatsRequest>,
+    ) -> Result<Response<GetAuctionStatsResponse>, Status> {
+        let stats = self.engine.get_stats().await;
+
+        Ok(Response::new(GetAuctionStatsResponse {
+            total_auctions: stats.total_auctions,
+            total_matches: stats.total_matches,
+            total_volume: stats.total_volume,
+            matches_by_precision: stats
+                .matches_by_precision
+                .iter()
+                .map(|(precision, count)| (format!("{:?}", precision), *count))
+                .collect(),
+            matches_by_lane: stats
+                .matches_by_lane
+                .iter()
+                .map(|(lane_id, count)| (lane_id.as_u32(), *count))
+                .collect(),
+            min_price: stats.min_price,
+            max_price: stats.max_price,
+            avg_price: stats.avg_price,
+        }))
+    }
+
+    async fn get_auction_history(
+        &self,
+        _request: Request<GetAuctionHistoryRequest>,
+    ) -> Result<Response<GetAuctionHistoryResponse>, Status> {
+        Err(Status::unimplemented("not used by this test"))
+    }
+
+    async fn release_capacity(
+        &self,
+        _request: Request<ReleaseCapacityRequest>,
+    ) -> Result<Response<ReleaseCapacityResponse>, Status> {
+        Err(Status::unimplemented("not used by this test"))
+    }
+
+    type StreamAuctionStatsStream = tokio_stream::Empty<Result<GetAuctionStatsResponse, Status>>;
+
+    async fn stream_auction_stats(
+        &self,
+        _request: Request<StreamStatsRequest>,
+    ) -> Result<Response<Self::StreamAuctionStatsStream>, Status> {
+        Err(Status::unimplemented("not used by this test"))
+    }
+}
+
+/// Run the `gix` binary and return its stdout, failing on a non-zero exit
+async fn run_gix(args: &[&str]) -> Result<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_gix")).args(args).output().await?;
+    anyhow::ensure!(
+        output.status.success(),
+        "gix {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A GCAM engine served on an ephemeral port, with a scratch directory
+struct LocalNode {
+    engine: AuctionEngine,
+    last_envelope: Arc<Mutex<Option<Vec<u8>>>>,
+    addr: String,
+    dir: PathBuf,
+    server: tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
+}
+
+impl LocalNode {
+    async fn start(name: &str) -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("gix_cli_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+
+        let node_key = KeyPair::generate();
+        std::fs::write(dir.join("node_key.pub"), node_key.public.to_armored())?;
+        let engine = AuctionEngine::new(dir.join("gcam_db"))?.with_receipt_key(node_key);
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = format!("http://{}", listener.local_addr()?);
+        let last_envelope = Arc::new(Mutex::new(None));
+        let service = EngineAuction { engine: engine.clone(), last_envelope: last_envelope.clone() };
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(AuctionServiceServer::new(service))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        Ok(LocalNode { engine, last_envelope, addr, dir, server })
+    }
+
+    /// Create a wallet and a job file, returning their paths
+    async fn prepare_submission(&self) -> Result<(String, String)> {
+        let wallet_path = self.dir.join("wallet.json").to_string_lossy().into_owned();
+        run_gix(&["keygen", "--output", &wallet_path]).await?;
+
+        let job_path = self.dir.join("job.yaml");
+        std::fs::write(
+            &job_path,
+            "model: \"test-model\"\nprecision: \"FP8\"\nkv_cache_seq_len: 1024\nbatch_size: 4\n",
+        )?;
+
+        Ok((wallet_path, job_path.to_string_lossy().into_owned()))
+    }
+
+    /// Path of the node's armored public key
+    fn node_key_path(&self) -> String {
+        self.dir.join("node_key.pub").to_string_lossy().into_owned()
+    }
+
+    fn stop(self) {
+        self.server.abort();
+        drop(self.engine);
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[tokio::test]
+async fn test_submit_against_local_node() -> Result<()> {
+    let node = LocalNode::start("submit").await?;
+    let (wallet, job_file) = node.prepare_submission().await?;
+
+    let stdout = run_gix(&["submit", &job_file, "--wallet", &wallet, "--node", &node.addr]).await?;
+    assert!(stdout.contains("Job submitted successfully"), "unexpected output: {}", stdout);
+
+    let history = node.engine.get_history(10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(node.engine.get_stats().await.total_matches, 1);
+
+    node.stop();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_json_output() -> Result<()> {
+    let node = LocalNode::start("submit_json").await?;
+    let (wallet, job_file) = node.prepare_submission().await?;
+
+    let stdout = run_gix(&["--json", "submit", &job_file, "--wallet", &wallet, "--node", &node.addr]).await?;
+    let output: serde_json::Value = serde_json::from_str(&stdout)?;
+
+    let record = &node.engine.get_history(1)[0];
+    assert_eq!(output["success"], true);
+    assert_eq!(output["job_id"], hex::encode(record.job_id.0));
+    assert_eq!(output["slp_id"], record.slp_id.0.as_str());
+    assert_eq!(output["price"], record.price);
+
+    node.stop();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_attaches_verifiable_vdf_proof() -> Result<()> {
+    let node = LocalNode::start("submit_pow").await?;
+    let (wallet, job_file) = node.prepare_submission().await?;
+
+    let stdout = run_gix(&[
+        "--json", "submit", &job_file, "--wallet", &wallet, "--node", &node.addr,
+        "--pow-iterations", "50",
+    ]).await?;
+    let output: serde_json::Value = serde_json::from_str(&stdout)?;
+    assert_eq!(output["success"], true);
+    assert!(output["vdf_elapsed_ms"].is_u64());
+
+    let sent = node.last_envelope.lock().unwrap().clone().expect("envelope was sent");
+    let envelope = GxfEnvelope::from_json(&sent)?;
+    assert_eq!(envelope.vdf_proof.as_ref().unwrap().iterations, 50);
+    envelope.verify_vdf_proof(50)?;
+    assert!(envelope.verify_vdf_proof(51).is_err());
+
+    node.stop();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_receipt_verifies_against_node_key() -> Result<()> {
+    let node = LocalNode::start("receipt").await?;
+    let (wallet, job_file) = node.prepare_submission().await?;
+    let receipt_path = node.dir.join("receipt.json").to_string_lossy().into_owned();
+
+    run_gix(&[
+        "submit", &job_file, "--wallet", &wallet, "--node", &node.addr, "--receipt", &receipt_path,
+    ]).await?;
+
+    let stdout = run_gix(&["--json", "verify-receipt", &receipt_path, "--node-key", &node.node_key_path()]).await?;
+    let output: serde_json::Value = serde_json::from_str(&stdout)?;
+    let record = &node.engine.get_history(1)[0];
+    assert_eq!(output["valid"], true);
+    assert_eq!(output["job_id"], hex::encode(record.job_id.0));
+    assert_eq!(output["price"], record.price);
+
+    // Raising the price in the file invalidates the signature
+    let mut receipt: serde_json::Value = serde_json::from_slice(&std::fs::read(&receipt_path)?)?;
+    receipt["auction_match"]["price"] = serde_json::json!(record.price + 1);
+    std::fs::write(&receipt_path, serde_json::to_vec(&receipt)?)?;
+    let err = run_gix(&["verify-receipt", &receipt_path, "--node-key", &node.node_key_path()]).await.unwrap_err();
+    assert!(err.to_string().contains("is not signed by"), "unexpected error: {}", err);
+
+    node.stop();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_json_output() -> Result<()> {
+    let node = LocalNode::start("status_json").await?;
+    let (wallet, job_file) = node.prepare_submission().await?;
+    run_gix(&["submit", &job_file, "--wallet", &wallet, "--node", &node.addr]).await?;
+
+    // The flag is global, so it may also follow the subcommand
+    let stdout = run_gix(&["status", "--node", &node.addr, "--json"]).await?;
+    let output: serde_json::Value = serde_json::from_str(&stdout)?;
+
+    assert_eq!(output["total_auctions"], 1);
+    assert_eq!(output["total_matches"], 1);
+    assert_eq!(output["matches_by_precision"]["FP8"], 1);
+    assert!(output["total_volume"].as_u64().unwrap() > 0);
+    assert_eq!(output["min_price"], output["total_volume"]);
+    assert_eq!(output["max_price"], output["total_volume"]);
+
+    node.stop();
+    Ok(())
+}
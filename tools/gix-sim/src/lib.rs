@@ -3,58 +3,243 @@
 //! Simulates the complete GIX workflow:
 //! - Job submission → AJR routing → GCAM auction → GSEE execution
 //!
-//! Uses gRPC clients to communicate with the service daemons.
+//! Uses gRPC clients to communicate with the service daemons. The
+//! [`pipeline`] module runs the same stages in-process instead.
+
+pub mod pipeline;
+
+pub use pipeline::run_pipeline;
 
 use anyhow::Result;
 use gix_common::JobId;
 use gix_crypto::hash_blake3;
 use gix_gxf::{GxfEnvelope, GxfJob, PrecisionLevel};
-use gix_proto::v1::{ExecuteJobRequest, GetAuctionStatsRequest, GetRouterStatsRequest, GetRuntimeStatsRequest, RouteEnvelopeRequest, RunAuctionRequest};
-use gix_proto::{AuctionServiceClient, ExecutionServiceClient, RouterServiceClient};
-use rand::Rng;
-use tonic::Request;
+use gix_proto::v1::{ExecuteJobRequest, HealthCheckRequest, ServingStatus, GetAuctionStatsRequest, GetRouterStatsRequest, GetRuntimeStatsRequest, ReleaseCapacityRequest, RouteEnvelopeRequest, RunAuctionRequest, SlpId};
+use gix_proto::trace::{new_trace_id, set_trace_id};
+use gix_proto::{AuctionServiceClient, ExecutionServiceClient, HealthServiceClient, RouterServiceClient};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+use tracing::warn;
 
 const AJR_SERVER_ADDR: &str = "http://127.0.0.1:50051";
 const GCAM_SERVER_ADDR: &str = "http://127.0.0.1:50052";
 const GSEE_SERVER_ADDR: &str = "http://127.0.0.1:50053";
 
+/// Health checks made per daemon before giving up
+const HEALTH_POLL_ATTEMPTS: u32 = 20;
+/// Delay between health checks
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Connection attempts made when reconnecting a lost client
+const RECONNECT_ATTEMPTS: u32 = 5;
+/// Delay before the first reconnection attempt, doubled after each failure
+const RECONNECT_BACKOFF: Duration = Duration::from_millis(100);
+/// Reconnections a single tick may make before it fails
+const MAX_TICK_RECONNECTS: u32 = 3;
+
+/// Service endpoints the simulator connects to
+#[derive(Debug, Clone)]
+pub struct Endpoints {
+    /// AJR router address
+    pub router: String,
+    /// GCAM node address
+    pub auction: String,
+    /// GSEE runtime address
+    pub runtime: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            router: AJR_SERVER_ADDR.to_string(),
+            auction: GCAM_SERVER_ADDR.to_string(),
+            runtime: GSEE_SERVER_ADDR.to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Address of `service`
+    pub fn address(&self, service: Service) -> &str {
+        match service {
+            Service::Router => &self.router,
+            Service::Auction => &self.auction,
+            Service::Runtime => &self.runtime,
+        }
+    }
+}
+
+/// Daemon the simulator holds a client for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    /// AJR router
+    Router,
+    /// GCAM node
+    Auction,
+    /// GSEE runtime
+    Runtime,
+}
+
+impl fmt::Display for Service {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Service::Router => write!(f, "AJR router"),
+            Service::Auction => write!(f, "GCAM node"),
+            Service::Runtime => write!(f, "GSEE runtime"),
+        }
+    }
+}
+
+/// A call failed because the connection to its daemon was lost
+#[derive(Debug)]
+struct Disconnected {
+    service: Service,
+    context: &'static str,
+    status: Status,
+}
+
+impl fmt::Display for Disconnected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.status)
+    }
+}
+
+impl std::error::Error for Disconnected {}
+
+/// Whether a failed call points at a lost connection rather than a rejected request
+///
+/// Transport failures carry the underlying transport error as their source;
+/// statuses sent by a daemon carry none, including the `Unavailable` a
+/// draining or locked daemon answers with.
+fn is_disconnect(status: &Status) -> bool {
+    std::error::Error::source(status).is_some()
+}
+
+/// Error for a failed call to `service`, marked as `Disconnected` when the connection was lost
+fn call_failed(service: Service, context: &'static str, status: Status) -> anyhow::Error {
+    if is_disconnect(&status) {
+        anyhow::Error::new(Disconnected { service, context, status })
+    } else {
+        anyhow::anyhow!("{}: {}", context, status)
+    }
+}
+
 /// Main simulation state
 pub struct Simulation {
-    pub router_client: RouterServiceClient<tonic::transport::Channel>,
-    pub auction_client: AuctionServiceClient<tonic::transport::Channel>,
-    pub runtime_client: ExecutionServiceClient<tonic::transport::Channel>,
+    pub router_client: RouterServiceClient<Channel>,
+    pub auction_client: AuctionServiceClient<Channel>,
+    pub runtime_client: ExecutionServiceClient<Channel>,
     pub tick: u64,
-    pub jobs_processed: u64,
+    /// Addresses the clients connect to, kept for reconnecting
+    endpoints: Endpoints,
+    /// Clients replaced after losing their connection
+    reconnects: u64,
+    /// Jobs that completed the full pipeline (shared with concurrent tick tasks)
+    jobs_processed: Arc<AtomicU64>,
+    /// RNG driving job generation (seeded for reproducible runs)
+    rng: StdRng,
 }
 
 impl Simulation {
     /// Create a new simulation with gRPC clients
     pub async fn new() -> Result<Self> {
+        Self::connect(&Endpoints::default(), None).await
+    }
+
+    /// Create a new simulation whose job sequence is reproducible from `seed`
+    pub async fn with_seed(seed: u64) -> Result<Self> {
+        Self::connect(&Endpoints::default(), Some(seed)).await
+    }
+
+    /// Create a new simulation connected to the given endpoints
+    pub async fn connect(endpoints: &Endpoints, seed: Option<u64>) -> Result<Self> {
+        // Wait for each daemon to report ready
+        wait_until_serving(&endpoints.router, "gix.v1.RouterService").await?;
+        wait_until_serving(&endpoints.auction, "gix.v1.AuctionService").await?;
+        wait_until_serving(&endpoints.runtime, "gix.v1.ExecutionService").await?;
+
         // Connect to service daemons
-        let router_client = RouterServiceClient::connect(AJR_SERVER_ADDR)
+        let router_client = RouterServiceClient::connect(endpoints.router.clone())
             .await
             .map_err(|e| anyhow::anyhow!("Failed to connect to AJR router: {}", e))?;
         
-        let auction_client = AuctionServiceClient::connect(GCAM_SERVER_ADDR)
+        let auction_client = AuctionServiceClient::connect(endpoints.auction.clone())
             .await
             .map_err(|e| anyhow::anyhow!("Failed to connect to GCAM node: {}", e))?;
         
-        let runtime_client = ExecutionServiceClient::connect(GSEE_SERVER_ADDR)
+        let runtime_client = ExecutionServiceClient::connect(endpoints.runtime.clone())
             .await
             .map_err(|e| anyhow::anyhow!("Failed to connect to GSEE runtime: {}", e))?;
 
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         Ok(Simulation {
             router_client,
             auction_client,
             runtime_client,
             tick: 0,
-            jobs_processed: 0,
+            endpoints: endpoints.clone(),
+            reconnects: 0,
+            jobs_processed: Arc::new(AtomicU64::new(0)),
+            rng,
         })
     }
 
+    /// Number of jobs that completed the full pipeline
+    pub fn jobs_processed(&self) -> u64 {
+        self.jobs_processed.load(Ordering::SeqCst)
+    }
+
+    /// Number of times a client was reconnected after losing its daemon
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects
+    }
+
+    /// Replace the client for `service` with a fresh connection
+    ///
+    /// Backs off between attempts so a restarting daemon has time to listen again.
+    async fn reconnect(&mut self, service: Service) -> Result<()> {
+        let endpoint = self.endpoints.address(service).to_string();
+        let mut last_error = String::new();
+
+        for attempt in 0..RECONNECT_ATTEMPTS {
+            tokio::time::sleep(RECONNECT_BACKOFF * 2u32.pow(attempt)).await;
+
+            let connected = match service {
+                Service::Router => RouterServiceClient::connect(endpoint.clone())
+                    .await
+                    .map(|client| self.router_client = client),
+                Service::Auction => AuctionServiceClient::connect(endpoint.clone())
+                    .await
+                    .map(|client| self.auction_client = client),
+                Service::Runtime => ExecutionServiceClient::connect(endpoint.clone())
+                    .await
+                    .map(|client| self.runtime_client = client),
+            };
+            match connected {
+                Ok(()) => {
+                    self.reconnects += 1;
+                    warn!("Reconnected to {} at {}", service, endpoint);
+                    return Ok(());
+                }
+                Err(e) => last_error = e.to_string(),
+            }
+        }
+
+        Err(anyhow::anyhow!("Failed to reconnect to {} at {}: {}", service, endpoint, last_error))
+    }
+
     /// Generate a random JobId using crypto hashing
-    fn generate_job_id() -> JobId {
-        let mut rng = rand::thread_rng();
+    fn generate_job_id(rng: &mut StdRng) -> JobId {
         let random_bytes: [u8; 16] = rng.gen();
         let hash = hash_blake3(&random_bytes);
         let mut job_id_bytes = [0u8; 16];
@@ -63,135 +248,396 @@ impl Simulation {
     }
 
     /// Create a random test job
-    fn create_test_job() -> GxfJob {
-        let job_id = Self::generate_job_id();
-        let precisions = vec![
+    fn create_test_job(rng: &mut StdRng) -> GxfJob {
+        let job_id = Self::generate_job_id(rng);
+        let precisions = [
             PrecisionLevel::BF16,
             PrecisionLevel::FP8,
             PrecisionLevel::E5M2,
             PrecisionLevel::INT8,
         ];
-        let precision = precisions[rand::thread_rng().gen_range(0..precisions.len())];
-        let seq_len = rand::thread_rng().gen_range(512..4096);
+        let precision = precisions[rng.gen_range(0..precisions.len())];
+        let seq_len = rng.gen_range(512..4096);
         
         let mut job = GxfJob::new(job_id, precision, seq_len);
         
-        if rand::thread_rng().gen_bool(0.5) {
-            job.parameters.insert("batch_size".to_string(), format!("{}", rand::thread_rng().gen_range(1..32)));
+        if rng.gen_bool(0.5) {
+            job.parameters.insert("batch_size".to_string(), format!("{}", rng.gen_range(1..32)));
         }
-        if rand::thread_rng().gen_bool(0.5) {
-            let regions = vec!["US", "EU"];
-            job.parameters.insert("region".to_string(), regions[rand::thread_rng().gen_range(0..regions.len())].to_string());
+        if rng.gen_bool(0.5) {
+            let regions = ["US", "EU"];
+            job.parameters.insert("region".to_string(), regions[rng.gen_range(0..regions.len())].to_string());
         }
         
         job
     }
 
+    /// Generate the next job and its priority
+    fn next_job(&mut self) -> (GxfJob, u8) {
+        let job = Self::create_test_job(&mut self.rng);
+        let priority = self.rng.gen_range(32..192);
+        (job, priority)
+    }
+
     /// Run one simulation tick
+    ///
+    /// When a daemon's connection is lost, its client is reconnected and the
+    /// job resumes from the stage that failed. A tick that gives up releases
+    /// any capacity its auction claimed.
     pub async fn run_tick(&mut self) -> Result<()> {
         self.tick += 1;
 
-        let job = Self::create_test_job();
-        let priority = rand::thread_rng().gen_range(32..192);
-        let envelope = GxfEnvelope::from_job(job.clone(), priority)?;
+        let (job, priority) = self.next_job();
+        let mut submission = Submission::new(job, priority)?;
+        let mut reconnects = 0;
+        loop {
+            let result = submission
+                .advance(&mut self.router_client, &mut self.auction_client, &mut self.runtime_client)
+                .await;
 
-        // Serialize envelope and job for gRPC calls
-        let envelope_bytes = envelope.to_json()
-            .map_err(|e| anyhow::anyhow!("Failed to serialize envelope: {}", e))?;
-        
-        let job_bytes = serde_json::to_vec(&job)
-            .map_err(|e| anyhow::anyhow!("Failed to serialize job: {}", e))?;
-
-        // Step 2: Route through AJR via gRPC
-        let route_request = Request::new(RouteEnvelopeRequest {
-            envelope: envelope_bytes.clone(),
-        });
-        
-        let route_response = self.router_client
-            .route_envelope(route_request)
-            .await
-            .map_err(|e| anyhow::anyhow!("AJR routing failed: {}", e))?;
-        
-        let route_resp = route_response.into_inner();
-        if !route_resp.success {
-            return Err(anyhow::anyhow!("AJR routing failed: {}", route_resp.error));
+            let Err(e) = result else { break };
+            let lost = e.downcast_ref::<Disconnected>().map(|lost| lost.service);
+            let e = match lost {
+                Some(service) if reconnects < MAX_TICK_RECONNECTS => {
+                    warn!("{}; reconnecting", e);
+                    reconnects += 1;
+                    match self.reconnect(service).await {
+                        Ok(()) => continue,
+                        Err(reconnect_error) => e.context(reconnect_error),
+                    }
+                }
+                _ => e,
+            };
+            submission.abandon(&mut self.auction_client).await;
+            return Err(e);
         }
 
-        // Step 3: Run GCAM auction via gRPC
-        let auction_request = Request::new(RunAuctionRequest {
-            job: job_bytes,
-            priority: priority as u32,
-        });
-        
-        let auction_response = self.auction_client
-            .run_auction(auction_request)
-            .await
-            .map_err(|e| anyhow::anyhow!("GCAM auction failed: {}", e))?;
-        
-        let auction_resp = auction_response.into_inner();
-        if !auction_resp.success {
-            return Err(anyhow::anyhow!("GCAM auction failed: {}", auction_resp.error));
+        self.jobs_processed.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Run `ticks` simulation ticks spread across `concurrency` tasks
+    ///
+    /// Jobs are generated up front so seeded runs submit the same sequence
+    /// regardless of concurrency. Each task uses its own clone of the gRPC
+    /// clients, which share the underlying channels.
+    pub async fn run_ticks(&mut self, ticks: u64, concurrency: usize) -> Result<()> {
+        let jobs: Vec<(GxfJob, u8)> = (0..ticks).map(|_| self.next_job()).collect();
+        self.tick += ticks;
+
+        let queue = Arc::new(Mutex::new(jobs.into_iter()));
+        let mut handles = Vec::new();
+
+        for _ in 0..concurrency.max(1) {
+            let queue = queue.clone();
+            let jobs_processed = self.jobs_processed.clone();
+            let mut router_client = self.router_client.clone();
+            let mut auction_client = self.auction_client.clone();
+            let mut runtime_client = self.runtime_client.clone();
+
+            handles.push(tokio::spawn(async move {
+                loop {
+                    let next = queue.lock().expect("job queue poisoned").next();
+                    let Some((job, priority)) = next else {
+                        return Ok::<(), anyhow::Error>(());
+                    };
+
+                    let mut submission = Submission::new(job, priority)?;
+                    let result = submission
+                        .advance(&mut router_client, &mut auction_client, &mut runtime_client)
+                        .await;
+                    if result.is_err() {
+                        submission.abandon(&mut auction_client).await;
+                    }
+                    result?;
+                    jobs_processed.fetch_add(1, Ordering::SeqCst);
+                }
+            }));
         }
 
-        // Step 4: Execute in GSEE runtime via gRPC
-        let execute_request = Request::new(ExecuteJobRequest {
-            envelope: envelope_bytes,
-        });
-        
-        let execute_response = self.runtime_client
-            .execute_job(execute_request)
-            .await
-            .map_err(|e| anyhow::anyhow!("GSEE execution failed: {}", e))?;
-        
-        let execute_resp = execute_response.into_inner();
-        if !execute_resp.success {
-            return Err(anyhow::anyhow!("GSEE execution failed: {}", execute_resp.error));
+        let mut first_error = None;
+        for handle in handles {
+            let result = handle
+                .await
+                .map_err(|e| anyhow::anyhow!("Simulation task failed: {}", e))
+                .and_then(|r| r);
+            if let Err(e) = result {
+                first_error.get_or_insert(e);
+            }
         }
 
-        self.jobs_processed += 1;
-        Ok(())
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 
     /// Get current simulation status
+    ///
+    /// A daemon whose connection was lost is reconnected once before its
+    /// stats are reported as zero.
     pub async fn status(&mut self) -> String {
         // Get stats from services via gRPC
-        let router_stats = self.router_client
+        let mut router_stats = self.router_client
             .get_router_stats(Request::new(GetRouterStatsRequest {}))
-            .await
-            .ok()
-            .map(|r| r.into_inner())
-            .unwrap_or_default();
+            .await;
+        if matches!(&router_stats, Err(status) if is_disconnect(status)) && self.reconnect(Service::Router).await.is_ok() {
+            router_stats = self.router_client
+                .get_router_stats(Request::new(GetRouterStatsRequest {}))
+                .await;
+        }
+        let router_stats = router_stats.ok().map(|r| r.into_inner()).unwrap_or_default();
         
-        let auction_stats = self.auction_client
+        let mut auction_stats = self.auction_client
             .get_auction_stats(Request::new(GetAuctionStatsRequest {}))
-            .await
-            .ok()
-            .map(|r| r.into_inner())
-            .unwrap_or_default();
+            .await;
+        if matches!(&auction_stats, Err(status) if is_disconnect(status)) && self.reconnect(Service::Auction).await.is_ok() {
+            auction_stats = self.auction_client
+                .get_auction_stats(Request::new(GetAuctionStatsRequest {}))
+                .await;
+        }
+        let auction_stats = auction_stats.ok().map(|r| r.into_inner()).unwrap_or_default();
         
-        let runtime_stats = self.runtime_client
+        let mut runtime_stats = self.runtime_client
             .get_runtime_stats(Request::new(GetRuntimeStatsRequest {}))
-            .await
-            .ok()
-            .map(|r| r.into_inner())
-            .unwrap_or_default();
+            .await;
+        if matches!(&runtime_stats, Err(status) if is_disconnect(status)) && self.reconnect(Service::Runtime).await.is_ok() {
+            runtime_stats = self.runtime_client
+                .get_runtime_stats(Request::new(GetRuntimeStatsRequest {}))
+                .await;
+        }
+        let runtime_stats = runtime_stats.ok().map(|r| r.into_inner()).unwrap_or_default();
 
         format!(
-            "Tick {}: Processed {} jobs | Router: {} routed | Auction: {} matches (volume: {}) | Runtime: {} executed ({} completed, {} rejected)",
+            "Tick {}: Processed {} jobs | Router: {} routed | Auction: {} matches (volume: {}) | Runtime: {} executed ({} completed, {} rejected) | Reconnects: {}",
             self.tick,
-            self.jobs_processed,
+            self.jobs_processed(),
             router_stats.total_routed,
             auction_stats.total_matches,
             auction_stats.total_volume,
             runtime_stats.total_executed,
             runtime_stats.total_completed,
-            runtime_stats.total_rejected
+            runtime_stats.total_rejected,
+            self.reconnects
         )
     }
 }
 
+/// Poll a daemon's health service until it reports serving
+async fn wait_until_serving(endpoint: &str, service: &str) -> Result<()> {
+    let mut last_error = String::from("not serving");
+    
+    for attempt in 0..HEALTH_POLL_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+        }
+        
+        let mut client = match HealthServiceClient::connect(endpoint.to_string()).await {
+            Ok(client) => client,
+            Err(e) => {
+                last_error = e.to_string();
+                continue;
+            }
+        };
+        
+        let request = Request::new(HealthCheckRequest {
+            service: service.to_string(),
+        });
+        match client.check(request).await {
+            Ok(response) if response.get_ref().status == ServingStatus::Serving as i32 => return Ok(()),
+            Ok(response) => last_error = format!("status {:?}", response.get_ref().status()),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    
+    Err(anyhow::anyhow!("{} at {} is not serving: {}", service, endpoint, last_error))
+}
+
+/// Wrap a message in a request tagged with the job's trace id
+fn traced<T>(message: T, trace_id: &str) -> Result<Request<T>> {
+    let mut request = Request::new(message);
+    set_trace_id(&mut request, trace_id)?;
+    Ok(request)
+}
+
+/// One job's progress through AJR routing, GCAM auction and GSEE execution
+///
+/// Every request for the job carries the same trace id so the services' logs
+/// can be correlated. Completed stages are remembered, so `advance` after a
+/// failure picks up at the stage that failed instead of routing and
+/// auctioning the job again.
+struct Submission {
+    job: GxfJob,
+    priority: u8,
+    source_slp: String,
+    envelope_bytes: Vec<u8>,
+    trace_id: String,
+    routed: bool,
+    /// Set once the auction succeeds: the provider whose capacity was claimed
+    matched: Option<Option<SlpId>>,
+    /// Region of the matched provider, checked by the runtime
+    provider_region: String,
+    executed: bool,
+}
+
+impl Submission {
+    fn new(job: GxfJob, priority: u8) -> Result<Self> {
+        let envelope = GxfEnvelope::from_job(job.clone(), priority)?;
+        let envelope_bytes = envelope.to_json()
+            .map_err(|e| anyhow::anyhow!("Failed to serialize envelope: {}", e))?;
+
+        Ok(Submission {
+            job,
+            priority,
+            source_slp: envelope.meta.source_slp.unwrap_or_default(),
+            envelope_bytes,
+            trace_id: new_trace_id(),
+            routed: false,
+            matched: None,
+            provider_region: String::new(),
+            executed: false,
+        })
+    }
+
+    /// Run the stages not yet completed
+    async fn advance(
+        &mut self,
+        router_client: &mut RouterServiceClient<Channel>,
+        auction_client: &mut AuctionServiceClient<Channel>,
+        runtime_client: &mut ExecutionServiceClient<Channel>,
+    ) -> Result<()> {
+        // Step 2: Route through AJR via gRPC
+        if !self.routed {
+            let route_request = traced(RouteEnvelopeRequest {
+                envelope: self.envelope_bytes.clone(),
+            }, &self.trace_id)?;
+
+            let route_response = router_client
+                .route_envelope(route_request)
+                .await
+                .map_err(|e| call_failed(Service::Router, "AJR routing failed", e))?;
+
+            let route_resp = route_response.into_inner();
+            if !route_resp.success {
+                return Err(anyhow::anyhow!("AJR routing failed: {}", route_resp.error));
+            }
+            self.routed = true;
+        }
+
+        // Step 3: Run GCAM auction via gRPC
+        if self.matched.is_none() {
+            let job_bytes = serde_json::to_vec(&self.job)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize job: {}", e))?;
+            let auction_request = traced(RunAuctionRequest {
+                job: job_bytes,
+                priority: self.priority as u32,
+                source_slp: self.source_slp.clone(),
+                envelope: Vec::new(),
+            }, &self.trace_id)?;
+
+            let auction_response = auction_client
+                .run_auction(auction_request)
+                .await
+                .map_err(|e| call_failed(Service::Auction, "GCAM auction failed", e))?;
+
+            let auction_resp = auction_response.into_inner();
+            if !auction_resp.success {
+                return Err(anyhow::anyhow!("GCAM auction failed: {}", auction_resp.error));
+            }
+            self.provider_region = auction_resp.provider_region;
+            self.matched = Some(auction_resp.slp_id);
+        }
+
+        // Step 4: Execute in GSEE runtime via gRPC
+        if !self.executed {
+            let execute_request = traced(ExecuteJobRequest {
+                envelope: self.envelope_bytes.clone(),
+                provider_region: self.provider_region.clone(),
+            }, &self.trace_id)?;
+
+            let execute_response = runtime_client
+                .execute_job(execute_request)
+                .await
+                .map_err(|e| call_failed(Service::Runtime, "GSEE execution failed", e))?;
+
+            let execute_resp = execute_response.into_inner();
+            if !execute_resp.success {
+                return Err(anyhow::anyhow!("GSEE execution failed: {}", execute_resp.error));
+            }
+            self.executed = true;
+        }
+
+        // Step 5: Free the provider's capacity now that execution has finished
+        self.release(auction_client).await
+    }
+
+    /// Release the capacity claimed by the auction, if it has not been already
+    async fn release(&mut self, auction_client: &mut AuctionServiceClient<Channel>) -> Result<()> {
+        if let Some(Some(slp_id)) = &self.matched {
+            let release_request = traced(ReleaseCapacityRequest {
+                slp_id: Some(slp_id.clone()),
+            }, &self.trace_id)?;
+
+            auction_client
+                .release_capacity(release_request)
+                .await
+                .map_err(|e| call_failed(Service::Auction, "GCAM capacity release failed", e))?;
+            self.matched = Some(None);
+        }
+        Ok(())
+    }
+
+    /// Give up on the job, releasing any capacity it still holds
+    async fn abandon(&mut self, auction_client: &mut AuctionServiceClient<Channel>) {
+        if let Err(e) = self.release(auction_client).await {
+            warn!("Failed to release capacity of abandoned job: {}", e);
+        }
+    }
+}
+
 impl Default for Simulation {
     fn default() -> Self {
         panic!("Simulation::default() cannot be used. Use Simulation::new().await instead.")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_job_generation_is_reproducible() {
+        let mut rng1 = StdRng::seed_from_u64(42);
+        let mut rng2 = StdRng::seed_from_u64(42);
+
+        for _ in 0..10 {
+            let job1 = Simulation::create_test_job(&mut rng1);
+            let job2 = Simulation::create_test_job(&mut rng2);
+            assert_eq!(job1.job_id, job2.job_id);
+            assert_eq!(job1.precision, job2.precision);
+            assert_eq!(job1.kv_cache_seq_len, job2.kv_cache_seq_len);
+            assert_eq!(job1.parameters, job2.parameters);
+        }
+    }
+
+    #[test]
+    fn test_daemon_statuses_are_not_disconnects() {
+        // A draining daemon answers Unavailable itself; the connection is fine
+        assert!(!is_disconnect(&Status::unavailable("Node is draining")));
+        assert!(!is_disconnect(&Status::internal("StorageLocked")));
+
+        let transport = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+        assert!(is_disconnect(&Status::from_error(Box::new(transport))));
+    }
+
+    #[test]
+    fn test_different_seeds_produce_different_jobs() {
+        let mut rng1 = StdRng::seed_from_u64(1);
+        let mut rng2 = StdRng::seed_from_u64(2);
+
+        assert_ne!(
+            Simulation::generate_job_id(&mut rng1),
+            Simulation::generate_job_id(&mut rng2)
+        );
+    }
+}
@@ -4,11 +4,33 @@
 //! - Job submission → AJR routing → GCAM auction → GSEE execution
 
 use anyhow::Result;
+use clap::Parser;
 use gix_sim::Simulation;
+use std::time::Instant;
 use tracing::info;
 
+/// GIX Localnet Simulator
+#[derive(Parser)]
+#[command(name = "gix-sim")]
+#[command(about = "GIX Localnet Simulator", long_about = None)]
+struct Args {
+    /// Seed for job generation (random if omitted)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Number of simulation ticks to run
+    #[arg(long, default_value = "5")]
+    ticks: u64,
+
+    /// Number of ticks processed concurrently
+    #[arg(long, default_value = "1")]
+    concurrency: usize,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+
     // Initialize tracing
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -24,15 +46,44 @@ async fn main() -> Result<()> {
     info!("  - GSEE Runtime:    http://127.0.0.1:50053");
     info!("");
 
-    let mut simulation = Simulation::new().await?;
+    let mut simulation = match args.seed {
+        Some(seed) => {
+            info!("Using RNG seed {}", seed);
+            Simulation::with_seed(seed).await?
+        }
+        None => Simulation::new().await?,
+    };
     
-    info!("Connected! Running 5 simulation ticks...\n");
+    info!(
+        "Connected! Running {} simulation ticks (concurrency {})...\n",
+        args.ticks, args.concurrency
+    );
+
+    let start = Instant::now();
 
-    for i in 1..=5 {
-        simulation.run_tick().await?;
-        info!("[Tick {}] {}", i, simulation.status().await);
+    if args.concurrency <= 1 {
+        for i in 1..=args.ticks {
+            simulation.run_tick().await?;
+            info!("[Tick {}] {}", i, simulation.status().await);
+        }
+    } else {
+        simulation.run_ticks(args.ticks, args.concurrency).await?;
+        info!("{}", simulation.status().await);
     }
 
+    let elapsed = start.elapsed().as_secs_f64();
+    let throughput = if elapsed > 0.0 {
+        simulation.jobs_processed() as f64 / elapsed
+    } else {
+        0.0
+    };
+
     info!("\nSimulation complete!");
+    info!(
+        "Processed {} jobs in {:.2}s ({:.1} jobs/sec)",
+        simulation.jobs_processed(),
+        elapsed,
+        throughput
+    );
     Ok(())
 }
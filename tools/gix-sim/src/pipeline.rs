@@ -0,0 +1,35 @@
+//! In-process pipeline
+//!
+//! Runs a job through AJR routing, GCAM auction and GSEE execution by
+//! calling each service library directly, with no daemons or network.
+
+use ajr_router::RouterState;
+use anyhow::{Context, Result};
+use gcam_node::{AuctionEngine, AuctionMatch};
+use gix_common::LaneId;
+use gix_gxf::{GxfEnvelope, GxfJob};
+use gsee_runtime::{ExecutionResult, RuntimeState};
+
+/// Push one job through all three stages against freshly constructed state
+///
+/// The auction engine uses a temporary database seeded with the default
+/// providers. Errors name the stage that rejected the job.
+pub async fn run_pipeline(job: GxfJob, priority: u8) -> Result<(LaneId, AuctionMatch, ExecutionResult)> {
+    let router = RouterState::new();
+    let engine = AuctionEngine::temporary().context("Failed to create auction engine")?;
+    let runtime = RuntimeState::new();
+
+    let envelope = GxfEnvelope::from_job(job, priority)?;
+
+    let lane_id = ajr_router::process_envelope(&router, envelope.clone())
+        .await
+        .context("AJR routing failed")?;
+    let auction_match = gcam_node::process_envelope(&engine, envelope.clone())
+        .await
+        .context("GCAM auction failed")?;
+    let result = gsee_runtime::process_envelope(&runtime, envelope, Some(&auction_match.provider_region))
+        .await
+        .context("GSEE execution failed")?;
+
+    Ok((lane_id, auction_match, result))
+}
@@ -0,0 +1,35 @@
+//! In-process pipeline tests
+//!
+//! These tests run a job through the service libraries directly, without
+//! daemons or network.
+
+use anyhow::Result;
+use gix_common::{JobId, LaneId};
+use gix_gxf::{GxfJob, PrecisionLevel};
+use gix_sim::run_pipeline;
+use gsee_runtime::ExecutionStatus;
+
+#[tokio::test]
+async fn test_valid_job_flows_through_all_stages() -> Result<()> {
+    let job = GxfJob::new(JobId([7u8; 16]), PrecisionLevel::BF16, 1024);
+
+    let (lane_id, auction_match, result) = run_pipeline(job, 200).await?;
+
+    assert_eq!(lane_id, LaneId(0), "high priority jobs use the Flash lane");
+    assert_eq!(auction_match.job_id, JobId([7u8; 16]));
+    assert_eq!(auction_match.precision_used, PrecisionLevel::BF16);
+    assert_eq!(result.job_id, JobId([7u8; 16]));
+    assert_eq!(result.status, ExecutionStatus::Completed);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compliance_violation_rejected_at_gsee() {
+    let mut job = GxfJob::new(JobId([8u8; 16]), PrecisionLevel::BF16, 1024);
+    job.parameters.insert("region".to_string(), "APAC".to_string());
+
+    let err = run_pipeline(job, 200).await.unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.starts_with("GSEE execution failed"), "{}", message);
+    assert!(message.contains("Residency violation"), "{}", message);
+}
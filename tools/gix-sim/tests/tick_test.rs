@@ -0,0 +1,108 @@
+//! Simulation tick tests
+//!
+//! These tests run the simulator against in-process mock services.
+
+use anyhow::Result;
+use gix_proto::mock::{spawn_mock_server, spawn_mock_server_on, MockServer};
+use gix_sim::{Endpoints, Simulation};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Start mock services on an ephemeral port and return endpoints pointing at them
+async fn start_mock_services() -> Result<(MockServer, Endpoints)> {
+    let server = spawn_mock_server().await?;
+    let endpoints = Endpoints {
+        router: server.addr.clone(),
+        auction: server.addr.clone(),
+        runtime: server.addr.clone(),
+    };
+    Ok((server, endpoints))
+}
+
+#[tokio::test]
+async fn test_sequential_ticks_count_jobs() -> Result<()> {
+    let (server, endpoints) = start_mock_services().await?;
+    let mut simulation = Simulation::connect(&endpoints, Some(7)).await?;
+
+    for _ in 0..10 {
+        simulation.run_tick().await?;
+    }
+
+    assert_eq!(simulation.tick, 10);
+    assert_eq!(simulation.jobs_processed(), 10);
+
+    let counters = server.services.counters();
+    for handled in [&counters.routed, &counters.auctions, &counters.released, &counters.executed] {
+        assert_eq!(handled.load(Ordering::SeqCst), 10);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_ticks_count_jobs() -> Result<()> {
+    let (_server, endpoints) = start_mock_services().await?;
+
+    for concurrency in [1, 3, 10, 16] {
+        let mut simulation = Simulation::connect(&endpoints, Some(7)).await?;
+        simulation.run_ticks(10, concurrency).await?;
+
+        assert_eq!(simulation.tick, 10);
+        assert_eq!(simulation.jobs_processed(), 10, "concurrency {}", concurrency);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tick_recovers_after_server_restart() -> Result<()> {
+    let (server, endpoints) = start_mock_services().await?;
+    let mut simulation = Simulation::connect(&endpoints, Some(7)).await?;
+    simulation.run_tick().await?;
+
+    // Restart the daemons on the same address
+    let local_addr = server.local_addr;
+    drop(server);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let restarted = spawn_mock_server_on(local_addr).await?;
+
+    simulation.run_tick().await?;
+    assert_eq!(simulation.jobs_processed(), 2);
+    assert_eq!(restarted.services.counters().executed.load(Ordering::SeqCst), 1);
+
+    let status = simulation.status().await;
+    assert!(status.contains(&format!("Reconnects: {}", simulation.reconnects())), "{}", status);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tick_resumes_at_the_failed_stage() -> Result<()> {
+    let router = spawn_mock_server().await?;
+    let auction = spawn_mock_server().await?;
+    let runtime = spawn_mock_server().await?;
+    let endpoints = Endpoints {
+        router: router.addr.clone(),
+        auction: auction.addr.clone(),
+        runtime: runtime.addr.clone(),
+    };
+    let mut simulation = Simulation::connect(&endpoints, Some(7)).await?;
+    simulation.run_tick().await?;
+
+    // Only the runtime goes down, coming back while the tick is in flight;
+    // routing and the auction must not be repeated
+    let local_addr = runtime.local_addr;
+    drop(runtime);
+    let restart = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        spawn_mock_server_on(local_addr).await
+    });
+
+    simulation.run_tick().await?;
+    let restarted = restart.await??;
+    assert_eq!(simulation.reconnects(), 1);
+    assert_eq!(router.services.counters().routed.load(Ordering::SeqCst), 2);
+    let counters = auction.services.counters();
+    assert_eq!(counters.auctions.load(Ordering::SeqCst), 2);
+    assert_eq!(counters.released.load(Ordering::SeqCst), 2);
+    assert_eq!(restarted.services.counters().executed.load(Ordering::SeqCst), 1);
+    Ok(())
+}